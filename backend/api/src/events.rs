@@ -0,0 +1,163 @@
+use custom_error::ServiceError;
+use database::DatabaseService;
+use futures::StreamExt;
+use models::{
+    SyncState,
+    live::{Feed, PushedEvent},
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+use tokio::{
+    sync::broadcast,
+    time::{interval, sleep},
+};
+use tracing::{error, info, warn};
+
+/// Messages a slow client may fall behind before it reads the missed records from the database
+const EVENT_BUS_CAPACITY: usize = 4096;
+const RESTART_DELAY: Duration = Duration::from_secs(5);
+/// Checkpoints are read again this often in case a live notification was missed
+const CHECKPOINT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Most blocks read at once, an indexer catching up is pushed page by page
+const MAX_PUSH_BLOCKS: u64 = 50;
+
+#[derive(Debug, Clone)]
+pub enum BusMessage {
+    Record(PushedEvent),
+    /// The blocks of `feed` above `block_number` were replaced by a reorganization,
+    /// their new records follow
+    Rewind {
+        feed: Feed,
+        block_number: u64,
+    },
+}
+
+/// Pushes the indexed records into a channel shared by every WebSocket connection, so
+/// the database is followed once for all clients. Records are read from the database
+/// when an indexer checkpoint moves, so each one is sent once, in block and index order
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<BusMessage>,
+    /// Highest block of each feed whose records have been sent
+    heights: Arc<RwLock<HashMap<Feed, u64>>>,
+}
+
+impl EventBus {
+    pub fn subscribe(&self) -> broadcast::Receiver<BusMessage> {
+        self.sender.subscribe()
+    }
+
+    /// Records up to this block were sent before any message received after this call
+    pub fn height(&self, feed: Feed) -> Option<u64> {
+        self.heights
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&feed)
+            .copied()
+    }
+
+    fn set_height(&self, feed: Feed, block_number: u64) {
+        self.heights
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(feed, block_number);
+    }
+
+    /// Sends the records between the pushed height of each feed following `state` and
+    /// its new checkpoint, lowest first
+    async fn advance(&self, db: &DatabaseService, state: &SyncState) -> Result<(), ServiceError> {
+        for feed in Feed::ALL
+            .into_iter()
+            .filter(|feed| feed.indexer() == state.indexer)
+        {
+            let Some(height) = feed.height(state) else {
+                continue;
+            };
+            // Blocks indexed before the first start are history and not pushed
+            let Some(pushed) = self.height(feed) else {
+                self.set_height(feed, height);
+                continue;
+            };
+
+            if height < pushed {
+                self.set_height(feed, height);
+                let _ = self.sender.send(BusMessage::Rewind {
+                    feed,
+                    block_number: height,
+                });
+                continue;
+            }
+
+            // The height moves before the page is sent, a connection reading it in between
+            // receives the page and skips the records it already read
+            let mut from = pushed + 1;
+            while from <= height {
+                let to = height.min(from + MAX_PUSH_BLOCKS - 1);
+                let records = db.live().records(feed, from, to).await?;
+
+                self.set_height(feed, to);
+                for record in records {
+                    // Sending only fails while no client is connected
+                    let _ = self.sender.send(BusMessage::Record(record));
+                }
+
+                from = to + 1;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn spawn_event_bus(db: DatabaseService) -> EventBus {
+    let (sender, _) = broadcast::channel(EVENT_BUS_CAPACITY);
+    let bus = EventBus {
+        sender,
+        heights: Arc::new(RwLock::new(HashMap::new())),
+    };
+    let pushing = bus.clone();
+
+    tokio::spawn(async move {
+        // Heights are kept across restarts, so the blocks indexed meanwhile are still pushed
+        loop {
+            match run_event_bus(&db, &pushing).await {
+                Ok(()) => warn!("Event bus live query ended"),
+                Err(e) => error!("Event bus failed: {}", e),
+            }
+
+            sleep(RESTART_DELAY).await;
+        }
+    });
+
+    bus
+}
+
+async fn run_event_bus(db: &DatabaseService, bus: &EventBus) -> Result<(), ServiceError> {
+    let mut sync_states = db.live().sync_states().await?;
+    // The first tick completes at once and reads the checkpoints at start
+    let mut poll = interval(CHECKPOINT_POLL_INTERVAL);
+
+    info!("Event bus following indexer checkpoints");
+
+    loop {
+        tokio::select! {
+            state = sync_states.next() => match state {
+                Some(Ok(state)) => {
+                    if let Err(e) = bus.advance(db, &state).await {
+                        warn!("Failed to push {} records: {}", state.indexer, e);
+                    }
+                }
+                Some(Err(e)) => warn!("Event bus sync state error: {}", e),
+                None => return Ok(()),
+            },
+            _ = poll.tick() => {
+                for state in db.sync_state().get_all().await? {
+                    bus.advance(db, &state).await?;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,456 @@
+use async_graphql::{Context, Enum, InputObject, Object, connection::Connection};
+use models::{
+    AccountInfo,
+    activity::Cursor,
+    evm::{
+        EvmAddressTxFilter, EvmBlock, EvmContract, EvmTransaction, TransactionDirection,
+        TransactionMethod,
+    },
+};
+
+use super::{
+    connection, data_loader, database_error, db,
+    loader::{AccountAddress, ContractAddress, TransactionHash},
+    page_complexity, page_size, parse_after,
+};
+
+/// Side of a transaction an address is on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum Direction {
+    All,
+    Sent,
+    Received,
+}
+
+impl From<Direction> for TransactionDirection {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::All => TransactionDirection::All,
+            Direction::Sent => TransactionDirection::Sent,
+            Direction::Received => TransactionDirection::Received,
+        }
+    }
+}
+
+/// Filter of the transactions of an account, `method` is a function name or a 4-byte selector
+#[derive(Debug, Clone, Default, InputObject)]
+pub struct EvmTransactionFilter {
+    pub direction: Option<Direction>,
+    pub method: Option<String>,
+    /// Unix timestamp in seconds
+    pub from_timestamp: Option<u64>,
+    /// Unix timestamp in seconds
+    pub to_timestamp: Option<u64>,
+}
+
+impl From<EvmTransactionFilter> for EvmAddressTxFilter {
+    fn from(filter: EvmTransactionFilter) -> Self {
+        Self {
+            direction: filter.direction.map(Into::into).unwrap_or_default(),
+            method: filter.method,
+            from_timestamp: filter.from_timestamp.map(u128::from),
+            to_timestamp: filter.to_timestamp.map(u128::from),
+        }
+    }
+}
+
+/// Transactions sent or received by an address as a newest-first connection
+pub(super) async fn address_transactions(
+    ctx: &Context<'_>,
+    address: &str,
+    filter: Option<EvmTransactionFilter>,
+    first: Option<i32>,
+    after: Option<String>,
+) -> async_graphql::Result<Connection<String, EvmTransactionObject>> {
+    let limit = page_size(first)?;
+    let cursor = parse_after(after, Cursor::<String>::parse)?;
+
+    let transactions = db(ctx)
+        .transactions()
+        .get_by_address(
+            address,
+            &filter.unwrap_or_default().into(),
+            cursor.as_ref(),
+            limit + 1,
+        )
+        .await
+        .map_err(database_error)?;
+
+    Ok(connection(
+        transactions,
+        limit,
+        cursor.is_some(),
+        |tx| Cursor::new(tx.timestamp, tx.hash.clone()).encode(),
+        EvmTransactionObject,
+    ))
+}
+
+pub struct EvmBlockObject(pub EvmBlock);
+
+#[Object(name = "EvmBlock")]
+impl EvmBlockObject {
+    async fn number(&self) -> u32 {
+        self.0.number
+    }
+
+    async fn hash(&self) -> &str {
+        &self.0.hash
+    }
+
+    async fn parent_hash(&self) -> &str {
+        &self.0.parent_hash
+    }
+
+    /// Unix timestamp in seconds
+    async fn timestamp(&self) -> u64 {
+        self.0.timestamp as u64
+    }
+
+    async fn transaction_count(&self) -> u16 {
+        self.0.transaction_count
+    }
+
+    async fn size(&self) -> u64 {
+        self.0.size as u64
+    }
+
+    async fn gas_used(&self) -> u64 {
+        self.0.gas_used
+    }
+
+    async fn gas_limit(&self) -> u64 {
+        self.0.gas_limit
+    }
+
+    async fn base_fee(&self) -> u64 {
+        self.0.base_fee
+    }
+
+    /// Burnt fees in wei, decimal string
+    async fn burn_fee(&self) -> &str {
+        &self.0.burn_fee
+    }
+
+    async fn validator(&self) -> &str {
+        &self.0.validator
+    }
+
+    async fn extra_data(&self) -> &str {
+        &self.0.extra_data
+    }
+
+    async fn session(&self) -> u32 {
+        self.0.session
+    }
+
+    async fn era(&self) -> u32 {
+        self.0.era
+    }
+
+    /// Transactions of the block ordered by hash
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> async_graphql::Result<Connection<String, EvmTransactionObject>> {
+        let limit = page_size(first)?;
+
+        let transactions = db(ctx)
+            .transactions()
+            .get_by_block_page(self.0.number, after.as_deref(), limit + 1)
+            .await
+            .map_err(database_error)?;
+
+        Ok(connection(
+            transactions,
+            limit,
+            after.is_some(),
+            |tx| tx.hash.clone(),
+            EvmTransactionObject,
+        ))
+    }
+}
+
+pub struct EvmTransactionObject(pub EvmTransaction);
+
+#[Object(name = "EvmTransaction")]
+impl EvmTransactionObject {
+    async fn hash(&self) -> &str {
+        &self.0.hash
+    }
+
+    async fn block_number(&self) -> u64 {
+        self.0.block_number
+    }
+
+    /// Unix timestamp in seconds
+    async fn timestamp(&self) -> u64 {
+        self.0.timestamp as u64
+    }
+
+    async fn from(&self) -> &str {
+        &self.0.from
+    }
+
+    async fn to(&self) -> Option<&str> {
+        self.0.to.as_deref()
+    }
+
+    /// Wei, decimal string
+    async fn value(&self) -> &str {
+        &self.0.value
+    }
+
+    async fn gas_price(&self) -> u64 {
+        self.0.gas_price
+    }
+
+    async fn gas_limit(&self) -> u64 {
+        self.0.gas_limit
+    }
+
+    async fn gas_used(&self) -> u64 {
+        self.0.gas_used
+    }
+
+    async fn nonce(&self) -> u64 {
+        self.0.nonce
+    }
+
+    /// `Success`, `Failed` or `Pending`
+    async fn status(&self) -> String {
+        format!("{:?}", self.0.status)
+    }
+
+    /// `Legacy`, `AccessList` or `DynamicFee`
+    async fn transaction_type(&self) -> String {
+        format!("{:?}", self.0.transaction_type)
+    }
+
+    /// Wei, decimal string
+    async fn fee(&self) -> &str {
+        &self.0.fee
+    }
+
+    /// Function called, from the verified ABI or the signature database
+    async fn method(&self) -> Option<String> {
+        if let Some(call) = &self.0.decoded_input {
+            return Some(call.name.clone());
+        }
+
+        match &self.0.transaction_method {
+            Some(TransactionMethod::ContractCall { function_name, .. }) => function_name.clone(),
+            _ => None,
+        }
+    }
+
+    /// Decoded call parameters, only for calls to verified contracts
+    async fn decoded_input(&self) -> Option<async_graphql::Json<serde_json::Value>> {
+        self.0
+            .decoded_input
+            .as_ref()
+            .map(|call| async_graphql::Json(call.params.clone()))
+    }
+
+    async fn block(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<EvmBlockObject>> {
+        let block = data_loader(ctx)
+            .load_one(self.0.block_number as u32)
+            .await?;
+
+        Ok(block.map(EvmBlockObject))
+    }
+
+    async fn from_account(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Option<AccountObject>> {
+        account(ctx, &self.0.from).await
+    }
+
+    async fn to_account(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<AccountObject>> {
+        match &self.0.to {
+            Some(to) => account(ctx, to).await,
+            None => Ok(None),
+        }
+    }
+}
+
+/// EVM addresses are stored lowercase, SS58 addresses as they are
+pub(super) async fn account(
+    ctx: &Context<'_>,
+    address: &str,
+) -> async_graphql::Result<Option<AccountObject>> {
+    let address = if address.starts_with("0x") {
+        address.to_lowercase()
+    } else {
+        address.to_string()
+    };
+
+    let account = data_loader(ctx).load_one(AccountAddress(address)).await?;
+
+    Ok(account.map(AccountObject))
+}
+
+pub(super) async fn contract(
+    ctx: &Context<'_>,
+    address: &str,
+) -> async_graphql::Result<Option<EvmContractObject>> {
+    let contract = data_loader(ctx)
+        .load_one(ContractAddress(address.to_lowercase()))
+        .await?;
+
+    Ok(contract.map(EvmContractObject))
+}
+
+pub struct AccountObject(pub AccountInfo);
+
+#[Object(name = "AccountInfo")]
+impl AccountObject {
+    async fn address(&self) -> &str {
+        &self.0.address
+    }
+
+    /// Exact decimal amount in token units
+    async fn balance(&self) -> &str {
+        &self.0.balance_token
+    }
+
+    async fn free_balance(&self) -> &str {
+        &self.0.free_balance
+    }
+
+    async fn nonce(&self) -> u64 {
+        self.0.nonce
+    }
+
+    async fn is_contract(&self) -> bool {
+        self.0.is_contract
+    }
+
+    /// `H160` or `SS58`
+    async fn address_type(&self) -> String {
+        format!("{:?}", self.0.address_type)
+    }
+
+    async fn created_at(&self) -> u64 {
+        self.0.created_at as u64
+    }
+
+    async fn last_activity(&self) -> u64 {
+        self.0.last_activity as u64
+    }
+
+    /// Contract deployed at the address
+    async fn contract(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Option<EvmContractObject>> {
+        if !self.0.is_contract {
+            return Ok(None);
+        }
+
+        contract(ctx, &self.0.address).await
+    }
+
+    /// Transactions sent or received by the account, newest first
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<EvmTransactionFilter>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> async_graphql::Result<Connection<String, EvmTransactionObject>> {
+        address_transactions(ctx, &self.0.address, filter, first, after).await
+    }
+}
+
+pub struct EvmContractObject(pub EvmContract);
+
+#[Object(name = "EvmContract")]
+impl EvmContractObject {
+    async fn address(&self) -> &str {
+        &self.0.address
+    }
+
+    /// `ERC20`, `ERC721`, `ERC1155`, `DEX`, `LendingProtocol`, `Proxy`, `Oracle` or `Unknown`
+    async fn contract_type(&self) -> String {
+        format!("{:?}", self.0.contract_type)
+    }
+
+    async fn name(&self) -> Option<&str> {
+        self.0.name.as_deref()
+    }
+
+    async fn symbol(&self) -> Option<&str> {
+        self.0.symbol.as_deref()
+    }
+
+    async fn decimals(&self) -> Option<u8> {
+        self.0.decimals
+    }
+
+    async fn total_supply(&self) -> Option<&str> {
+        self.0.total_supply.as_deref()
+    }
+
+    async fn is_verified(&self) -> bool {
+        self.0.is_verified
+    }
+
+    async fn creator(&self) -> Option<&str> {
+        self.0
+            .creator_info
+            .as_ref()
+            .and_then(|info| info.creator_address.as_deref())
+    }
+
+    async fn creation_block_number(&self) -> Option<u64> {
+        self.0.creator_info.as_ref().map(|info| info.block_number)
+    }
+
+    /// Proxy standard, when the contract is a proxy
+    async fn proxy_kind(&self) -> Option<String> {
+        self.0
+            .proxy
+            .as_ref()
+            .map(|proxy| format!("{:?}", proxy.kind))
+    }
+
+    async fn account(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<AccountObject>> {
+        account(ctx, &self.0.address).await
+    }
+
+    async fn creation_transaction(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Option<EvmTransactionObject>> {
+        let Some(hash) = self
+            .0
+            .creator_info
+            .as_ref()
+            .and_then(|info| info.transaction_hash.as_deref())
+        else {
+            return Ok(None);
+        };
+
+        let transaction = data_loader(ctx)
+            .load_one(TransactionHash(hash.to_string()))
+            .await?;
+
+        Ok(transaction.map(EvmTransactionObject))
+    }
+
+    /// Contract a proxy currently delegates to
+    async fn implementation(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Option<EvmContractObject>> {
+        match &self.0.proxy {
+            Some(proxy) => contract(ctx, &proxy.implementation).await,
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use async_graphql::dataloader::Loader;
+use database::DatabaseService;
+use models::{
+    AccountInfo,
+    activity::SubstrateKey,
+    evm::{EvmBlock, EvmContract, EvmTransaction},
+    substrate::{SubstrateBlock, SubstrateExtrinsic},
+};
+use std::collections::HashMap;
+
+use super::database_error;
+
+/// Batches the lookups of nested fields, so a page of transactions loads their blocks,
+/// accounts and contracts in one query each instead of one per transaction
+pub struct DbLoader(pub DatabaseService);
+
+impl Loader<u32> for DbLoader {
+    type Value = EvmBlock;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, numbers: &[u32]) -> Result<HashMap<u32, EvmBlock>, Self::Error> {
+        let blocks = self
+            .0
+            .evm_blocks()
+            .get_by_numbers(numbers)
+            .await
+            .map_err(database_error)?;
+
+        Ok(blocks
+            .into_iter()
+            .map(|block| (block.number, block))
+            .collect())
+    }
+}
+
+/// Substrate block numbers, kept apart from the EVM ones that share the `u32` key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubstrateBlockNumber(pub u32);
+
+impl Loader<SubstrateBlockNumber> for DbLoader {
+    type Value = SubstrateBlock;
+    type Error = async_graphql::Error;
+
+    async fn load(
+        &self,
+        numbers: &[SubstrateBlockNumber],
+    ) -> Result<HashMap<SubstrateBlockNumber, SubstrateBlock>, Self::Error> {
+        let numbers: Vec<u32> = numbers.iter().map(|number| number.0).collect();
+        let blocks = self
+            .0
+            .substrate_blocks()
+            .get_by_numbers(&numbers)
+            .await
+            .map_err(database_error)?;
+
+        Ok(blocks
+            .into_iter()
+            .map(|block| (SubstrateBlockNumber(block.number), block))
+            .collect())
+    }
+}
+
+impl Loader<SubstrateKey> for DbLoader {
+    type Value = SubstrateExtrinsic;
+    type Error = async_graphql::Error;
+
+    async fn load(
+        &self,
+        keys: &[SubstrateKey],
+    ) -> Result<HashMap<SubstrateKey, SubstrateExtrinsic>, Self::Error> {
+        let extrinsics = self
+            .0
+            .substrate_extrinsics()
+            .get_by_keys(keys)
+            .await
+            .map_err(database_error)?;
+
+        Ok(extrinsics
+            .into_iter()
+            .map(|extrinsic| {
+                (
+                    (extrinsic.block_number, extrinsic.extrinsic_index),
+                    extrinsic,
+                )
+            })
+            .collect())
+    }
+}
+
+/// Account address, lowercase for EVM addresses
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AccountAddress(pub String);
+
+impl Loader<AccountAddress> for DbLoader {
+    type Value = AccountInfo;
+    type Error = async_graphql::Error;
+
+    async fn load(
+        &self,
+        addresses: &[AccountAddress],
+    ) -> Result<HashMap<AccountAddress, AccountInfo>, Self::Error> {
+        let addresses: Vec<String> = addresses.iter().map(|a| a.0.clone()).collect();
+        let accounts = self
+            .0
+            .accounts()
+            .get_by_addresses(&addresses)
+            .await
+            .map_err(database_error)?;
+
+        Ok(accounts
+            .into_iter()
+            .map(|account| (AccountAddress(account.address.clone()), account))
+            .collect())
+    }
+}
+
+/// Lowercase contract address
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContractAddress(pub String);
+
+impl Loader<ContractAddress> for DbLoader {
+    type Value = EvmContract;
+    type Error = async_graphql::Error;
+
+    async fn load(
+        &self,
+        addresses: &[ContractAddress],
+    ) -> Result<HashMap<ContractAddress, EvmContract>, Self::Error> {
+        let addresses: Vec<String> = addresses.iter().map(|a| a.0.clone()).collect();
+        let contracts = self
+            .0
+            .contracts()
+            .get_by_addresses(&addresses)
+            .await
+            .map_err(database_error)?;
+
+        Ok(contracts
+            .into_iter()
+            .map(|contract| (ContractAddress(contract.address.clone()), contract))
+            .collect())
+    }
+}
+
+/// Transaction hash
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TransactionHash(pub String);
+
+impl Loader<TransactionHash> for DbLoader {
+    type Value = EvmTransaction;
+    type Error = async_graphql::Error;
+
+    async fn load(
+        &self,
+        hashes: &[TransactionHash],
+    ) -> Result<HashMap<TransactionHash, EvmTransaction>, Self::Error> {
+        let hashes: Vec<String> = hashes.iter().map(|hash| hash.0.clone()).collect();
+        let transactions = self
+            .0
+            .transactions()
+            .get_by_hashes(&hashes)
+            .await
+            .map_err(database_error)?;
+
+        Ok(transactions
+            .into_iter()
+            .map(|transaction| (TransactionHash(transaction.hash.clone()), transaction))
+            .collect())
+    }
+}
@@ -0,0 +1,147 @@
+// backend/api/src/graphql/mod.rs
+mod evm;
+mod loader;
+mod query;
+mod substrate;
+
+use async_graphql::{
+    Context, EmptyMutation, EmptySubscription, OutputType, Schema,
+    connection::{Connection, Edge},
+    dataloader::DataLoader,
+    http::GraphiQLSource,
+};
+use axum::{Json, extract::State, response::Html};
+use custom_error::ServiceError;
+use database::DatabaseService;
+use std::sync::Arc;
+
+use crate::{AppState, handlers::cursor_page};
+use loader::DbLoader;
+use query::QueryRoot;
+
+/// Deepest selection a query may nest, enough for block → transactions → account → contract → ...
+const MAX_DEPTH: usize = 12;
+/// Highest estimated cost of a query, connections count their page size times their selection
+const MAX_COMPLEXITY: usize = 2000;
+const DEFAULT_PAGE_SIZE: u32 = 20;
+const MAX_PAGE_SIZE: u32 = 100;
+
+pub type ExplorerSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+pub fn build_schema(db: DatabaseService) -> ExplorerSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(DataLoader::new(DbLoader(db.clone()), tokio::spawn))
+        .data(db)
+        .limit_depth(MAX_DEPTH)
+        .limit_complexity(MAX_COMPLEXITY)
+        .finish()
+}
+
+pub async fn graphql_handler(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    Json(state.graphql.execute(request).await)
+}
+
+pub async fn graphiql() -> Html<String> {
+    Html(GraphiQLSource::build().endpoint("/graphql").finish())
+}
+
+fn db<'a>(ctx: &Context<'a>) -> &'a DatabaseService {
+    ctx.data_unchecked::<DatabaseService>()
+}
+
+fn data_loader<'a>(ctx: &Context<'a>) -> &'a DataLoader<DbLoader> {
+    ctx.data_unchecked::<DataLoader<DbLoader>>()
+}
+
+/// Database failures are logged and reported without their details, like the REST handlers do
+fn database_error(e: ServiceError) -> async_graphql::Error {
+    eprintln!("Error resolving GraphQL query: {:?}", e);
+    async_graphql::Error::new("Internal server error")
+}
+
+fn page_size(first: Option<i32>) -> async_graphql::Result<u32> {
+    match first {
+        None => Ok(DEFAULT_PAGE_SIZE),
+        Some(first) if first >= 1 && first as u32 <= MAX_PAGE_SIZE => Ok(first as u32),
+        Some(_) => Err(async_graphql::Error::new(format!(
+            "first must be between 1 and {}",
+            MAX_PAGE_SIZE
+        ))),
+    }
+}
+
+/// Cost of a connection field, one selection per requested item
+fn page_complexity(first: Option<i32>, child_complexity: usize) -> usize {
+    first
+        .map(|first| first.clamp(1, MAX_PAGE_SIZE as i32) as usize)
+        .unwrap_or(DEFAULT_PAGE_SIZE as usize)
+        * child_complexity
+}
+
+fn parse_after<C>(
+    after: Option<String>,
+    parse: impl Fn(&str) -> Option<C>,
+) -> async_graphql::Result<Option<C>> {
+    after
+        .map(|after| parse(&after).ok_or_else(|| async_graphql::Error::new("Invalid cursor")))
+        .transpose()
+}
+
+/// Builds a forward page from up to `limit + 1` items, the extra one only tells that more exist
+fn connection<T, N: OutputType>(
+    items: Vec<T>,
+    limit: u32,
+    has_previous_page: bool,
+    cursor: impl Fn(&T) -> String,
+    node: impl Fn(T) -> N,
+) -> Connection<String, N> {
+    let page = cursor_page(items, limit, &cursor);
+
+    let mut connection = Connection::new(has_previous_page, page.next_cursor.is_some());
+    connection.edges.extend(
+        page.items
+            .into_iter()
+            .map(|item| Edge::new(cursor(&item), node(item))),
+    );
+
+    connection
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_size_is_bounded() {
+        assert_eq!(page_size(None).unwrap(), DEFAULT_PAGE_SIZE);
+        assert_eq!(page_size(Some(5)).unwrap(), 5);
+        assert!(page_size(Some(0)).is_err());
+        assert!(page_size(Some(MAX_PAGE_SIZE as i32 + 1)).is_err());
+    }
+
+    #[test]
+    fn page_complexity_counts_every_item() {
+        assert_eq!(page_complexity(None, 3), DEFAULT_PAGE_SIZE as usize * 3);
+        assert_eq!(page_complexity(Some(10), 3), 30);
+        // Invalid sizes are rejected when resolved, they still cost the largest page
+        assert_eq!(page_complexity(Some(1000), 1), MAX_PAGE_SIZE as usize);
+    }
+
+    #[test]
+    fn connection_tells_whether_more_exist() {
+        let page: Connection<String, u32> =
+            connection(vec![1, 2, 3], 2, false, |n| n.to_string(), |n| n);
+        assert!(page.has_next_page);
+        assert!(!page.has_previous_page);
+        assert_eq!(page.edges.len(), 2);
+        assert_eq!(page.edges[1].node, 2);
+
+        let page: Connection<String, u32> =
+            connection(vec![1, 2], 2, true, |n| n.to_string(), |n| n);
+        assert!(!page.has_next_page);
+        assert!(page.has_previous_page);
+    }
+}
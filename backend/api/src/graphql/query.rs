@@ -0,0 +1,200 @@
+use async_graphql::{Context, Object, connection::Connection};
+
+use super::{
+    connection, database_error, db,
+    evm::{
+        AccountObject, EvmBlockObject, EvmContractObject, EvmTransactionFilter,
+        EvmTransactionObject, account, address_transactions, contract,
+    },
+    page_complexity, page_size, parse_after,
+    substrate::{
+        SubstrateBlockObject, SubstrateEventObject, SubstrateExtrinsicObject, SubstrateFilter,
+        account_events, account_extrinsics, block, extrinsic,
+    },
+};
+
+fn number_or_hash<T>(number: &Option<T>, hash: &Option<String>) -> async_graphql::Result<()> {
+    match (number, hash) {
+        (Some(_), None) | (None, Some(_)) => Ok(()),
+        _ => Err(async_graphql::Error::new(
+            "Exactly one of number or hash is required",
+        )),
+    }
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn evm_block(
+        &self,
+        ctx: &Context<'_>,
+        number: Option<u32>,
+        hash: Option<String>,
+    ) -> async_graphql::Result<Option<EvmBlockObject>> {
+        number_or_hash(&number, &hash)?;
+        let block_service = db(ctx).evm_blocks();
+
+        let block = match (number, hash) {
+            (Some(number), _) => block_service.get_by_number(number).await,
+            (_, Some(hash)) => block_service.get_by_hash(&hash.to_lowercase()).await,
+            _ => Ok(None),
+        }
+        .map_err(database_error)?;
+
+        Ok(block.map(EvmBlockObject))
+    }
+
+    /// EVM blocks, highest first
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn evm_blocks(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> async_graphql::Result<Connection<String, EvmBlockObject>> {
+        let limit = page_size(first)?;
+        let before = parse_after(after, |cursor| cursor.parse::<u32>().ok())?;
+
+        let blocks = db(ctx)
+            .evm_blocks()
+            .get_before(before, limit + 1)
+            .await
+            .map_err(database_error)?;
+
+        Ok(connection(
+            blocks,
+            limit,
+            before.is_some(),
+            |block| block.number.to_string(),
+            EvmBlockObject,
+        ))
+    }
+
+    async fn evm_transaction(
+        &self,
+        ctx: &Context<'_>,
+        hash: String,
+    ) -> async_graphql::Result<Option<EvmTransactionObject>> {
+        let transaction = db(ctx)
+            .transactions()
+            .get_by_hash(&hash.to_lowercase())
+            .await
+            .map_err(database_error)?;
+
+        Ok(transaction.map(EvmTransactionObject))
+    }
+
+    /// Transactions sent or received by an address, newest first
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn evm_transactions(
+        &self,
+        ctx: &Context<'_>,
+        address: String,
+        filter: Option<EvmTransactionFilter>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> async_graphql::Result<Connection<String, EvmTransactionObject>> {
+        address_transactions(ctx, &address, filter, first, after).await
+    }
+
+    async fn account(
+        &self,
+        ctx: &Context<'_>,
+        address: String,
+    ) -> async_graphql::Result<Option<AccountObject>> {
+        account(ctx, &address).await
+    }
+
+    async fn contract(
+        &self,
+        ctx: &Context<'_>,
+        address: String,
+    ) -> async_graphql::Result<Option<EvmContractObject>> {
+        contract(ctx, &address).await
+    }
+
+    async fn substrate_block(
+        &self,
+        ctx: &Context<'_>,
+        number: Option<u32>,
+        hash: Option<String>,
+    ) -> async_graphql::Result<Option<SubstrateBlockObject>> {
+        number_or_hash(&number, &hash)?;
+
+        match (number, hash) {
+            (Some(number), _) => block(ctx, number).await,
+            (_, Some(hash)) => {
+                let block = db(ctx)
+                    .substrate_blocks()
+                    .get_by_hash(&hash)
+                    .await
+                    .map_err(database_error)?;
+
+                Ok(block.map(SubstrateBlockObject))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Substrate blocks, highest first
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn substrate_blocks(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> async_graphql::Result<Connection<String, SubstrateBlockObject>> {
+        let limit = page_size(first)?;
+        let before = parse_after(after, |cursor| cursor.parse::<u32>().ok())?;
+
+        let blocks = db(ctx)
+            .substrate_blocks()
+            .get_before(before, limit + 1)
+            .await
+            .map_err(database_error)?;
+
+        Ok(connection(
+            blocks,
+            limit,
+            before.is_some(),
+            |block| block.number.to_string(),
+            SubstrateBlockObject,
+        ))
+    }
+
+    async fn substrate_extrinsic(
+        &self,
+        ctx: &Context<'_>,
+        block_number: u32,
+        extrinsic_index: u32,
+    ) -> async_graphql::Result<Option<SubstrateExtrinsicObject>> {
+        extrinsic(ctx, block_number, extrinsic_index).await
+    }
+
+    /// Extrinsics signed by an account, newest first
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn substrate_extrinsics(
+        &self,
+        ctx: &Context<'_>,
+        account: String,
+        filter: Option<SubstrateFilter>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> async_graphql::Result<Connection<String, SubstrateExtrinsicObject>> {
+        account_extrinsics(ctx, &account, filter, first, after).await
+    }
+
+    /// Events that mention an account, newest first
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn substrate_events(
+        &self,
+        ctx: &Context<'_>,
+        account: String,
+        filter: Option<SubstrateFilter>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> async_graphql::Result<Connection<String, SubstrateEventObject>> {
+        account_events(ctx, &account, filter, first, after).await
+    }
+}
@@ -0,0 +1,403 @@
+use async_graphql::{Context, InputObject, Object, connection::Connection};
+use models::{
+    activity::{Cursor, SubstrateKey},
+    substrate::{SubstrateAccountFilter, SubstrateBlock, SubstrateEvent, SubstrateExtrinsic},
+};
+
+use super::{
+    connection, data_loader, database_error, db, loader::SubstrateBlockNumber, page_complexity,
+    page_size, parse_after,
+};
+
+/// Filter of the extrinsics or events of an account, `name` is the call or event name
+#[derive(Debug, Clone, Default, InputObject)]
+pub struct SubstrateFilter {
+    pub pallet: Option<String>,
+    pub name: Option<String>,
+    /// Unix timestamp in milliseconds
+    pub from_timestamp: Option<u64>,
+    /// Unix timestamp in milliseconds
+    pub to_timestamp: Option<u64>,
+}
+
+impl From<SubstrateFilter> for SubstrateAccountFilter {
+    fn from(filter: SubstrateFilter) -> Self {
+        Self {
+            module: filter.pallet,
+            name: filter.name,
+            from_timestamp: filter.from_timestamp,
+            to_timestamp: filter.to_timestamp,
+        }
+    }
+}
+
+/// Extrinsics signed by an account as a newest-first connection
+pub(super) async fn account_extrinsics(
+    ctx: &Context<'_>,
+    account: &str,
+    filter: Option<SubstrateFilter>,
+    first: Option<i32>,
+    after: Option<String>,
+) -> async_graphql::Result<Connection<String, SubstrateExtrinsicObject>> {
+    let limit = page_size(first)?;
+    let cursor = parse_after(after, Cursor::<SubstrateKey>::parse)?;
+
+    let extrinsics = db(ctx)
+        .substrate_extrinsics()
+        .get_by_account(
+            account,
+            &filter.unwrap_or_default().into(),
+            cursor.as_ref(),
+            limit + 1,
+        )
+        .await
+        .map_err(database_error)?;
+
+    Ok(connection(
+        extrinsics,
+        limit,
+        cursor.is_some(),
+        |extrinsic| {
+            Cursor::new(
+                extrinsic.timestamp as u128,
+                (extrinsic.block_number, extrinsic.extrinsic_index),
+            )
+            .encode()
+        },
+        SubstrateExtrinsicObject,
+    ))
+}
+
+/// Events that mention an account as a newest-first connection
+pub(super) async fn account_events(
+    ctx: &Context<'_>,
+    account: &str,
+    filter: Option<SubstrateFilter>,
+    first: Option<i32>,
+    after: Option<String>,
+) -> async_graphql::Result<Connection<String, SubstrateEventObject>> {
+    let limit = page_size(first)?;
+    let cursor = parse_after(after, Cursor::<SubstrateKey>::parse)?;
+
+    let events = db(ctx)
+        .substrate_events()
+        .get_by_account(
+            account,
+            &filter.unwrap_or_default().into(),
+            cursor.as_ref(),
+            limit + 1,
+        )
+        .await
+        .map_err(database_error)?;
+
+    Ok(connection(
+        events,
+        limit,
+        cursor.is_some(),
+        |event| {
+            Cursor::new(
+                event.timestamp as u128,
+                (event.block_number, event.event_index),
+            )
+            .encode()
+        },
+        SubstrateEventObject,
+    ))
+}
+
+pub(super) async fn block(
+    ctx: &Context<'_>,
+    block_number: u32,
+) -> async_graphql::Result<Option<SubstrateBlockObject>> {
+    let block = data_loader(ctx)
+        .load_one(SubstrateBlockNumber(block_number))
+        .await?;
+
+    Ok(block.map(SubstrateBlockObject))
+}
+
+pub(super) async fn extrinsic(
+    ctx: &Context<'_>,
+    block_number: u32,
+    extrinsic_index: u32,
+) -> async_graphql::Result<Option<SubstrateExtrinsicObject>> {
+    let extrinsic = data_loader(ctx)
+        .load_one((block_number, extrinsic_index))
+        .await?;
+
+    Ok(extrinsic.map(SubstrateExtrinsicObject))
+}
+
+/// Events of a block, or of one of its extrinsics, in order
+async fn block_events(
+    ctx: &Context<'_>,
+    block_number: u32,
+    extrinsic_index: Option<u32>,
+    filter: SubstrateAccountFilter,
+    first: Option<i32>,
+    after: Option<String>,
+) -> async_graphql::Result<Connection<String, SubstrateEventObject>> {
+    let limit = page_size(first)?;
+    let after = parse_after(after, |cursor| cursor.parse::<u32>().ok())?;
+
+    let events = db(ctx)
+        .substrate_events()
+        .get_block_page(block_number, extrinsic_index, &filter, after, limit + 1)
+        .await
+        .map_err(database_error)?;
+
+    Ok(connection(
+        events,
+        limit,
+        after.is_some(),
+        |event| event.event_index.to_string(),
+        SubstrateEventObject,
+    ))
+}
+
+/// Pallet and name filter of the extrinsics or events of a block
+fn name_filter(pallet: Option<String>, name: Option<String>) -> SubstrateAccountFilter {
+    SubstrateAccountFilter {
+        module: pallet,
+        name,
+        ..Default::default()
+    }
+}
+
+pub struct SubstrateBlockObject(pub SubstrateBlock);
+
+#[Object(name = "SubstrateBlock")]
+impl SubstrateBlockObject {
+    async fn number(&self) -> u32 {
+        self.0.number
+    }
+
+    /// Unix timestamp in milliseconds
+    async fn timestamp(&self) -> u64 {
+        self.0.timestamp
+    }
+
+    async fn is_finalized(&self) -> bool {
+        self.0.is_finalize
+    }
+
+    async fn hash(&self) -> &str {
+        &self.0.hash
+    }
+
+    async fn parent_hash(&self) -> &str {
+        &self.0.parent_hash
+    }
+
+    async fn state_root(&self) -> &str {
+        &self.0.state_root
+    }
+
+    async fn extrinsics_root(&self) -> &str {
+        &self.0.extrinsics_root
+    }
+
+    async fn extrinsic_count(&self) -> u64 {
+        self.0.extrinscs_len as u64
+    }
+
+    async fn event_count(&self) -> u64 {
+        self.0.events_len as u64
+    }
+
+    /// Extrinsics of the block in order, pallet and call names ignore case
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn extrinsics(
+        &self,
+        ctx: &Context<'_>,
+        pallet: Option<String>,
+        name: Option<String>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> async_graphql::Result<Connection<String, SubstrateExtrinsicObject>> {
+        let limit = page_size(first)?;
+        let after = parse_after(after, |cursor| cursor.parse::<u32>().ok())?;
+
+        let extrinsics = db(ctx)
+            .substrate_extrinsics()
+            .get_block_page(self.0.number, &name_filter(pallet, name), after, limit + 1)
+            .await
+            .map_err(database_error)?;
+
+        Ok(connection(
+            extrinsics,
+            limit,
+            after.is_some(),
+            |extrinsic| extrinsic.extrinsic_index.to_string(),
+            SubstrateExtrinsicObject,
+        ))
+    }
+
+    /// Events of the block in order, pallet and event names ignore case
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn events(
+        &self,
+        ctx: &Context<'_>,
+        pallet: Option<String>,
+        name: Option<String>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> async_graphql::Result<Connection<String, SubstrateEventObject>> {
+        block_events(
+            ctx,
+            self.0.number,
+            None,
+            name_filter(pallet, name),
+            first,
+            after,
+        )
+        .await
+    }
+}
+
+pub struct SubstrateExtrinsicObject(pub SubstrateExtrinsic);
+
+#[Object(name = "SubstrateExtrinsic")]
+impl SubstrateExtrinsicObject {
+    async fn block_number(&self) -> u32 {
+        self.0.block_number
+    }
+
+    async fn extrinsic_index(&self) -> u32 {
+        self.0.extrinsic_index
+    }
+
+    async fn is_signed(&self) -> bool {
+        self.0.is_signed
+    }
+
+    async fn signer(&self) -> Option<&str> {
+        self.0.signer.as_deref()
+    }
+
+    async fn call_module(&self) -> &str {
+        &self.0.call_module
+    }
+
+    async fn call_function(&self) -> &str {
+        &self.0.call_function
+    }
+
+    async fn args(&self) -> async_graphql::Json<serde_json::Value> {
+        async_graphql::Json(self.0.args.clone())
+    }
+
+    async fn success(&self) -> bool {
+        self.0.success
+    }
+
+    async fn dispatch_error(&self) -> Option<&str> {
+        self.0.dispatch_error.as_deref()
+    }
+
+    /// Smallest unit, decimal string
+    async fn actual_fee(&self) -> Option<String> {
+        self.0.actual_fee.map(|fee| fee.to_string())
+    }
+
+    /// Smallest unit, decimal string
+    async fn tip(&self) -> Option<String> {
+        self.0.tip.map(|tip| tip.to_string())
+    }
+
+    async fn weight(&self) -> Option<u64> {
+        self.0.weight
+    }
+
+    /// Unix timestamp in milliseconds
+    async fn timestamp(&self) -> u64 {
+        self.0.timestamp
+    }
+
+    async fn block(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Option<SubstrateBlockObject>> {
+        block(ctx, self.0.block_number).await
+    }
+
+    /// Events emitted by the extrinsic in order
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
+    async fn events(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> async_graphql::Result<Connection<String, SubstrateEventObject>> {
+        block_events(
+            ctx,
+            self.0.block_number,
+            Some(self.0.extrinsic_index),
+            SubstrateAccountFilter::default(),
+            first,
+            after,
+        )
+        .await
+    }
+}
+
+pub struct SubstrateEventObject(pub SubstrateEvent);
+
+#[Object(name = "SubstrateEvent")]
+impl SubstrateEventObject {
+    async fn block_number(&self) -> u32 {
+        self.0.block_number
+    }
+
+    async fn event_index(&self) -> u32 {
+        self.0.event_index
+    }
+
+    async fn phase(&self) -> &str {
+        &self.0.phase
+    }
+
+    async fn extrinsic_index(&self) -> Option<u32> {
+        self.0.extrinsic_index
+    }
+
+    async fn module(&self) -> &str {
+        &self.0.module
+    }
+
+    async fn event(&self) -> &str {
+        &self.0.event
+    }
+
+    async fn data(&self) -> async_graphql::Json<serde_json::Value> {
+        async_graphql::Json(self.0.data.clone())
+    }
+
+    /// Unix timestamp in milliseconds
+    async fn timestamp(&self) -> u64 {
+        self.0.timestamp
+    }
+
+    /// SS58 accounts mentioned in `data`
+    async fn accounts(&self) -> &[String] {
+        &self.0.accounts
+    }
+
+    async fn block(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Option<SubstrateBlockObject>> {
+        block(ctx, self.0.block_number).await
+    }
+
+    /// Extrinsic that emitted the event, none for events of block initialization or finalization
+    async fn extrinsic(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Option<SubstrateExtrinsicObject>> {
+        match self.0.extrinsic_index {
+            Some(extrinsic_index) => extrinsic(ctx, self.0.block_number, extrinsic_index).await,
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use blockscan::{ethers::types::U256, evm_to_ss58, normalize_ss58, ss58_to_evm_address};
+use custom_error::ServiceError;
+use models::{
+    AccountInfo, AccountLink, LinkedAccount, NATIVE_DECIMALS,
+    activity::{AccountActivity, ActivityCursor, ActivityFilter, CursorPage},
+    format_units, parse_units,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::{
+    AppState,
+    handlers::{ApiResponse, CursorQuery, cursor_page, identity::display_names, parse_cursor},
+};
+
+/// Time range of the activity timeline in milliseconds
+#[derive(Debug, Deserialize)]
+pub struct ActivityQuery {
+    pub from_timestamp: Option<u128>,
+    pub to_timestamp: Option<u128>,
+}
+
+/// Every form of an account: stored links plus the addresses derived from the one asked for
+pub(crate) struct AccountAddresses {
+    pub(crate) links: Vec<AccountLink>,
+    pub(crate) evm_addresses: Vec<String>,
+    pub(crate) substrate_addresses: Vec<String>,
+}
+
+pub(crate) fn is_evm_address(address: &str) -> bool {
+    address
+        .strip_prefix("0x")
+        .is_some_and(|hex| hex.len() == 40 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+fn push_unique(addresses: &mut Vec<String>, address: String) {
+    if !addresses.contains(&address) {
+        addresses.push(address);
+    }
+}
+
+pub(crate) async fn account_addresses(
+    state: &AppState,
+    address: &str,
+) -> Result<AccountAddresses, ServiceError> {
+    let mut evm_addresses = Vec::new();
+    let mut substrate_addresses = Vec::new();
+
+    // Derived forms are computed so accounts the indexers have not linked yet still resolve
+    let address = if is_evm_address(address) {
+        let address = address.to_lowercase();
+        evm_addresses.push(address.clone());
+        if let Ok(substrate_address) = evm_to_ss58(&address) {
+            substrate_addresses.push(substrate_address);
+        }
+        address
+    } else {
+        // Accounts are stored with the chain's prefix, an address of another network matches too
+        let address = normalize_ss58(address).unwrap_or_else(|_| address.to_string());
+        substrate_addresses.push(address.clone());
+        if let Ok(evm_address) = ss58_to_evm_address(&address) {
+            evm_addresses.push(evm_address);
+        }
+        address
+    };
+
+    let links = state.db.account_links().get_by_address(&address).await?;
+    for link in &links {
+        push_unique(&mut evm_addresses, link.evm_address.clone());
+        push_unique(&mut substrate_addresses, link.substrate_address.clone());
+    }
+
+    Ok(AccountAddresses {
+        links,
+        evm_addresses,
+        substrate_addresses,
+    })
+}
+
+/// Sums the balances of the accounts, an SS58 account that only holds the native balance
+/// of an H160 account in the list is skipped as its funds are already counted
+fn combined_balances(accounts: &[AccountInfo]) -> (String, String) {
+    let shared: Vec<String> = accounts
+        .iter()
+        .filter(|account| is_evm_address(&account.address))
+        .filter_map(|account| evm_to_ss58(&account.address).ok())
+        .collect();
+
+    let mut balance = U256::zero();
+    let mut free_balance = U256::zero();
+    for account in accounts {
+        if shared.contains(&account.address) {
+            continue;
+        }
+        balance += parse_units(&account.balance_token, NATIVE_DECIMALS).unwrap_or_default();
+        free_balance += parse_units(&account.free_balance, NATIVE_DECIMALS).unwrap_or_default();
+    }
+
+    (
+        format_units(balance, NATIVE_DECIMALS),
+        format_units(free_balance, NATIVE_DECIMALS),
+    )
+}
+
+// Account API handlers
+pub async fn get_linked_account(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+) -> Result<Json<ApiResponse<LinkedAccount>>, StatusCode> {
+    let addresses = account_addresses(&state, &address).await.map_err(|e| {
+        eprintln!("Error fetching account links: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let account_service = state.db.accounts();
+    let mut accounts = Vec::new();
+    for account_address in addresses
+        .evm_addresses
+        .iter()
+        .chain(addresses.substrate_addresses.iter())
+    {
+        match account_service.get_by_address(account_address).await {
+            Ok(Some(account)) => accounts.push(account),
+            Ok(None) => {}
+            Err(e) => {
+                eprintln!("Error fetching linked account: {:?}", e);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        }
+    }
+
+    let (balance_token, free_balance) = combined_balances(&accounts);
+
+    let mut names = display_names(&state, addresses.substrate_addresses.clone()).await;
+    let display_name = addresses
+        .substrate_addresses
+        .iter()
+        .find_map(|address| names.remove(address));
+
+    Ok(Json(ApiResponse::success(LinkedAccount {
+        address,
+        display_name,
+        evm_addresses: addresses.evm_addresses,
+        substrate_addresses: addresses.substrate_addresses,
+        links: addresses.links,
+        accounts,
+        balance_token,
+        free_balance,
+    })))
+}
+
+pub async fn get_account_activity(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(page): Query<CursorQuery>,
+    Query(query): Query<ActivityQuery>,
+) -> Result<Json<ApiResponse<CursorPage<AccountActivity>>>, StatusCode> {
+    let cursor = parse_cursor(page.cursor.as_deref(), ActivityCursor::parse)?;
+
+    let addresses = account_addresses(&state, &address).await.map_err(|e| {
+        eprintln!("Error fetching account links: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let filter = ActivityFilter {
+        from_timestamp: query.from_timestamp,
+        to_timestamp: query.to_timestamp,
+    };
+    let activity_service = state.db.activity();
+
+    match activity_service
+        .get_by_addresses(
+            &addresses.evm_addresses,
+            &addresses.substrate_addresses,
+            &filter,
+            cursor.as_ref(),
+            page.limit + 1,
+        )
+        .await
+    {
+        Ok(activity) => Ok(Json(ApiResponse::success(cursor_page(
+            activity,
+            page.limit,
+            |item| item.cursor().encode(),
+        )))),
+        Err(e) => {
+            eprintln!("Error fetching account activity: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
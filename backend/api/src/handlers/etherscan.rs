@@ -0,0 +1,810 @@
+use axum::{
+    Form, Json,
+    extract::{Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use custom_error::ServiceError;
+use models::{
+    NATIVE_DECIMALS,
+    evm::{
+        EvmContract, EvmContractSource, EvmLog, EvmLogFilter, EvmTransaction, SourceFile,
+        TokenStandard, TokenTransfer, TokenTransferFilter, TransactionMethod, TransactionStatus,
+        VerificationRequest,
+    },
+    parse_units,
+};
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    AppState,
+    handlers::{account::is_evm_address, evm::verify_and_save},
+};
+
+/// Largest page of any list action, Etherscan caps `getLogs` at the same size
+const MAX_PAGE_SIZE: u32 = 1000;
+
+/// Etherscan response envelope, `status` is "1" on success and "0" otherwise
+#[derive(Debug, Serialize)]
+pub struct EtherscanResponse {
+    pub status: String,
+    pub message: String,
+    pub result: Value,
+}
+
+impl EtherscanResponse {
+    fn ok(result: Value) -> Self {
+        Self {
+            status: "1".to_string(),
+            message: "OK".to_string(),
+            result,
+        }
+    }
+
+    /// Empty lists are reported with status "0" like Etherscan does
+    fn list(rows: Vec<Value>, empty_message: &str) -> Self {
+        if rows.is_empty() {
+            Self {
+                status: "0".to_string(),
+                message: empty_message.to_string(),
+                result: json!([]),
+            }
+        } else {
+            Self::ok(Value::Array(rows))
+        }
+    }
+
+    fn error(result: String) -> Self {
+        Self {
+            status: "0".to_string(),
+            message: "NOTOK".to_string(),
+            result: Value::String(result),
+        }
+    }
+}
+
+/// Invalid requests are answered inside the envelope, failures of the backing services with a 500
+enum EtherscanError {
+    Invalid(String),
+    Internal,
+}
+
+type ActionResult = Result<EtherscanResponse, EtherscanError>;
+
+fn invalid(message: &str) -> EtherscanError {
+    EtherscanError::Invalid(message.to_string())
+}
+
+fn internal<E: std::fmt::Debug>(context: &'static str) -> impl Fn(E) -> EtherscanError {
+    move |e| {
+        eprintln!("Error {}: {:?}", context, e);
+        EtherscanError::Internal
+    }
+}
+
+/// Query string and form parameters, empty values count as missing
+struct Params(HashMap<String, String>);
+
+impl Params {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0
+            .get(key)
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+    }
+
+    fn require(&self, key: &str) -> Result<&str, EtherscanError> {
+        self.get(key)
+            .ok_or_else(|| EtherscanError::Invalid(format!("Error! Missing {}", key)))
+    }
+
+    fn number<T: std::str::FromStr>(&self, key: &str) -> Result<Option<T>, EtherscanError> {
+        self.get(key)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| EtherscanError::Invalid(format!("Error! Invalid {}", key)))
+            })
+            .transpose()
+    }
+
+    /// Block number parameter where `latest` means no upper bound
+    fn block(&self, key: &str) -> Result<Option<u64>, EtherscanError> {
+        match self.get(key) {
+            Some("latest") => Ok(None),
+            _ => self.number(key),
+        }
+    }
+
+    fn address(&self, key: &str) -> Result<String, EtherscanError> {
+        let address = self.require(key)?;
+        if !is_evm_address(address) {
+            return Err(EtherscanError::Invalid(format!("Error! Invalid {}", key)));
+        }
+
+        Ok(address.to_lowercase())
+    }
+
+    fn ascending(&self) -> Result<bool, EtherscanError> {
+        match self.get("sort") {
+            None | Some("asc") => Ok(true),
+            Some("desc") => Ok(false),
+            Some(_) => Err(invalid("Error! Invalid sort")),
+        }
+    }
+
+    /// `page` starts at 1 and `offset` is the page size, as (limit, offset) for the database
+    fn page(&self) -> Result<(u32, u32), EtherscanError> {
+        let page = self.number::<u32>("page")?.unwrap_or(1).max(1);
+        let size = self
+            .number::<u32>("offset")?
+            .unwrap_or(MAX_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE);
+
+        Ok((size, (page - 1).saturating_mul(size)))
+    }
+}
+
+/// Etherscan style API, `module` and `action` pick the call as on etherscan.io
+pub async fn etherscan_get(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Response, StatusCode> {
+    dispatch(&state, Params(params)).await
+}
+
+/// Form posts such as `verifysourcecode`, query string parameters are merged in
+pub async fn etherscan_post(
+    State(state): State<Arc<AppState>>,
+    Query(mut params): Query<HashMap<String, String>>,
+    Form(form): Form<HashMap<String, String>>,
+) -> Result<Response, StatusCode> {
+    params.extend(form);
+    dispatch(&state, Params(params)).await
+}
+
+async fn dispatch(state: &AppState, params: Params) -> Result<Response, StatusCode> {
+    let module = params.get("module").unwrap_or_default();
+    let action = params.get("action").unwrap_or_default();
+
+    let response = match (module, action) {
+        ("proxy", action) if action.starts_with("eth_") => {
+            return Ok(Json(proxy(state, action, &params).await).into_response());
+        }
+        ("account", "balance") => balance(state, &params).await,
+        ("account", "txlist") => transaction_list(state, &params).await,
+        ("account", "tokentx") => token_transfers(state, &params, TokenStandard::ERC20).await,
+        ("account", "tokennfttx") => token_transfers(state, &params, TokenStandard::ERC721).await,
+        ("account", "token1155tx") => token_transfers(state, &params, TokenStandard::ERC1155).await,
+        ("contract", "getabi") => contract_abi(state, &params).await,
+        ("contract", "getsourcecode") => contract_source(state, &params).await,
+        ("contract", "verifysourcecode") => verify_source(state, &params).await,
+        ("contract", "checkverifystatus") => verify_status(state, &params).await,
+        ("block", "getblocknobytime") => block_by_time(state, &params).await,
+        ("logs", "getLogs") => logs(state, &params).await,
+        ("stats", "ethsupply") => native_supply(state).await,
+        ("account" | "contract" | "block" | "logs" | "proxy" | "stats", _) => {
+            Err(invalid("Error! Missing Or invalid Action name"))
+        }
+        _ => Err(invalid("Error! Missing Or invalid Module name")),
+    };
+
+    match response {
+        Ok(response) => Ok(Json(response).into_response()),
+        Err(EtherscanError::Invalid(message)) => {
+            Ok(Json(EtherscanResponse::error(message)).into_response())
+        }
+        Err(EtherscanError::Internal) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+// ===== ACCOUNT =====
+
+async fn balance(state: &AppState, params: &Params) -> ActionResult {
+    let address = params.address("address")?;
+
+    let account = state
+        .db
+        .accounts()
+        .get_by_address(&address)
+        .await
+        .map_err(internal("fetching account balance"))?;
+
+    // Stored in token units, Etherscan answers in wei. An unknown account holds nothing,
+    // a stored balance that does not parse is an error rather than a zero balance
+    let balance = match account {
+        Some(account) => parse_units(&account.balance_token, NATIVE_DECIMALS).ok_or_else(|| {
+            eprintln!(
+                "Error converting balance {} of {}",
+                account.balance_token, address
+            );
+            EtherscanError::Internal
+        })?,
+        None => Default::default(),
+    };
+
+    Ok(EtherscanResponse::ok(Value::String(balance.to_string())))
+}
+
+/// Number of the latest indexed block, confirmations are counted from it
+async fn latest_block_number(state: &AppState) -> Result<u64, EtherscanError> {
+    let latest = state
+        .db
+        .evm_blocks()
+        .get_latest()
+        .await
+        .map_err(internal("fetching latest block"))?;
+
+    Ok(latest.map(|block| block.number as u64).unwrap_or(0))
+}
+
+fn confirmations(latest: u64, block_number: u64) -> String {
+    (latest + 1).saturating_sub(block_number).to_string()
+}
+
+fn transaction_row(transaction: &EvmTransaction, latest: u64) -> Value {
+    let (method_id, function_name) = match &transaction.transaction_method {
+        Some(TransactionMethod::ContractCall {
+            function_name,
+            function_signature,
+            ..
+        }) => (
+            function_signature
+                .as_deref()
+                .map(|selector| format!("0x{}", selector.trim_start_matches("0x")))
+                .unwrap_or_else(|| "0x".to_string()),
+            function_name.clone().unwrap_or_default(),
+        ),
+        _ => ("0x".to_string(), String::new()),
+    };
+    let function_name = transaction
+        .decoded_input
+        .as_ref()
+        .map(|call| call.signature.clone())
+        .unwrap_or(function_name);
+
+    let (is_error, receipt_status) = match transaction.status {
+        TransactionStatus::Success => ("0", "1"),
+        TransactionStatus::Failed => ("1", "0"),
+        TransactionStatus::Pending => ("0", ""),
+    };
+
+    json!({
+        "blockNumber": transaction.block_number.to_string(),
+        "timeStamp": transaction.timestamp.to_string(),
+        "hash": transaction.hash,
+        "nonce": transaction.nonce.to_string(),
+        "from": transaction.from,
+        "to": transaction.to.clone().unwrap_or_default(),
+        "value": transaction.value,
+        "gas": transaction.gas_limit.to_string(),
+        "gasPrice": transaction.gas_price.to_string(),
+        "gasUsed": transaction.gas_used.to_string(),
+        "isError": is_error,
+        "txreceipt_status": receipt_status,
+        "methodId": method_id,
+        "functionName": function_name,
+        "confirmations": confirmations(latest, transaction.block_number),
+    })
+}
+
+async fn transaction_list(state: &AppState, params: &Params) -> ActionResult {
+    let address = params.address("address")?;
+    let (limit, offset) = params.page()?;
+
+    let transactions = state
+        .db
+        .transactions()
+        .get_by_address_in_blocks(
+            &address,
+            params.block("startblock")?,
+            params.block("endblock")?,
+            params.ascending()?,
+            limit,
+            offset,
+        )
+        .await
+        .map_err(internal("fetching transactions"))?;
+
+    let latest = latest_block_number(state).await?;
+    let rows = transactions
+        .iter()
+        .map(|transaction| transaction_row(transaction, latest))
+        .collect();
+
+    Ok(EtherscanResponse::list(rows, "No transactions found"))
+}
+
+fn token_transfer_row(transfer: &TokenTransfer, token: Option<&EvmContract>, latest: u64) -> Value {
+    let mut row = json!({
+        "blockNumber": transfer.block_number.to_string(),
+        "timeStamp": transfer.timestamp.to_string(),
+        "hash": transfer.transaction_hash,
+        "from": transfer.from,
+        "to": transfer.to,
+        "contractAddress": transfer.token_address,
+        "tokenName": token.and_then(|token| token.name.clone()).unwrap_or_default(),
+        "tokenSymbol": token.and_then(|token| token.symbol.clone()).unwrap_or_default(),
+        "logIndex": transfer.log_index.to_string(),
+        "confirmations": confirmations(latest, transfer.block_number),
+    });
+
+    let value = transfer.value.clone().unwrap_or_default();
+    let token_id = transfer.token_id.clone().unwrap_or_default();
+    match transfer.standard {
+        TokenStandard::ERC20 => {
+            row["value"] = json!(value);
+            row["tokenDecimal"] = json!(
+                token
+                    .and_then(|token| token.decimals)
+                    .map(|decimals| decimals.to_string())
+                    .unwrap_or_default()
+            );
+        }
+        TokenStandard::ERC721 => {
+            row["tokenID"] = json!(token_id);
+            row["tokenDecimal"] = json!("0");
+        }
+        TokenStandard::ERC1155 => {
+            row["tokenID"] = json!(token_id);
+            row["tokenValue"] = json!(value);
+        }
+    }
+
+    row
+}
+
+/// Transfers of one token standard by address, contract or both
+async fn token_transfers(
+    state: &AppState,
+    params: &Params,
+    standard: TokenStandard,
+) -> ActionResult {
+    let address = params
+        .get("address")
+        .map(|_| params.address("address"))
+        .transpose()?;
+    let token = params
+        .get("contractaddress")
+        .map(|_| params.address("contractaddress"))
+        .transpose()?;
+    if address.is_none() && token.is_none() {
+        return Err(invalid("Error! Missing address or contractaddress"));
+    }
+
+    let filter = TokenTransferFilter {
+        address,
+        token,
+        standards: vec![standard],
+        from_block: params.block("startblock")?,
+        to_block: params.block("endblock")?,
+    };
+    let (limit, offset) = params.page()?;
+
+    let transfers = state
+        .db
+        .token_transfers()
+        .get_by_filter(&filter, params.ascending()?, limit, offset)
+        .await
+        .map_err(internal("fetching token transfers"))?;
+
+    let contract_service = state.db.contracts();
+    let mut tokens: HashMap<String, Option<EvmContract>> = HashMap::new();
+    for transfer in &transfers {
+        if !tokens.contains_key(&transfer.token_address) {
+            let token = contract_service
+                .get_by_address(&transfer.token_address)
+                .await
+                .map_err(internal("fetching token contract"))?;
+            tokens.insert(transfer.token_address.clone(), token);
+        }
+    }
+
+    let latest = latest_block_number(state).await?;
+    let rows = transfers
+        .iter()
+        .map(|transfer| {
+            let token = tokens.get(&transfer.token_address).and_then(Option::as_ref);
+            token_transfer_row(transfer, token, latest)
+        })
+        .collect();
+
+    Ok(EtherscanResponse::list(rows, "No transactions found"))
+}
+
+// ===== CONTRACT =====
+
+async fn contract_abi(state: &AppState, params: &Params) -> ActionResult {
+    let address = params.address("address")?;
+
+    let source = state
+        .db
+        .contract_sources()
+        .get_by_address(&address)
+        .await
+        .map_err(internal("fetching contract source"))?;
+
+    match source {
+        Some(source) => Ok(EtherscanResponse::ok(Value::String(source.abi.to_string()))),
+        None => Err(invalid("Contract source code not verified")),
+    }
+}
+
+/// A single file is returned as is, several files as Etherscan's `{{standard json}}` form
+fn source_code(source: &EvmContractSource) -> String {
+    if let [file] = source.sources.as_slice() {
+        return file.content.clone();
+    }
+
+    let sources: serde_json::Map<String, Value> = source
+        .sources
+        .iter()
+        .map(|file| (file.path.clone(), json!({ "content": file.content })))
+        .collect();
+    let input = json!({
+        "language": "Solidity",
+        "sources": sources,
+        "settings": source.settings,
+    });
+
+    format!("{{{}}}", input)
+}
+
+fn setting_string(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
+
+async fn contract_source(state: &AppState, params: &Params) -> ActionResult {
+    let address = params.address("address")?;
+
+    let source = state
+        .db
+        .contract_sources()
+        .get_by_address(&address)
+        .await
+        .map_err(internal("fetching contract source"))?;
+    let contract = state
+        .db
+        .contracts()
+        .get_by_address(&address)
+        .await
+        .map_err(internal("fetching contract"))?;
+
+    let implementation = contract
+        .and_then(|contract| contract.proxy)
+        .map(|proxy| proxy.implementation);
+    let (proxy, implementation) = match implementation {
+        Some(implementation) => ("1", implementation),
+        None => ("0", String::new()),
+    };
+
+    let row = match source {
+        Some(source) => {
+            let optimizer = &source.settings["optimizer"];
+            let optimization_used = if optimizer["enabled"].as_bool().unwrap_or(false) {
+                "1"
+            } else {
+                "0"
+            };
+            let compiler_version = if source.compiler_version.starts_with('v') {
+                source.compiler_version.clone()
+            } else {
+                format!("v{}", source.compiler_version)
+            };
+
+            json!({
+                "SourceCode": source_code(&source),
+                "ABI": source.abi.to_string(),
+                "ContractName": source.contract_name,
+                "CompilerVersion": compiler_version,
+                "OptimizationUsed": optimization_used,
+                "Runs": setting_string(&optimizer["runs"]),
+                "ConstructorArguments": "",
+                "EVMVersion": setting_string(&source.settings["evmVersion"]),
+                "Library": "",
+                "LicenseType": "",
+                "Proxy": proxy,
+                "Implementation": implementation,
+                "SwarmSource": "",
+            })
+        }
+        None => json!({
+            "SourceCode": "",
+            "ABI": "Contract source code not verified",
+            "ContractName": "",
+            "CompilerVersion": "",
+            "OptimizationUsed": "",
+            "Runs": "",
+            "ConstructorArguments": "",
+            "EVMVersion": "",
+            "Library": "",
+            "LicenseType": "",
+            "Proxy": proxy,
+            "Implementation": implementation,
+            "SwarmSource": "",
+        }),
+    };
+
+    Ok(EtherscanResponse::ok(json!([row])))
+}
+
+/// Builds the verification request from Etherscan's form fields. The local compiler picks
+/// the version, `compilerversion` is not checked
+fn verification_request(params: &Params) -> Result<VerificationRequest, EtherscanError> {
+    let address = params.address("contractaddress")?;
+    let source_code = params.require("sourceCode")?;
+    let contract_name = params.require("contractname")?.to_string();
+
+    let (sources, standard_json) = match params.get("codeformat") {
+        None | Some("solidity-single-file") => {
+            let path = match contract_name.split_once(':') {
+                Some((path, _)) => path.to_string(),
+                None => format!("{}.sol", contract_name),
+            };
+            let file = SourceFile {
+                path,
+                content: source_code.to_string(),
+            };
+            (vec![file], None)
+        }
+        Some("solidity-standard-json-input") => {
+            let standard_json = serde_json::from_str(source_code)
+                .map_err(|_| invalid("Error! Invalid standard json input"))?;
+            (Vec::new(), Some(standard_json))
+        }
+        Some(_) => return Err(invalid("Error! Invalid codeformat")),
+    };
+
+    Ok(VerificationRequest {
+        address,
+        contract_name,
+        sources,
+        optimizer_enabled: params.get("optimizationUsed") == Some("1"),
+        optimizer_runs: params.number("runs")?.unwrap_or(200),
+        evm_version: params
+            .get("evmversion")
+            .filter(|version| *version != "default")
+            .map(str::to_string),
+        standard_json,
+    })
+}
+
+/// Verification runs before responding, the returned guid is the contract address
+async fn verify_source(state: &AppState, params: &Params) -> ActionResult {
+    let request = verification_request(params)?;
+
+    match verify_and_save(state, &request).await {
+        Ok(source) => Ok(EtherscanResponse::ok(Value::String(source.address))),
+        Err(ServiceError::VerificationError(message)) => Err(EtherscanError::Invalid(format!(
+            "Fail - Unable to verify. {}",
+            message
+        ))),
+        Err(e) => Err(internal("verifying contract")(e)),
+    }
+}
+
+async fn verify_status(state: &AppState, params: &Params) -> ActionResult {
+    let guid = params.require("guid")?;
+    if !is_evm_address(guid) {
+        return Err(invalid("Unknown UID"));
+    }
+
+    let source = state
+        .db
+        .contract_sources()
+        .get_by_address(&guid.to_lowercase())
+        .await
+        .map_err(internal("fetching contract source"))?;
+
+    match source {
+        Some(_) => Ok(EtherscanResponse::ok(json!("Pass - Verified"))),
+        None => Err(invalid("Fail - Unable to verify")),
+    }
+}
+
+// ===== BLOCK =====
+
+async fn block_by_time(state: &AppState, params: &Params) -> ActionResult {
+    let timestamp: u128 = params
+        .number("timestamp")?
+        .ok_or_else(|| invalid("Error! Missing timestamp"))?;
+    let before = match params.get("closest") {
+        None | Some("before") => true,
+        Some("after") => false,
+        Some(_) => return Err(invalid("Error! Invalid closest")),
+    };
+
+    let block = state
+        .db
+        .evm_blocks()
+        .get_closest_by_timestamp(timestamp, before)
+        .await
+        .map_err(internal("fetching block by timestamp"))?;
+
+    match block {
+        Some(block) => Ok(EtherscanResponse::ok(Value::String(
+            block.number.to_string(),
+        ))),
+        None => Err(invalid("Error! No closest block found")),
+    }
+}
+
+// ===== LOGS =====
+
+fn hex_number(value: impl std::fmt::LowerHex) -> String {
+    format!("{:#x}", value)
+}
+
+fn log_row(log: &EvmLog) -> Value {
+    let topics: Vec<&String> = [&log.topic0, &log.topic1, &log.topic2, &log.topic3]
+        .into_iter()
+        .flatten()
+        .collect();
+
+    json!({
+        "address": log.address,
+        "topics": topics,
+        "data": log.data,
+        "blockNumber": hex_number(log.block_number),
+        "timeStamp": hex_number(log.timestamp),
+        "logIndex": hex_number(log.log_index),
+        "transactionHash": log.transaction_hash,
+    })
+}
+
+/// Topics are combined with `and`, which is the only operator the log index supports
+async fn logs(state: &AppState, params: &Params) -> ActionResult {
+    let operators = ["0_1", "0_2", "0_3", "1_2", "1_3", "2_3"];
+    if operators
+        .iter()
+        .any(|pair| params.get(&format!("topic{}_opr", pair)) == Some("or"))
+    {
+        return Err(invalid("Error! Only the and topic operator is supported"));
+    }
+
+    let from_block = params.block("fromBlock")?;
+    let to_block = params.block("toBlock")?;
+    if let (Some(from_block), Some(to_block)) = (from_block, to_block) {
+        if from_block > to_block {
+            return Err(invalid("Error! fromBlock is after toBlock"));
+        }
+    }
+
+    let topic = |key: &str| -> Vec<String> {
+        params
+            .get(key)
+            .map(|topic| vec![topic.to_lowercase()])
+            .unwrap_or_default()
+    };
+    let filter = EvmLogFilter {
+        addresses: params
+            .get("address")
+            .map(|_| params.address("address"))
+            .transpose()?
+            .into_iter()
+            .collect(),
+        topics: [
+            topic("topic0"),
+            topic("topic1"),
+            topic("topic2"),
+            topic("topic3"),
+        ],
+        from_block,
+        to_block,
+    };
+    let (limit, offset) = params.page()?;
+
+    let logs = state
+        .db
+        .evm_logs()
+        .get_by_filter(&filter, limit, offset)
+        .await
+        .map_err(internal("fetching logs"))?;
+
+    let rows = logs.iter().map(log_row).collect();
+
+    Ok(EtherscanResponse::list(rows, "No records found"))
+}
+
+// ===== PROXY =====
+
+/// JSON-RPC parameters of a proxied call, read from Etherscan's named parameters
+fn proxy_params(action: &str, params: &Params) -> Result<Vec<Value>, EtherscanError> {
+    let value = |key: &str| params.require(key).map(|value| json!(value));
+    let tag = || json!(params.get("tag").unwrap_or("latest"));
+    let call = || {
+        let mut call = serde_json::Map::new();
+        for (key, field) in [
+            ("from", "from"),
+            ("to", "to"),
+            ("data", "data"),
+            ("value", "value"),
+            ("gas", "gas"),
+            ("gasPrice", "gasPrice"),
+        ] {
+            if let Some(value) = params.get(key) {
+                call.insert(field.to_string(), json!(value));
+            }
+        }
+        Value::Object(call)
+    };
+
+    Ok(match action {
+        "eth_blockNumber" | "eth_gasPrice" | "eth_chainId" => Vec::new(),
+        "eth_getBlockByNumber" => vec![value("tag")?, json!(params.get("boolean") == Some("true"))],
+        "eth_getBlockTransactionCountByNumber" => vec![value("tag")?],
+        "eth_getTransactionByHash" | "eth_getTransactionReceipt" => vec![value("txhash")?],
+        "eth_getTransactionByBlockNumberAndIndex" | "eth_getUncleByBlockNumberAndIndex" => {
+            vec![value("tag")?, value("index")?]
+        }
+        "eth_getTransactionCount" | "eth_getCode" | "eth_getBalance" => {
+            vec![value("address")?, tag()]
+        }
+        "eth_getStorageAt" => vec![value("address")?, value("position")?, tag()],
+        "eth_sendRawTransaction" => vec![value("hex")?],
+        "eth_call" => {
+            value("to")?;
+            vec![call(), tag()]
+        }
+        "eth_estimateGas" => vec![call()],
+        _ => return Err(invalid("Error! Missing Or invalid Action name")),
+    })
+}
+
+/// Forwards the call to the node and answers in JSON-RPC form, as Etherscan's proxy module does
+async fn proxy(state: &AppState, action: &str, params: &Params) -> Value {
+    let rpc_error = |message: String| {
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "error": { "code": -32602, "message": message },
+        })
+    };
+
+    let rpc_params = match proxy_params(action, params) {
+        Ok(rpc_params) => rpc_params,
+        Err(EtherscanError::Invalid(message)) => return rpc_error(message),
+        Err(EtherscanError::Internal) => return rpc_error("Internal error".to_string()),
+    };
+
+    match state
+        .evm_provider
+        .request::<_, Value>(action, rpc_params)
+        .await
+    {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": 1, "result": result }),
+        Err(e) => {
+            eprintln!("Error proxying {}: {:?}", action, e);
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": { "code": -32000, "message": e.to_string() },
+            })
+        }
+    }
+}
+
+// ===== STATS =====
+
+/// Total issuance of the native token in its smallest unit
+async fn native_supply(state: &AppState) -> ActionResult {
+    let api = state
+        .substrate()
+        .await
+        .map_err(internal("connecting to substrate"))?;
+
+    let total_issuance = api
+        .get_total_issuance()
+        .await
+        .map_err(internal("fetching total issuance"))?;
+
+    Ok(EtherscanResponse::ok(Value::String(
+        total_issuance.to_string(),
+    )))
+}
@@ -12,12 +12,13 @@ use crate::{
     handlers::{ApiResponse, PaginationQuery},
 };
 
+/// Bounds are decimal amounts in token units, e.g. `1000.5`
 #[derive(Debug, Deserialize)]
 pub struct BalanceRangeQuery {
     #[serde(default = "default_min_balance")]
-    pub min_balance: f64,
-    #[serde(default = "default_max_balance")]
-    pub max_balance: f64,
+    pub min_balance: String,
+    #[serde(default)]
+    pub max_balance: Option<String>,
     #[serde(default = "default_limit")]
     pub limit: u32,
 }
@@ -26,12 +27,15 @@ fn default_limit() -> u32 {
     20
 }
 
-fn default_min_balance() -> f64 {
-    0.0
+fn default_min_balance() -> String {
+    "0".to_string()
 }
 
-fn default_max_balance() -> f64 {
-    f64::MAX
+fn is_decimal(value: &str) -> bool {
+    let (integer, fraction) = value.split_once('.').unwrap_or((value, ""));
+    !integer.is_empty()
+        && integer.chars().all(|c| c.is_ascii_digit())
+        && fraction.chars().all(|c| c.is_ascii_digit())
 }
 
 // Account API handlers
@@ -72,12 +76,22 @@ pub async fn get_accounts_by_balance_range(
     State(state): State<Arc<AppState>>,
     Query(balance_query): Query<BalanceRangeQuery>,
 ) -> Result<Json<ApiResponse<Vec<AccountInfo>>>, StatusCode> {
+    let bounds_valid = is_decimal(&balance_query.min_balance)
+        && balance_query.max_balance.as_deref().is_none_or(is_decimal);
+    if !bounds_valid {
+        eprintln!(
+            "Invalid balance range: {} - {:?}",
+            balance_query.min_balance, balance_query.max_balance
+        );
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
     let account_service = state.db.accounts();
 
     match account_service
         .get_accounts_by_balance_range(
-            balance_query.min_balance,
-            balance_query.max_balance,
+            &balance_query.min_balance,
+            balance_query.max_balance.as_deref(),
             balance_query.limit,
         )
         .await
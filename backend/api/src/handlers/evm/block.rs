@@ -3,7 +3,7 @@ use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
 };
-use models::evm::EvmBlock;
+use models::evm::{EvmBlock, EvmOrphanedBlock};
 use std::sync::Arc;
 
 use crate::{
@@ -73,3 +73,36 @@ pub async fn get_block_by_hash(
         }
     }
 }
+
+pub async fn get_orphaned_blocks(
+    State(state): State<Arc<AppState>>,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<ApiResponse<Vec<EvmOrphanedBlock>>>, StatusCode> {
+    let orphaned_service = state.db.orphaned_blocks();
+
+    match orphaned_service
+        .get_all(pagination.limit, pagination.offset)
+        .await
+    {
+        Ok(blocks) => Ok(Json(ApiResponse::success(blocks))),
+        Err(e) => {
+            eprintln!("Error fetching orphaned blocks: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn get_orphaned_blocks_by_number(
+    State(state): State<Arc<AppState>>,
+    Path(block_number): Path<u32>,
+) -> Result<Json<ApiResponse<Vec<EvmOrphanedBlock>>>, StatusCode> {
+    let orphaned_service = state.db.orphaned_blocks();
+
+    match orphaned_service.get_by_number(block_number).await {
+        Ok(blocks) => Ok(Json(ApiResponse::success(blocks))),
+        Err(e) => {
+            eprintln!("Error fetching orphaned blocks by number: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
@@ -3,14 +3,25 @@ use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
 };
-use models::evm::{ContractType, EvmContract};
-use std::sync::Arc;
+use blockscan::{BlockStateQuery, decode_calldata};
+use custom_error::ServiceError;
+use database::DatabaseService;
+use models::evm::{
+    ContractType, EvmContract, EvmContractSource, EvmProxyImplementation, VerificationRequest,
+};
+use std::{
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use crate::{
     AppState,
     handlers::{ApiResponse, PaginationQuery},
 };
 
+/// Transactions loaded per page while decoding the history of a newly verified contract
+const REDECODE_PAGE_SIZE: u32 = 200;
+
 pub async fn get_all_contracts(
     State(state): State<Arc<AppState>>,
     Query(pagination): Query<PaginationQuery>,
@@ -88,3 +99,131 @@ pub async fn get_verified_contracts(
         }
     }
 }
+
+/// Compiles the submitted sources and stores them when the runtime bytecode matches the
+/// deployed code. Compilation errors and mismatches are reported in the response body.
+pub async fn verify_contract(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<VerificationRequest>,
+) -> Result<Json<ApiResponse<EvmContractSource>>, StatusCode> {
+    match verify_and_save(&state, &request).await {
+        Ok(source) => Ok(Json(ApiResponse::success(source))),
+        Err(ServiceError::VerificationError(message)) => Ok(Json(ApiResponse::error(message))),
+        Err(e) => {
+            eprintln!("Error verifying contract {}: {:?}", request.address, e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Verifies a contract and marks it verified, failed verifications are `VerificationError`s
+pub(crate) async fn verify_and_save(
+    state: &AppState,
+    request: &VerificationRequest,
+) -> Result<EvmContractSource, ServiceError> {
+    let provider = Arc::clone(&state.evm_provider);
+    let query = BlockStateQuery::new(Arc::clone(&provider), None);
+
+    let verified = query.verify_contract(request).await?;
+
+    let source = EvmContractSource {
+        address: verified.address,
+        contract_name: verified.contract_name,
+        source_path: verified.source_path,
+        compiler_version: verified.compiler_version,
+        abi: verified.abi,
+        sources: verified.sources,
+        settings: verified.settings,
+        metadata: verified.metadata,
+        verified_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or(0),
+    };
+
+    state.db.contract_sources().save(&source).await?;
+    state
+        .db
+        .contracts()
+        .update_verification_status(&source.address, true)
+        .await?;
+
+    let db = state.db.clone();
+    let (address, abi) = (source.address.clone(), source.abi.clone());
+    tokio::spawn(async move {
+        let query = BlockStateQuery::new(provider, None);
+        match redecode_transactions(&db, &query, &address, &abi).await {
+            Ok(decoded) => println!("🔎 Decoded {} earlier calls to {}", decoded, address),
+            Err(e) => eprintln!("Error decoding earlier calls to {}: {:?}", address, e),
+        }
+    });
+
+    Ok(source)
+}
+
+/// Decodes the calls to a contract that were indexed before its ABI was known. The
+/// calldata is not stored, so it is fetched again from the node.
+async fn redecode_transactions(
+    db: &DatabaseService,
+    query: &BlockStateQuery,
+    address: &str,
+    abi: &serde_json::Value,
+) -> Result<usize, ServiceError> {
+    let transaction_service = db.transactions();
+    let mut after: Option<String> = None;
+    let mut decoded = 0;
+
+    loop {
+        let page = transaction_service
+            .get_undecoded_to(address, after.as_deref(), REDECODE_PAGE_SIZE)
+            .await?;
+
+        for transaction in &page {
+            let Some(input) = query.transaction_input(&transaction.hash).await? else {
+                continue;
+            };
+            if let Some(call) = decode_calldata(abi, &input) {
+                transaction_service
+                    .set_decoded_input(&transaction.hash, &call)
+                    .await?;
+                decoded += 1;
+            }
+        }
+
+        if page.len() < REDECODE_PAGE_SIZE as usize {
+            return Ok(decoded);
+        }
+        after = page.last().map(|transaction| transaction.hash.clone());
+    }
+}
+
+pub async fn get_contract_source(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+) -> Result<Json<ApiResponse<Option<EvmContractSource>>>, StatusCode> {
+    let source_service = state.db.contract_sources();
+
+    match source_service.get_by_address(&address).await {
+        Ok(source) => Ok(Json(ApiResponse::success(source))),
+        Err(e) => {
+            eprintln!("Error fetching contract source: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Implementation history of a proxy contract, most recent first
+pub async fn get_proxy_implementations(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+) -> Result<Json<ApiResponse<Vec<EvmProxyImplementation>>>, StatusCode> {
+    let implementation_service = state.db.proxy_implementations();
+
+    match implementation_service.get_by_proxy(&address).await {
+        Ok(implementations) => Ok(Json(ApiResponse::success(implementations))),
+        Err(e) => {
+            eprintln!("Error fetching proxy implementations: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
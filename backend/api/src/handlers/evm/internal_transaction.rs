@@ -0,0 +1,49 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use models::evm::EvmInternalTransaction;
+use std::sync::Arc;
+
+use crate::{
+    AppState,
+    handlers::{ApiResponse, PaginationQuery},
+};
+
+pub async fn get_internal_transactions_by_transaction(
+    State(state): State<Arc<AppState>>,
+    Path(tx_hash): Path<String>,
+) -> Result<Json<ApiResponse<Vec<EvmInternalTransaction>>>, StatusCode> {
+    let internal_tx_service = state.db.internal_transactions();
+
+    match internal_tx_service.get_by_transaction(&tx_hash).await {
+        Ok(internal_txs) => Ok(Json(ApiResponse::success(internal_txs))),
+        Err(e) => {
+            eprintln!(
+                "Error fetching internal transactions by transaction: {:?}",
+                e
+            );
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn get_internal_transactions_by_address(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<ApiResponse<Vec<EvmInternalTransaction>>>, StatusCode> {
+    let internal_tx_service = state.db.internal_transactions();
+
+    match internal_tx_service
+        .get_by_address(&address, pagination.limit, pagination.offset)
+        .await
+    {
+        Ok(internal_txs) => Ok(Json(ApiResponse::success(internal_txs))),
+        Err(e) => {
+            eprintln!("Error fetching internal transactions by address: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
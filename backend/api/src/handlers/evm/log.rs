@@ -0,0 +1,120 @@
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::StatusCode,
+};
+use blockscan::decode_log_with_abi;
+use models::evm::{EvmLog, EvmLogFilter};
+use serde::Deserialize;
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{AppState, handlers::ApiResponse};
+
+// Upper bound on a single page of logs
+const MAX_LOGS_LIMIT: u32 = 1000;
+
+/// `eth_getLogs` style filter, `address` and each topic accept comma separated alternatives
+#[derive(Debug, Deserialize)]
+pub struct LogFilterQuery {
+    #[serde(default)]
+    pub address: Option<String>,
+    #[serde(default)]
+    pub topic0: Option<String>,
+    #[serde(default)]
+    pub topic1: Option<String>,
+    #[serde(default)]
+    pub topic2: Option<String>,
+    #[serde(default)]
+    pub topic3: Option<String>,
+    #[serde(default)]
+    pub from_block: Option<u64>,
+    #[serde(default)]
+    pub to_block: Option<u64>,
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+    #[serde(default)]
+    pub offset: u32,
+}
+
+fn default_limit() -> u32 {
+    100
+}
+
+fn split_values(value: &Option<String>) -> Vec<String> {
+    value
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+pub async fn get_evm_logs(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<LogFilterQuery>,
+) -> Result<Json<ApiResponse<Vec<EvmLog>>>, StatusCode> {
+    if let (Some(from_block), Some(to_block)) = (query.from_block, query.to_block) {
+        if from_block > to_block {
+            eprintln!("Invalid log block range: {} > {}", from_block, to_block);
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
+
+    let filter = EvmLogFilter {
+        addresses: split_values(&query.address),
+        topics: [
+            split_values(&query.topic0),
+            split_values(&query.topic1),
+            split_values(&query.topic2),
+            split_values(&query.topic3),
+        ],
+        from_block: query.from_block,
+        to_block: query.to_block,
+    };
+
+    let log_service = state.db.evm_logs();
+
+    match log_service
+        .get_by_filter(&filter, query.limit.min(MAX_LOGS_LIMIT), query.offset)
+        .await
+    {
+        Ok(mut logs) => {
+            decode_with_verified_abis(&state, &mut logs).await;
+            Ok(Json(ApiResponse::success(logs)))
+        }
+        Err(e) => {
+            eprintln!("Error fetching logs: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Logs indexed before their contract was verified are decoded when they are read
+async fn decode_with_verified_abis(state: &AppState, logs: &mut [EvmLog]) {
+    let source_service = state.db.contract_sources();
+    let mut abis = HashMap::new();
+
+    for log in logs.iter_mut().filter(|log| log.decoded.is_none()) {
+        if !abis.contains_key(&log.address) {
+            let abi = match source_service.get_decoding_abi(&log.address).await {
+                Ok(abi) => abi,
+                Err(e) => {
+                    eprintln!("Error fetching contract source: {:?}", e);
+                    None
+                }
+            };
+            abis.insert(log.address.clone(), abi);
+        }
+
+        if let Some(abi) = abis.get(&log.address).and_then(Option::as_ref) {
+            let topics: Vec<String> = [&log.topic0, &log.topic1, &log.topic2, &log.topic3]
+                .into_iter()
+                .flatten()
+                .cloned()
+                .collect();
+            log.decoded = decode_log_with_abi(abi, &topics, &log.data);
+        }
+    }
+}
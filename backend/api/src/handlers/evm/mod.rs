@@ -1,11 +1,19 @@
 mod account;
 mod block;
 mod contract;
+mod internal_transaction;
+mod log;
 mod network;
+mod token;
+mod token_transfer;
 mod transaction;
 
 pub use account::*;
 pub use block::*;
 pub use contract::*;
+pub use internal_transaction::*;
+pub use log::*;
 pub use network::*;
+pub use token::*;
+pub use token_transfer::*;
 pub use transaction::*;
@@ -0,0 +1,204 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use blockscan::ethers::types::{U256, U512};
+use models::evm::{ContractType, TokenHolder, TokenHolding};
+use serde::Deserialize;
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    AppState,
+    handlers::{ApiResponse, PaginationQuery},
+};
+
+/// Decimal places of a holder's share of the supply
+const PERCENTAGE_DECIMALS: usize = 6;
+
+/// Share of the supply held by a balance, both given as decimal strings. Computed in
+/// integers and returned as a decimal string so large supplies keep their precision
+fn supply_percentage(balance: &str, total_supply: Option<&str>) -> Option<String> {
+    let balance = U256::from_dec_str(balance).ok()?;
+    let total_supply = U256::from_dec_str(total_supply?).ok()?;
+
+    if total_supply.is_zero() {
+        return None;
+    }
+
+    let scale = U256::from(100) * U256::exp10(PERCENTAGE_DECIMALS);
+    let scaled = (balance.full_mul(scale) / U512::from(total_supply)).to_string();
+    let scaled = format!("{:0>width$}", scaled, width = PERCENTAGE_DECIMALS + 1);
+
+    let (whole, fraction) = scaled.split_at(scaled.len() - PERCENTAGE_DECIMALS);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        Some(whole.to_string())
+    } else {
+        Some(format!("{}.{}", whole, fraction))
+    }
+}
+
+/// Token id of the holders of an ERC1155 token, whose ids each have their own holders
+#[derive(Debug, Deserialize)]
+pub struct TokenHoldersQuery {
+    pub token_id: Option<String>,
+}
+
+pub async fn get_token_holders(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(pagination): Query<PaginationQuery>,
+    Query(query): Query<TokenHoldersQuery>,
+) -> Result<Json<ApiResponse<Vec<TokenHolder>>>, StatusCode> {
+    let address = address.to_lowercase();
+
+    let contract = match state.db.contracts().get_by_address(&address).await {
+        Ok(contract) => contract,
+        Err(e) => {
+            eprintln!("Error fetching token contract: {:?}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    // The contract-level supply is not the supply of any single ERC1155 id, so ERC1155
+    // holders are listed per id and without a share
+    let is_erc1155 = contract
+        .as_ref()
+        .is_some_and(|contract| matches!(contract.contract_type, ContractType::ERC1155));
+    if is_erc1155 && query.token_id.is_none() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let total_supply = if is_erc1155 {
+        None
+    } else {
+        contract.and_then(|contract| contract.total_supply)
+    };
+
+    let balances = match state
+        .db
+        .token_balances()
+        .get_holders(
+            &address,
+            query.token_id.as_deref(),
+            pagination.limit,
+            pagination.offset,
+        )
+        .await
+    {
+        Ok(balances) => balances,
+        Err(e) => {
+            eprintln!("Error fetching token holders: {:?}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    let holders = balances
+        .into_iter()
+        .map(|balance| TokenHolder {
+            percentage: supply_percentage(&balance.balance, total_supply.as_deref()),
+            holder: balance.holder,
+            token_id: balance.token_id,
+            balance: balance.balance,
+        })
+        .collect();
+
+    Ok(Json(ApiResponse::success(holders)))
+}
+
+pub async fn get_account_tokens(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<ApiResponse<Vec<TokenHolding>>>, StatusCode> {
+    let balances = match state
+        .db
+        .token_balances()
+        .get_by_holder(&address, pagination.limit, pagination.offset)
+        .await
+    {
+        Ok(balances) => balances,
+        Err(e) => {
+            eprintln!("Error fetching account tokens: {:?}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    // Look each token contract up once for its metadata
+    let mut contracts = HashMap::new();
+    for balance in &balances {
+        if contracts.contains_key(&balance.token_address) {
+            continue;
+        }
+        match state
+            .db
+            .contracts()
+            .get_by_address(&balance.token_address)
+            .await
+        {
+            Ok(contract) => {
+                contracts.insert(balance.token_address.clone(), contract);
+            }
+            Err(e) => {
+                eprintln!("Error fetching token contract: {:?}", e);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        }
+    }
+
+    let holdings = balances
+        .into_iter()
+        .map(|balance| {
+            let contract = contracts.get(&balance.token_address).cloned().flatten();
+            TokenHolding {
+                token_address: balance.token_address,
+                standard: balance.standard,
+                token_id: balance.token_id,
+                balance: balance.balance,
+                name: contract.as_ref().and_then(|c| c.name.clone()),
+                symbol: contract.as_ref().and_then(|c| c.symbol.clone()),
+                decimals: contract.and_then(|c| c.decimals),
+            }
+        })
+        .collect();
+
+    Ok(Json(ApiResponse::success(holdings)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn supply_percentage_is_exact() {
+        assert_eq!(
+            supply_percentage("250", Some("1000")),
+            Some("25".to_string())
+        );
+        assert_eq!(
+            supply_percentage("1", Some("3")),
+            Some("33.333333".to_string())
+        );
+        assert_eq!(
+            supply_percentage("1", Some("1000000000")),
+            Some("0".to_string())
+        );
+        assert_eq!(
+            supply_percentage("15", Some("1000000")),
+            Some("0.0015".to_string())
+        );
+    }
+
+    #[test]
+    fn supply_percentage_of_large_supplies() {
+        let max = U256::MAX.to_string();
+        assert_eq!(supply_percentage(&max, Some(&max)), Some("100".to_string()));
+    }
+
+    #[test]
+    fn supply_percentage_without_supply() {
+        assert_eq!(supply_percentage("1", None), None);
+        assert_eq!(supply_percentage("1", Some("0")), None);
+        assert_eq!(supply_percentage("-1", Some("10")), None);
+    }
+}
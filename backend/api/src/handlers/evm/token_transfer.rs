@@ -0,0 +1,65 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use models::evm::TokenTransfer;
+use std::sync::Arc;
+
+use crate::{
+    AppState,
+    handlers::{ApiResponse, PaginationQuery},
+};
+
+pub async fn get_token_transfers_by_token(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<ApiResponse<Vec<TokenTransfer>>>, StatusCode> {
+    let transfer_service = state.db.token_transfers();
+
+    match transfer_service
+        .get_by_token(&address, pagination.limit, pagination.offset)
+        .await
+    {
+        Ok(transfers) => Ok(Json(ApiResponse::success(transfers))),
+        Err(e) => {
+            eprintln!("Error fetching token transfers by token: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn get_token_transfers_by_address(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<ApiResponse<Vec<TokenTransfer>>>, StatusCode> {
+    let transfer_service = state.db.token_transfers();
+
+    match transfer_service
+        .get_by_address(&address, pagination.limit, pagination.offset)
+        .await
+    {
+        Ok(transfers) => Ok(Json(ApiResponse::success(transfers))),
+        Err(e) => {
+            eprintln!("Error fetching token transfers by address: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn get_token_transfers_by_transaction(
+    State(state): State<Arc<AppState>>,
+    Path(tx_hash): Path<String>,
+) -> Result<Json<ApiResponse<Vec<TokenTransfer>>>, StatusCode> {
+    let transfer_service = state.db.token_transfers();
+
+    match transfer_service.get_by_transaction(&tx_hash).await {
+        Ok(transfers) => Ok(Json(ApiResponse::success(transfers))),
+        Err(e) => {
+            eprintln!("Error fetching token transfers by transaction: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
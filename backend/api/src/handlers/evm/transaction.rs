@@ -3,14 +3,27 @@ use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
 };
-use models::evm::EvmTransaction;
+use models::{
+    activity::{Cursor, CursorPage},
+    evm::{EvmAddressTxFilter, EvmTransaction, TransactionDirection},
+};
+use serde::Deserialize;
 use std::sync::Arc;
 
 use crate::{
     AppState,
-    handlers::{ApiResponse, PaginationQuery},
+    handlers::{ApiResponse, CursorQuery, PaginationQuery, cursor_page, parse_cursor},
 };
 
+#[derive(Debug, Deserialize)]
+pub struct AddressTransactionsQuery {
+    #[serde(default)]
+    pub direction: TransactionDirection,
+    pub method: Option<String>,
+    pub from_timestamp: Option<u128>,
+    pub to_timestamp: Option<u128>,
+}
+
 pub async fn get_all_transactions(
     State(state): State<Arc<AppState>>,
     Query(pagination): Query<PaginationQuery>,
@@ -75,3 +88,35 @@ pub async fn get_transactions_by_block_number(
         }
     }
 }
+
+pub async fn get_transactions_by_address(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(page): Query<CursorQuery>,
+    Query(query): Query<AddressTransactionsQuery>,
+) -> Result<Json<ApiResponse<CursorPage<EvmTransaction>>>, StatusCode> {
+    let cursor = parse_cursor(page.cursor.as_deref(), Cursor::<String>::parse)?;
+
+    let filter = EvmAddressTxFilter {
+        direction: query.direction,
+        method: query.method,
+        from_timestamp: query.from_timestamp,
+        to_timestamp: query.to_timestamp,
+    };
+    let transaction_service = state.db.transactions();
+
+    match transaction_service
+        .get_by_address(&address, &filter, cursor.as_ref(), page.limit + 1)
+        .await
+    {
+        Ok(transactions) => Ok(Json(ApiResponse::success(cursor_page(
+            transactions,
+            page.limit,
+            |tx| Cursor::new(tx.timestamp, tx.hash.clone()).encode(),
+        )))),
+        Err(e) => {
+            eprintln!("Error fetching transactions by address: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use models::substrate::{Identity, SubstrateExtrinsic, SubstrateTransfer, ValidatorSnapshot};
+use serde::Deserialize;
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    AppState,
+    handlers::{ApiResponse, PaginationQuery},
+};
+
+#[derive(Debug, Deserialize)]
+pub struct IdentitySearchQuery {
+    pub q: String,
+}
+
+/// Display names of the accounts, a failed lookup only leaves the names out
+pub async fn display_names(state: &AppState, accounts: Vec<String>) -> HashMap<String, String> {
+    match state.db.identities().display_names(&accounts).await {
+        Ok(names) => names,
+        Err(e) => {
+            eprintln!("Error fetching display names: {:?}", e);
+            HashMap::new()
+        }
+    }
+}
+
+pub async fn with_signer_displays(
+    state: &AppState,
+    mut extrinsics: Vec<SubstrateExtrinsic>,
+) -> Vec<SubstrateExtrinsic> {
+    let signers = extrinsics
+        .iter()
+        .filter_map(|extrinsic| extrinsic.signer.clone())
+        .collect();
+    let names = display_names(state, signers).await;
+
+    for extrinsic in &mut extrinsics {
+        extrinsic.signer_display = extrinsic
+            .signer
+            .as_ref()
+            .and_then(|signer| names.get(signer).cloned());
+    }
+    extrinsics
+}
+
+pub async fn with_validator_displays(
+    state: &AppState,
+    mut validators: Vec<ValidatorSnapshot>,
+) -> Vec<ValidatorSnapshot> {
+    let accounts = validators
+        .iter()
+        .map(|validator| validator.account_id.clone())
+        .collect();
+    let names = display_names(state, accounts).await;
+
+    for validator in &mut validators {
+        validator.display_name = names.get(&validator.account_id).cloned();
+    }
+    validators
+}
+
+pub async fn with_transfer_displays(
+    state: &AppState,
+    mut transfers: Vec<SubstrateTransfer>,
+) -> Vec<SubstrateTransfer> {
+    let accounts = transfers
+        .iter()
+        .flat_map(|transfer| [transfer.from.clone(), transfer.to.clone()])
+        .collect();
+    let names = display_names(state, accounts).await;
+
+    for transfer in &mut transfers {
+        transfer.from_display = names.get(&transfer.from).cloned();
+        transfer.to_display = names.get(&transfer.to).cloned();
+    }
+    transfers
+}
+
+// Identity API handlers
+pub async fn get_identity(
+    State(state): State<Arc<AppState>>,
+    Path(account): Path<String>,
+) -> Result<Json<ApiResponse<Option<Identity>>>, StatusCode> {
+    let identity_service = state.db.identities();
+
+    match identity_service.get_by_account(&account).await {
+        Ok(identity) => Ok(Json(ApiResponse::success(identity))),
+        Err(e) => {
+            eprintln!("Error fetching identity: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn search_identities(
+    State(state): State<Arc<AppState>>,
+    Query(search): Query<IdentitySearchQuery>,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<ApiResponse<Vec<Identity>>>, StatusCode> {
+    let query = search.q.trim();
+    if query.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let identity_service = state.db.identities();
+
+    match identity_service
+        .search(query, pagination.limit, pagination.offset)
+        .await
+    {
+        Ok(identities) => Ok(Json(ApiResponse::success(identities))),
+        Err(e) => {
+            eprintln!("Error searching identities: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
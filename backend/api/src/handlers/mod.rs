@@ -1,8 +1,18 @@
+pub mod account;
+pub mod etherscan;
 pub mod evm;
+pub mod identity;
+pub mod search;
 pub mod substrate;
+pub mod webhook;
 pub mod websocket;
 
-use serde::{Deserialize, Serialize};
+use axum::http::StatusCode;
+use models::activity::CursorPage;
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Largest page a cursor query returns
+pub const MAX_PAGE_LIMIT: u32 = 100;
 
 #[derive(Debug, Deserialize)]
 pub struct PaginationQuery {
@@ -12,10 +22,55 @@ pub struct PaginationQuery {
     pub offset: u32,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct CursorQuery {
+    #[serde(default = "default_limit", deserialize_with = "page_limit")]
+    pub limit: u32, // Between 1 and `MAX_PAGE_LIMIT`, so `limit + 1` cannot overflow
+    pub cursor: Option<String>,
+}
+
+/// Parses an optional client cursor, a malformed one is a bad request
+pub fn parse_cursor<C>(
+    cursor: Option<&str>,
+    parse: impl Fn(&str) -> Option<C>,
+) -> Result<Option<C>, StatusCode> {
+    cursor
+        .map(|cursor| {
+            parse(cursor).ok_or_else(|| {
+                eprintln!("Invalid cursor: {}", cursor);
+                StatusCode::BAD_REQUEST
+            })
+        })
+        .transpose()
+}
+
+/// Builds a page from up to `limit + 1` items, the extra one only tells that more exist
+pub fn cursor_page<T>(
+    mut items: Vec<T>,
+    limit: u32,
+    cursor: impl Fn(&T) -> String,
+) -> CursorPage<T> {
+    let has_more = items.len() > limit as usize;
+    items.truncate(limit as usize);
+
+    CursorPage {
+        next_cursor: if has_more {
+            items.last().map(cursor)
+        } else {
+            None
+        },
+        items,
+    }
+}
+
 fn default_limit() -> u32 {
     20
 }
 
+fn page_limit<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    Ok(u32::deserialize(deserializer)?.clamp(1, MAX_PAGE_LIMIT))
+}
+
 fn default_offset() -> u32 {
     0
 }
@@ -45,3 +100,30 @@ impl<T> ApiResponse<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limit(query: &str) -> u32 {
+        serde_json::from_str::<CursorQuery>(query).unwrap().limit
+    }
+
+    #[test]
+    fn cursor_limit_is_clamped() {
+        assert_eq!(limit("{}"), 20);
+        assert_eq!(limit(r#"{"limit": 0}"#), 1);
+        assert_eq!(limit(r#"{"limit": 50}"#), 50);
+        assert_eq!(limit(r#"{"limit": 4294967295}"#), MAX_PAGE_LIMIT);
+    }
+
+    #[test]
+    fn cursor_page_detects_more() {
+        let page = cursor_page(vec![1, 2, 3], 2, |n| n.to_string());
+        assert_eq!(page.items, vec![1, 2]);
+        assert_eq!(page.next_cursor.as_deref(), Some("2"));
+
+        let page = cursor_page(vec![1, 2], 2, |n| n.to_string());
+        assert_eq!(page.next_cursor, None);
+    }
+}
@@ -0,0 +1,422 @@
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::StatusCode,
+};
+use blockscan::normalize_ss58;
+use custom_error::ServiceError;
+use models::{
+    evm::{ContractType, EvmContract},
+    search::{AddressMatch, SearchMatch, SearchQueryKind, SearchResult, SearchResults},
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::{
+    AppState,
+    handlers::{ApiResponse, account::account_addresses, identity::display_names},
+};
+
+const DEFAULT_SEARCH_LIMIT: u32 = 10;
+const MAX_SEARCH_LIMIT: u32 = 50;
+
+/// Score of a block, transaction or address found by its exact number, hash or address
+const EXACT_SCORE: u32 = 100;
+/// Score of an address that is valid but has nothing indexed
+const UNKNOWN_ADDRESS_SCORE: u32 = 50;
+
+#[derive(Debug, Deserialize)]
+pub struct SearchQuery {
+    pub q: String,
+    pub limit: Option<u32>,
+}
+
+/// Hex digits of a query with or without its `0x` prefix
+fn hex_digits(query: &str) -> &str {
+    query
+        .strip_prefix("0x")
+        .or_else(|| query.strip_prefix("0X"))
+        .unwrap_or(query)
+}
+
+fn is_hex_of_length(query: &str, length: usize) -> bool {
+    let hex = hex_digits(query);
+    hex.len() == length && hex.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Lowercase hash or address with its `0x` prefix, the way they are stored
+fn normalized_hex(query: &str) -> String {
+    format!("0x{}", hex_digits(query).to_lowercase())
+}
+
+/// Hashes and addresses are told apart by their length before numbers, a long enough run
+/// of digits is a valid hash. Digits only are a block number even past the highest one, which
+/// then finds nothing
+fn classify(query: &str) -> SearchQueryKind {
+    if is_hex_of_length(query, 64) {
+        SearchQueryKind::Hash
+    } else if is_hex_of_length(query, 40) {
+        SearchQueryKind::EvmAddress
+    } else if query.chars().all(|c| c.is_ascii_digit()) {
+        SearchQueryKind::BlockNumber
+    } else if normalize_ss58(query).is_ok() {
+        SearchQueryKind::Ss58Address
+    } else {
+        SearchQueryKind::Text
+    }
+}
+
+/// Accounts and identities are stored under the chain's prefix, an address of any other
+/// network is looked up in that form
+fn substrate_address(query: &str) -> String {
+    normalize_ss58(query).unwrap_or_else(|_| query.to_string())
+}
+
+fn result(score: u32, item: SearchMatch) -> SearchResult {
+    SearchResult { score, item }
+}
+
+fn is_token(contract: &EvmContract) -> bool {
+    matches!(
+        contract.contract_type,
+        ContractType::ERC20 | ContractType::ERC721 | ContractType::ERC1155
+    )
+}
+
+/// Text match score, an exact match beats a prefix and a short completion beats a long one
+fn text_score(value: Option<&str>, query: &str) -> Option<u32> {
+    let value = value?.to_lowercase();
+    if value == query {
+        Some(80)
+    } else if value.starts_with(query) {
+        let extra = (value.chars().count() - query.chars().count()).min(20) as u32;
+        Some(60 - extra)
+    } else {
+        None
+    }
+}
+
+pub async fn search(
+    State(state): State<Arc<AppState>>,
+    Query(search): Query<SearchQuery>,
+) -> Result<Json<ApiResponse<SearchResults>>, StatusCode> {
+    let query = search.q.trim();
+    if query.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let limit = search
+        .limit
+        .unwrap_or(DEFAULT_SEARCH_LIMIT)
+        .clamp(1, MAX_SEARCH_LIMIT);
+    let query_kind = classify(query);
+
+    let found = match query_kind {
+        SearchQueryKind::BlockNumber => search_block_number(&state, query).await,
+        SearchQueryKind::Hash => search_hash(&state, &normalized_hex(query)).await,
+        SearchQueryKind::EvmAddress => search_evm_address(&state, &normalized_hex(query)).await,
+        SearchQueryKind::Ss58Address => {
+            search_ss58_address(&state, &substrate_address(query)).await
+        }
+        SearchQueryKind::Text => search_text(&state, &query.to_lowercase(), limit).await,
+    };
+
+    match found {
+        Ok(mut results) => {
+            // Stable, so equal scores keep the order they were found in
+            results.sort_by(|a, b| b.score.cmp(&a.score));
+            results.truncate(limit as usize);
+
+            Ok(Json(ApiResponse::success(SearchResults {
+                query: query.to_string(),
+                query_kind,
+                results,
+            })))
+        }
+        Err(e) => {
+            eprintln!("Error searching for {}: {:?}", query, e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// A number is a block on either chain
+async fn search_block_number(
+    state: &AppState,
+    query: &str,
+) -> Result<Vec<SearchResult>, ServiceError> {
+    let Ok(number) = query.parse::<u32>() else {
+        return Ok(Vec::new());
+    };
+
+    let (evm_block, substrate_block) = tokio::try_join!(
+        state.db.evm_blocks().get_by_number(number),
+        state.db.substrate_blocks().get_by_number(number),
+    )?;
+
+    let mut results = Vec::new();
+    if let Some(block) = evm_block {
+        results.push(result(EXACT_SCORE, SearchMatch::EvmBlock(block)));
+    }
+    if let Some(block) = substrate_block {
+        results.push(result(EXACT_SCORE, SearchMatch::SubstrateBlock(block)));
+    }
+
+    Ok(results)
+}
+
+/// A 32-byte hash is an EVM transaction, an EVM block or a Substrate block
+async fn search_hash(state: &AppState, hash: &str) -> Result<Vec<SearchResult>, ServiceError> {
+    let (transaction, evm_block, substrate_block) = tokio::try_join!(
+        state.db.transactions().get_by_hash(hash),
+        state.db.evm_blocks().get_by_hash(hash),
+        state.db.substrate_blocks().get_by_hash(hash),
+    )?;
+
+    let mut results = Vec::new();
+    if let Some(transaction) = transaction {
+        results.push(result(
+            EXACT_SCORE,
+            SearchMatch::EvmTransaction(transaction),
+        ));
+    }
+    if let Some(block) = evm_block {
+        results.push(result(EXACT_SCORE, SearchMatch::EvmBlock(block)));
+    }
+    if let Some(block) = substrate_block {
+        results.push(result(EXACT_SCORE, SearchMatch::SubstrateBlock(block)));
+    }
+
+    Ok(results)
+}
+
+/// An H160 address is a contract or token when one is deployed there, and always an account
+async fn search_evm_address(
+    state: &AppState,
+    address: &str,
+) -> Result<Vec<SearchResult>, ServiceError> {
+    let (contract, account, addresses) = tokio::try_join!(
+        state.db.contracts().get_by_address(address),
+        state.db.accounts().get_by_address(address),
+        account_addresses(state, address),
+    )?;
+
+    // A linked SS58 account wins over the one derived from the address
+    let linked_address = addresses
+        .links
+        .first()
+        .map(|link| link.substrate_address.clone())
+        .or_else(|| addresses.substrate_addresses.first().cloned());
+
+    let mut results = Vec::new();
+    if let Some(contract) = contract {
+        let item = if is_token(&contract) {
+            SearchMatch::Token(contract)
+        } else {
+            SearchMatch::Contract(contract)
+        };
+        results.push(result(EXACT_SCORE, item));
+    }
+
+    let display_name = match &linked_address {
+        Some(linked) => display_names(state, vec![linked.clone()])
+            .await
+            .remove(linked),
+        None => None,
+    };
+    let score = if account.is_some() {
+        EXACT_SCORE
+    } else {
+        UNKNOWN_ADDRESS_SCORE
+    };
+    results.push(result(
+        score,
+        SearchMatch::EvmAccount(AddressMatch {
+            address: address.to_string(),
+            display_name,
+            linked_address,
+            account,
+        }),
+    ));
+
+    Ok(results)
+}
+
+/// An SS58 address is a Substrate account, shown with its linked EVM address
+async fn search_ss58_address(
+    state: &AppState,
+    address: &str,
+) -> Result<Vec<SearchResult>, ServiceError> {
+    let (account, addresses) = tokio::try_join!(
+        state.db.accounts().get_by_address(address),
+        account_addresses(state, address),
+    )?;
+
+    // A linked EVM account wins over the one derived from the public key
+    let linked_address = addresses
+        .links
+        .first()
+        .map(|link| link.evm_address.clone())
+        .or_else(|| addresses.evm_addresses.first().cloned());
+
+    let display_name = display_names(state, vec![address.to_string()])
+        .await
+        .remove(address);
+    let score = if account.is_some() || display_name.is_some() {
+        EXACT_SCORE
+    } else {
+        UNKNOWN_ADDRESS_SCORE
+    };
+
+    Ok(vec![result(
+        score,
+        SearchMatch::SubstrateAccount(AddressMatch {
+            address: address.to_string(),
+            display_name,
+            linked_address,
+            account,
+        }),
+    )])
+}
+
+/// Other text matches token names and symbols, contract names and identity display names
+/// by prefix, tokens first on equal scores
+async fn search_text(
+    state: &AppState,
+    query: &str,
+    limit: u32,
+) -> Result<Vec<SearchResult>, ServiceError> {
+    let (mut contracts, source_names, identities) = tokio::try_join!(
+        state.db.contracts().search_by_prefix(query, limit),
+        state
+            .db
+            .contract_sources()
+            .contract_names_by_prefix(query, limit),
+        state.db.identities().search_by_display_prefix(query, limit),
+    )?;
+
+    // Verified contracts found by their source name that were not matched on their own name
+    let missing: Vec<String> = source_names
+        .keys()
+        .filter(|address| !contracts.iter().any(|c| &c.address == *address))
+        .cloned()
+        .collect();
+    contracts.extend(state.db.contracts().get_by_addresses(&missing).await?);
+
+    let mut results = Vec::new();
+    for contract in contracts {
+        let name_score = [
+            text_score(contract.name.as_deref(), query),
+            text_score(contract.symbol.as_deref(), query),
+            text_score(
+                source_names.get(&contract.address).map(String::as_str),
+                query,
+            ),
+        ]
+        .into_iter()
+        .flatten()
+        .max();
+
+        let Some(score) = name_score else {
+            continue;
+        };
+
+        if is_token(&contract) {
+            results.push(result(score + 10, SearchMatch::Token(contract)));
+        } else {
+            results.push(result(score + 5, SearchMatch::Contract(contract)));
+        }
+    }
+
+    for identity in identities {
+        if let Some(score) = text_score(identity.display_name.as_deref(), query) {
+            results.push(result(score, SearchMatch::Identity(identity)));
+        }
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HASH: &str = "bc7cd75a20ee27fd9adebab32041f755214dbc6bffa90cc0225b39da2e5c2d3b";
+    const ADDRESS: &str = "742d35Cc6634C0532925a3b844Bc454e4438f44e";
+
+    #[test]
+    fn classifies_block_numbers() {
+        assert_eq!(classify("12345"), SearchQueryKind::BlockNumber);
+        // Past u32, still a number that finds no block
+        assert_eq!(classify("99999999999"), SearchQueryKind::BlockNumber);
+        // A sign is not part of a block number
+        assert_eq!(classify("+5"), SearchQueryKind::Text);
+        assert_eq!(classify("-5"), SearchQueryKind::Text);
+    }
+
+    #[test]
+    fn classifies_hashes_and_addresses_with_or_without_prefix() {
+        assert_eq!(classify(HASH), SearchQueryKind::Hash);
+        assert_eq!(classify(&format!("0x{}", HASH)), SearchQueryKind::Hash);
+        assert_eq!(classify(&"1".repeat(64)), SearchQueryKind::Hash);
+        assert_eq!(classify(ADDRESS), SearchQueryKind::EvmAddress);
+        assert_eq!(
+            classify(&format!("0X{}", ADDRESS)),
+            SearchQueryKind::EvmAddress
+        );
+        assert_eq!(classify(&"1".repeat(40)), SearchQueryKind::EvmAddress);
+    }
+
+    #[test]
+    fn classifies_ss58_addresses_and_text() {
+        assert_eq!(
+            classify("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"),
+            SearchQueryKind::Ss58Address
+        );
+        assert_eq!(
+            classify("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"),
+            SearchQueryKind::Ss58Address
+        );
+        assert_eq!(classify("usdt"), SearchQueryKind::Text);
+        assert_eq!(classify("0x1234"), SearchQueryKind::Text);
+    }
+
+    #[test]
+    fn looks_up_ss58_addresses_under_the_chain_prefix() {
+        // Alice on Polkadot (prefix 0) is stored as Alice on the generic prefix (42)
+        assert_eq!(
+            substrate_address("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"),
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        );
+        assert_eq!(
+            substrate_address("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"),
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        );
+    }
+
+    #[test]
+    fn normalizes_hex_queries() {
+        assert_eq!(
+            normalized_hex(ADDRESS),
+            format!("0x{}", ADDRESS.to_lowercase())
+        );
+        assert_eq!(
+            normalized_hex(&format!("0X{}", HASH)),
+            format!("0x{}", HASH)
+        );
+    }
+
+    #[test]
+    fn text_score_prefers_exact_and_short_matches() {
+        assert_eq!(text_score(Some("USDT"), "usdt"), Some(80));
+        assert_eq!(text_score(Some("usdt2"), "usdt"), Some(59));
+        assert!(text_score(Some("usdt2"), "usdt") > text_score(Some("usdt_bridged"), "usdt"));
+        // Long completions all score the same lowest prefix score
+        assert_eq!(
+            text_score(Some(&format!("usdt{}", "x".repeat(30))), "usdt"),
+            Some(40)
+        );
+        assert_eq!(text_score(Some("tether"), "usdt"), None);
+        assert_eq!(text_score(None, "usdt"), None);
+    }
+}
@@ -1,8 +1,43 @@
-use axum::{Json, extract::Path, http::StatusCode};
-use blockscan::SubstrtaeGeneralQuery;
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use blockscan::{SubstrtaeGeneralQuery, normalize_ss58};
 use config::SUBSTRATE_URL;
+use models::{
+    activity::{Cursor, CursorPage, SubstrateKey},
+    substrate::{SubstrateAccountFilter, SubstrateEvent, SubstrateExtrinsic},
+};
+use serde::Deserialize;
+use std::sync::Arc;
 
-use crate::handlers::ApiResponse;
+use crate::{
+    AppState,
+    handlers::{
+        ApiResponse, CursorQuery, cursor_page, identity::with_signer_displays, parse_cursor,
+    },
+};
+
+/// Filter of an account's extrinsics or events, `name` is the call or event name
+#[derive(Debug, Deserialize)]
+pub struct SubstrateAccountQuery {
+    pub module: Option<String>,
+    pub name: Option<String>,
+    pub from_timestamp: Option<u64>,
+    pub to_timestamp: Option<u64>,
+}
+
+impl From<SubstrateAccountQuery> for SubstrateAccountFilter {
+    fn from(query: SubstrateAccountQuery) -> Self {
+        Self {
+            module: query.module,
+            name: query.name,
+            from_timestamp: query.from_timestamp,
+            to_timestamp: query.to_timestamp,
+        }
+    }
+}
 
 // Account API handlers
 pub async fn get_ss58_from_evm(
@@ -30,3 +65,67 @@ pub async fn get_evm_from_ss58(
         Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
     }
 }
+
+pub async fn get_substrate_account_extrinsics(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(page): Query<CursorQuery>,
+    Query(query): Query<SubstrateAccountQuery>,
+) -> Result<Json<ApiResponse<CursorPage<SubstrateExtrinsic>>>, StatusCode> {
+    let address = normalize_ss58(&address).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let cursor = parse_cursor(page.cursor.as_deref(), Cursor::<SubstrateKey>::parse)?;
+    let extrinsic_service = state.db.substrate_extrinsics();
+
+    match extrinsic_service
+        .get_by_account(&address, &query.into(), cursor.as_ref(), page.limit + 1)
+        .await
+    {
+        Ok(extrinsics) => Ok(Json(ApiResponse::success(cursor_page(
+            with_signer_displays(&state, extrinsics).await,
+            page.limit,
+            |extrinsic| {
+                Cursor::new(
+                    extrinsic.timestamp as u128,
+                    (extrinsic.block_number, extrinsic.extrinsic_index),
+                )
+                .encode()
+            },
+        )))),
+        Err(e) => {
+            eprintln!("Error fetching account extrinsics: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn get_substrate_account_events(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(page): Query<CursorQuery>,
+    Query(query): Query<SubstrateAccountQuery>,
+) -> Result<Json<ApiResponse<CursorPage<SubstrateEvent>>>, StatusCode> {
+    let address = normalize_ss58(&address).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let cursor = parse_cursor(page.cursor.as_deref(), Cursor::<SubstrateKey>::parse)?;
+    let event_service = state.db.substrate_events();
+
+    match event_service
+        .get_by_account(&address, &query.into(), cursor.as_ref(), page.limit + 1)
+        .await
+    {
+        Ok(events) => Ok(Json(ApiResponse::success(cursor_page(
+            events,
+            page.limit,
+            |event| {
+                Cursor::new(
+                    event.timestamp as u128,
+                    (event.block_number, event.event_index),
+                )
+                .encode()
+            },
+        )))),
+        Err(e) => {
+            eprintln!("Error fetching account events: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
@@ -3,7 +3,7 @@ use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
 };
-use models::substrate::SubstrateEvent;
+use models::substrate::{EventFieldFilter, SubstrateEvent};
 use serde::Deserialize;
 use std::sync::Arc;
 
@@ -21,6 +21,18 @@ pub struct EventByModuleQuery {
     pub limit: u32,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct EventByFieldQuery {
+    pub module: String,
+    pub event: String,
+    pub field: String,
+    pub value: String,
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+    #[serde(default)]
+    pub offset: u32,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct RecentEventsQuery {
     #[serde(default = "default_hours")]
@@ -101,6 +113,34 @@ pub async fn get_substrate_events_by_module(
     }
 }
 
+pub async fn get_substrate_events_by_field(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<EventByFieldQuery>,
+) -> Result<Json<ApiResponse<Vec<SubstrateEvent>>>, StatusCode> {
+    let Some(filter) = EventFieldFilter::new(&query.field, &query.value) else {
+        return Err(StatusCode::BAD_REQUEST);
+    };
+
+    let event_service = state.db.substrate_events();
+
+    match event_service
+        .get_by_field(
+            &query.module,
+            &query.event,
+            &filter,
+            query.limit,
+            query.offset,
+        )
+        .await
+    {
+        Ok(events) => Ok(Json(ApiResponse::success(events))),
+        Err(e) => {
+            eprintln!("Error fetching substrate events by field: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
 pub async fn get_substrate_events_by_event_name(
     State(state): State<Arc<AppState>>,
     Path(event_name): Path<String>,
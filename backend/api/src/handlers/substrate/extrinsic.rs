@@ -3,13 +3,13 @@ use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
 };
-use models::substrate::SubstrateExtrinsic;
-use serde::Deserialize;
+use models::substrate::{SubstrateEvent, SubstrateExtrinsic};
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 use crate::{
     AppState,
-    handlers::{ApiResponse, PaginationQuery},
+    handlers::{ApiResponse, PaginationQuery, identity::with_signer_displays},
 };
 
 #[derive(Debug, Deserialize)]
@@ -21,6 +21,13 @@ pub struct ExtrinsicByModuleQuery {
     pub limit: u32,
 }
 
+#[derive(Debug, Serialize)]
+pub struct SubstrateExtrinsicWithEvents {
+    #[serde(flatten)]
+    pub extrinsic: SubstrateExtrinsic,
+    pub events: Vec<SubstrateEvent>,
+}
+
 fn default_limit() -> u32 {
     20
 }
@@ -36,7 +43,9 @@ pub async fn get_all_substrate_extrinsics(
         .get_all(pagination.limit, pagination.offset)
         .await
     {
-        Ok(extrinsics) => Ok(Json(ApiResponse::success(extrinsics))),
+        Ok(extrinsics) => Ok(Json(ApiResponse::success(
+            with_signer_displays(&state, extrinsics).await,
+        ))),
         Err(e) => {
             eprintln!("Error fetching substrate extrinsics: {:?}", e);
             Err(StatusCode::INTERNAL_SERVER_ERROR)
@@ -51,7 +60,9 @@ pub async fn get_substrate_extrinsics_by_block_number(
     let extrinsic_service = state.db.substrate_extrinsics();
 
     match extrinsic_service.get_by_block_number(block_number).await {
-        Ok(extrinsics) => Ok(Json(ApiResponse::success(extrinsics))),
+        Ok(extrinsics) => Ok(Json(ApiResponse::success(
+            with_signer_displays(&state, extrinsics).await,
+        ))),
         Err(e) => {
             eprintln!(
                 "Error fetching substrate extrinsics by block number: {:?}",
@@ -73,7 +84,9 @@ pub async fn get_substrate_extrinsics_by_signer(
         .get_by_signer(&signer, pagination.limit)
         .await
     {
-        Ok(extrinsics) => Ok(Json(ApiResponse::success(extrinsics))),
+        Ok(extrinsics) => Ok(Json(ApiResponse::success(
+            with_signer_displays(&state, extrinsics).await,
+        ))),
         Err(e) => {
             eprintln!("Error fetching substrate extrinsics by signer: {:?}", e);
             Err(StatusCode::INTERNAL_SERVER_ERROR)
@@ -103,10 +116,48 @@ pub async fn get_substrate_extrinsics_by_module(
     };
 
     match result {
-        Ok(extrinsics) => Ok(Json(ApiResponse::success(extrinsics))),
+        Ok(extrinsics) => Ok(Json(ApiResponse::success(
+            with_signer_displays(&state, extrinsics).await,
+        ))),
         Err(e) => {
             eprintln!("Error fetching substrate extrinsics by module: {:?}", e);
             Err(StatusCode::INTERNAL_SERVER_ERROR)
         }
     }
 }
+
+pub async fn get_substrate_extrinsic_with_events(
+    State(state): State<Arc<AppState>>,
+    Path((block_number, extrinsic_index)): Path<(u32, u32)>,
+) -> Result<Json<ApiResponse<Option<SubstrateExtrinsicWithEvents>>>, StatusCode> {
+    let extrinsic = match state
+        .db
+        .substrate_extrinsics()
+        .get_by_block_and_index(block_number, extrinsic_index)
+        .await
+    {
+        Ok(Some(extrinsic)) => with_signer_displays(&state, vec![extrinsic])
+            .await
+            .remove(0),
+        Ok(None) => return Ok(Json(ApiResponse::success(None))),
+        Err(e) => {
+            eprintln!("Error fetching substrate extrinsic: {:?}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    match state
+        .db
+        .substrate_events()
+        .get_by_extrinsic(block_number, extrinsic_index)
+        .await
+    {
+        Ok(events) => Ok(Json(ApiResponse::success(Some(
+            SubstrateExtrinsicWithEvents { extrinsic, events },
+        )))),
+        Err(e) => {
+            eprintln!("Error fetching substrate extrinsic events: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
@@ -3,9 +3,15 @@ mod block;
 mod event;
 mod extrinsic;
 mod network;
+mod staking;
+mod transfer;
+mod validator;
 
 pub use account::*;
 pub use block::*;
 pub use event::*;
 pub use extrinsic::*;
 pub use network::*;
+pub use staking::*;
+pub use transfer::*;
+pub use validator::*;
@@ -20,7 +20,7 @@ pub async fn get_substrate_latest_block() -> Result<Json<ApiResponse<u32>>, axum
     Ok(Json(ApiResponse::success(latest_block)))
 }
 
-pub async fn get_total_issuance() -> Result<Json<ApiResponse<u128>>, axum::http::StatusCode> {
+pub async fn get_total_issuance() -> Result<Json<ApiResponse<String>>, axum::http::StatusCode> {
     let api = SubstrtaeGeneralQuery::new(SUBSTRATE_URL.as_str())
         .await
         .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
@@ -30,7 +30,7 @@ pub async fn get_total_issuance() -> Result<Json<ApiResponse<u128>>, axum::http:
         .await
         .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    Ok(Json(ApiResponse::success(total_issuance)))
+    Ok(Json(ApiResponse::success(total_issuance.to_string())))
 }
 
 pub async fn get_era_session() -> Result<Json<ApiResponse<SubstrateEra>>, axum::http::StatusCode> {
@@ -59,7 +59,7 @@ pub async fn get_era_session() -> Result<Json<ApiResponse<SubstrateEra>>, axum::
     Ok(Json(ApiResponse::success(session_era)))
 }
 
-pub async fn get_total_staking() -> Result<Json<ApiResponse<u128>>, axum::http::StatusCode> {
+pub async fn get_total_staking() -> Result<Json<ApiResponse<String>>, axum::http::StatusCode> {
     let api = SubstrtaeGeneralQuery::new(SUBSTRATE_URL.as_str())
         .await
         .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
@@ -69,5 +69,5 @@ pub async fn get_total_staking() -> Result<Json<ApiResponse<u128>>, axum::http::
         .await
         .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    Ok(Json(ApiResponse::success(total_staking)))
+    Ok(Json(ApiResponse::success(total_staking.to_string())))
 }
@@ -0,0 +1,150 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use models::substrate::{
+    EraPayout, EraValidatorPoints, StakingLedger, StakingReward, StakingSlash,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::{
+    AppState,
+    handlers::{ApiResponse, PaginationQuery},
+};
+
+/// Inclusive era range of a reward history
+#[derive(Debug, Deserialize)]
+pub struct EraRangeQuery {
+    pub from_era: Option<u32>,
+    pub to_era: Option<u32>,
+}
+
+// Substrate Staking API Handlers
+pub async fn get_staking_account(
+    State(state): State<Arc<AppState>>,
+    Path(stash): Path<String>,
+) -> Result<Json<ApiResponse<Option<StakingLedger>>>, StatusCode> {
+    let staking_service = state.db.staking();
+
+    match staking_service.get_ledger(&stash).await {
+        Ok(ledger) => Ok(Json(ApiResponse::success(ledger))),
+        Err(e) => {
+            eprintln!("Error fetching staking ledger: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn get_staking_rewards_by_stash(
+    State(state): State<Arc<AppState>>,
+    Path(stash): Path<String>,
+    Query(pagination): Query<PaginationQuery>,
+    Query(range): Query<EraRangeQuery>,
+) -> Result<Json<ApiResponse<Vec<StakingReward>>>, StatusCode> {
+    let staking_service = state.db.staking();
+
+    match staking_service
+        .get_rewards_by_stash(
+            &stash,
+            range.from_era,
+            range.to_era,
+            pagination.limit,
+            pagination.offset,
+        )
+        .await
+    {
+        Ok(rewards) => Ok(Json(ApiResponse::success(rewards))),
+        Err(e) => {
+            eprintln!("Error fetching staking rewards: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn get_staking_slashes_by_staker(
+    State(state): State<Arc<AppState>>,
+    Path(staker): Path<String>,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<ApiResponse<Vec<StakingSlash>>>, StatusCode> {
+    let staking_service = state.db.staking();
+
+    match staking_service
+        .get_slashes_by_staker(&staker, pagination.limit, pagination.offset)
+        .await
+    {
+        Ok(slashes) => Ok(Json(ApiResponse::success(slashes))),
+        Err(e) => {
+            eprintln!("Error fetching staking slashes: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn get_validator_era_rewards(
+    State(state): State<Arc<AppState>>,
+    Path(validator): Path<String>,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<ApiResponse<Vec<EraValidatorPoints>>>, StatusCode> {
+    let staking_service = state.db.staking();
+
+    match staking_service
+        .get_validator_eras(&validator, pagination.limit, pagination.offset)
+        .await
+    {
+        Ok(eras) => Ok(Json(ApiResponse::success(eras))),
+        Err(e) => {
+            eprintln!("Error fetching validator era rewards: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn get_era_payouts(
+    State(state): State<Arc<AppState>>,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<ApiResponse<Vec<EraPayout>>>, StatusCode> {
+    let staking_service = state.db.staking();
+
+    match staking_service
+        .get_era_payouts(pagination.limit, pagination.offset)
+        .await
+    {
+        Ok(payouts) => Ok(Json(ApiResponse::success(payouts))),
+        Err(e) => {
+            eprintln!("Error fetching era payouts: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn get_era_payout(
+    State(state): State<Arc<AppState>>,
+    Path(era): Path<u32>,
+) -> Result<Json<ApiResponse<Option<EraPayout>>>, StatusCode> {
+    let staking_service = state.db.staking();
+
+    match staking_service.get_era_payout(era).await {
+        Ok(payout) => Ok(Json(ApiResponse::success(payout))),
+        Err(e) => {
+            eprintln!("Error fetching era payout: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn get_era_validator_points(
+    State(state): State<Arc<AppState>>,
+    Path(era): Path<u32>,
+) -> Result<Json<ApiResponse<Vec<EraValidatorPoints>>>, StatusCode> {
+    let staking_service = state.db.staking();
+
+    match staking_service.get_era_validators(era).await {
+        Ok(validators) => Ok(Json(ApiResponse::success(validators))),
+        Err(e) => {
+            eprintln!("Error fetching era validator points: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use models::substrate::SubstrateTransfer;
+use std::sync::Arc;
+
+use crate::{
+    AppState,
+    handlers::{ApiResponse, PaginationQuery, identity::with_transfer_displays},
+};
+
+// Substrate Transfer API Handlers
+pub async fn get_substrate_transfers_by_account(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<ApiResponse<Vec<SubstrateTransfer>>>, StatusCode> {
+    let transfer_service = state.db.substrate_transfers();
+
+    match transfer_service
+        .get_by_account(&address, pagination.limit, pagination.offset)
+        .await
+    {
+        Ok(transfers) => Ok(Json(ApiResponse::success(
+            with_transfer_displays(&state, transfers).await,
+        ))),
+        Err(e) => {
+            eprintln!("Error fetching account transfers: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn get_substrate_transfers_by_block_number(
+    State(state): State<Arc<AppState>>,
+    Path(block_number): Path<u32>,
+) -> Result<Json<ApiResponse<Vec<SubstrateTransfer>>>, StatusCode> {
+    let transfer_service = state.db.substrate_transfers();
+
+    match transfer_service.get_by_block_number(block_number).await {
+        Ok(transfers) => Ok(Json(ApiResponse::success(
+            with_transfer_displays(&state, transfers).await,
+        ))),
+        Err(e) => {
+            eprintln!("Error fetching block transfers: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
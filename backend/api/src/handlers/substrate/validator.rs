@@ -0,0 +1,93 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use models::substrate::{StakingLedger, ValidatorSnapshot};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::{
+    AppState,
+    handlers::{ApiResponse, PaginationQuery, identity::with_validator_displays},
+};
+
+/// Era of a validator listing, the latest snapshotted era when omitted
+#[derive(Debug, Deserialize)]
+pub struct ValidatorEraQuery {
+    pub era: Option<u32>,
+}
+
+// Substrate Validator API Handlers
+pub async fn get_substrate_validators(
+    State(state): State<Arc<AppState>>,
+    Query(pagination): Query<PaginationQuery>,
+    Query(query): Query<ValidatorEraQuery>,
+) -> Result<Json<ApiResponse<Vec<ValidatorSnapshot>>>, StatusCode> {
+    let snapshot_service = state.db.validator_snapshots();
+
+    let era = match query.era {
+        Some(era) => era,
+        None => match snapshot_service.get_latest_era().await {
+            Ok(Some(era)) => era,
+            Ok(None) => return Ok(Json(ApiResponse::success(Vec::new()))),
+            Err(e) => {
+                eprintln!("Error fetching latest validator era: {:?}", e);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        },
+    };
+
+    match snapshot_service
+        .get_by_era(era, pagination.limit, pagination.offset)
+        .await
+    {
+        Ok(validators) => Ok(Json(ApiResponse::success(
+            with_validator_displays(&state, validators).await,
+        ))),
+        Err(e) => {
+            eprintln!("Error fetching validators: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn get_substrate_validator(
+    State(state): State<Arc<AppState>>,
+    Path(account): Path<String>,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<ApiResponse<Vec<ValidatorSnapshot>>>, StatusCode> {
+    let snapshot_service = state.db.validator_snapshots();
+
+    match snapshot_service
+        .get_by_account(&account, pagination.limit, pagination.offset)
+        .await
+    {
+        Ok(history) => Ok(Json(ApiResponse::success(
+            with_validator_displays(&state, history).await,
+        ))),
+        Err(e) => {
+            eprintln!("Error fetching validator history: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn get_substrate_validator_nominators(
+    State(state): State<Arc<AppState>>,
+    Path(account): Path<String>,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<ApiResponse<Vec<StakingLedger>>>, StatusCode> {
+    let staking_service = state.db.staking();
+
+    match staking_service
+        .get_nominators(&account, pagination.limit, pagination.offset)
+        .await
+    {
+        Ok(nominators) => Ok(Json(ApiResponse::success(nominators))),
+        Err(e) => {
+            eprintln!("Error fetching validator nominators: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
@@ -0,0 +1,319 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode, Uri, header::AUTHORIZATION},
+};
+use config::WEBHOOK_ALLOW_PRIVATE_URLS;
+use models::webhook::{Webhook, WebhookDelivery, WebhookFilter, is_public_ip};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::{
+    net::SocketAddr,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tokio::net::lookup_host;
+use uuid::Uuid;
+
+use crate::{
+    AppState,
+    handlers::{ApiResponse, PaginationQuery},
+};
+
+/// Shortest owner token accepted, it is the only credential guarding a client's webhooks
+const MIN_OWNER_TOKEN_LENGTH: usize = 32;
+
+#[derive(Debug, Deserialize)]
+pub struct WebhookRequest {
+    pub url: String,
+    pub filter: WebhookFilter,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// EVM addresses are stored lowercase, SS58 addresses as given
+fn normalize_address(address: &str) -> String {
+    let address = address.trim();
+    if address.starts_with("0x") {
+        address.to_lowercase()
+    } else {
+        address.to_string()
+    }
+}
+
+/// Checks the filter fields and normalizes its addresses
+fn validate_filter(filter: WebhookFilter) -> Result<WebhookFilter, String> {
+    Ok(match filter {
+        WebhookFilter::AddressActivity { address } => {
+            if address.trim().is_empty() {
+                return Err("address must not be empty".to_string());
+            }
+            WebhookFilter::AddressActivity {
+                address: normalize_address(&address),
+            }
+        }
+        WebhookFilter::TokenTransfer { token, min_value } => {
+            if let Some(min_value) = &min_value {
+                if min_value.is_empty() || !min_value.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(
+                        "min_value must be a decimal amount in the smallest unit".to_string()
+                    );
+                }
+            }
+            WebhookFilter::TokenTransfer {
+                token: token.as_deref().map(normalize_address),
+                min_value,
+            }
+        }
+        WebhookFilter::PalletEvent { pallet, event } => {
+            if pallet.trim().is_empty() {
+                return Err("pallet must not be empty".to_string());
+            }
+            WebhookFilter::PalletEvent { pallet, event }
+        }
+        WebhookFilter::ValidatorSlashed { validator } => WebhookFilter::ValidatorSlashed {
+            validator: validator.as_deref().map(normalize_address),
+        },
+        WebhookFilter::ValidatorOffline { validator } => WebhookFilter::ValidatorOffline {
+            validator: validator.as_deref().map(normalize_address),
+        },
+    })
+}
+
+/// Webhook as listed, its secret is only shown once on registration
+fn without_secret(mut webhook: Webhook) -> Webhook {
+    webhook.secret.clear();
+    webhook.owner.clear();
+    webhook
+}
+
+/// Hash of the `Authorization: Bearer` token, webhooks are only visible to the token they
+/// were registered with
+fn owner(headers: &HeaderMap) -> Result<String, StatusCode> {
+    let token = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+        .filter(|token| token.len() >= MIN_OWNER_TOKEN_LENGTH)
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    Ok(hex::encode(Sha256::digest(token.as_bytes())))
+}
+
+/// The webhook `id` when it belongs to `owner`, a webhook of another owner is not found
+async fn owned_webhook(
+    state: &AppState,
+    id: &str,
+    owner: &str,
+) -> Result<Option<Webhook>, StatusCode> {
+    match state.db.webhooks().get(id).await {
+        Ok(webhook) => Ok(webhook.filter(|webhook| webhook.owner == owner)),
+        Err(e) => {
+            eprintln!("Error fetching webhook: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Checks the URL is http or https and that its host only resolves to public addresses.
+/// Delivery resolves the host again, so a name pointed elsewhere later is still refused
+async fn validate_url(url: &str) -> Result<(), String> {
+    let invalid = || "url must be an http or https URL".to_string();
+    let uri: Uri = url.parse().map_err(|_| invalid())?;
+    let default_port = match uri.scheme_str() {
+        Some("http") => 80,
+        Some("https") => 443,
+        _ => return Err(invalid()),
+    };
+    let host = uri
+        .host()
+        .ok_or_else(invalid)?
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+
+    if *WEBHOOK_ALLOW_PRIVATE_URLS {
+        return Ok(());
+    }
+
+    let addresses: Vec<SocketAddr> = lookup_host((host, uri.port_u16().unwrap_or(default_port)))
+        .await
+        .map_err(|e| format!("url host could not be resolved: {}", e))?
+        .collect();
+    if addresses.is_empty() || addresses.iter().any(|address| !is_public_ip(address.ip())) {
+        return Err("url must resolve to public addresses only".to_string());
+    }
+
+    Ok(())
+}
+
+// Webhook API handlers
+pub async fn register_webhook(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(request): Json<WebhookRequest>,
+) -> Result<Json<ApiResponse<Webhook>>, StatusCode> {
+    let owner = owner(&headers)?;
+
+    if let Err(message) = validate_url(&request.url).await {
+        return Ok(Json(ApiResponse::error(message)));
+    }
+
+    let filter = match validate_filter(request.filter) {
+        Ok(filter) => filter,
+        Err(message) => return Ok(Json(ApiResponse::error(message))),
+    };
+
+    let webhook = Webhook {
+        id: Uuid::new_v4().to_string(),
+        url: request.url,
+        secret: format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple()),
+        filter,
+        description: request.description,
+        created_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or(0),
+        owner,
+    };
+
+    match state.db.webhooks().save(&webhook).await {
+        Ok(()) => Ok(Json(ApiResponse::success(Webhook {
+            owner: String::new(),
+            ..webhook
+        }))),
+        Err(e) => {
+            eprintln!("Error saving webhook: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn get_webhooks(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Json<ApiResponse<Vec<Webhook>>>, StatusCode> {
+    let owner = owner(&headers)?;
+    let webhook_service = state.db.webhooks();
+
+    match webhook_service.get_by_owner(&owner).await {
+        Ok(webhooks) => Ok(Json(ApiResponse::success(
+            webhooks.into_iter().map(without_secret).collect(),
+        ))),
+        Err(e) => {
+            eprintln!("Error fetching webhooks: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn get_webhook(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<Option<Webhook>>>, StatusCode> {
+    let owner = owner(&headers)?;
+    let webhook = owned_webhook(&state, &id, &owner).await?;
+
+    Ok(Json(ApiResponse::success(webhook.map(without_secret))))
+}
+
+pub async fn delete_webhook(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<String>>, StatusCode> {
+    let owner = owner(&headers)?;
+    if owned_webhook(&state, &id, &owner).await?.is_none() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let webhook_service = state.db.webhooks();
+
+    match webhook_service.delete(&id).await {
+        Ok(true) => Ok(Json(ApiResponse::success(id))),
+        Ok(false) => Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            eprintln!("Error deleting webhook: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn get_webhook_deliveries(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<ApiResponse<Vec<WebhookDelivery>>>, StatusCode> {
+    let owner = owner(&headers)?;
+    if owned_webhook(&state, &id, &owner).await?.is_none() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let delivery_service = state.db.webhook_deliveries();
+
+    match delivery_service
+        .get_by_webhook(&id, pagination.limit, pagination.offset)
+        .await
+    {
+        Ok(deliveries) => Ok(Json(ApiResponse::success(deliveries))),
+        Err(e) => {
+            eprintln!("Error fetching webhook deliveries: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn get_webhook_dead_letters(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<ApiResponse<Vec<WebhookDelivery>>>, StatusCode> {
+    let owner = owner(&headers)?;
+    if owned_webhook(&state, &id, &owner).await?.is_none() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let delivery_service = state.db.webhook_deliveries();
+
+    match delivery_service
+        .get_dead_letters(&id, pagination.limit, pagination.offset)
+        .await
+    {
+        Ok(deliveries) => Ok(Json(ApiResponse::success(deliveries))),
+        Err(e) => {
+            eprintln!("Error fetching webhook dead letters: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(authorization: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, authorization.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn owner_requires_a_long_bearer_token() {
+        let token = "0123456789abcdef0123456789abcdef";
+
+        assert_eq!(owner(&HeaderMap::new()), Err(StatusCode::UNAUTHORIZED));
+        assert_eq!(owner(&headers(token)), Err(StatusCode::UNAUTHORIZED));
+        assert_eq!(
+            owner(&headers("Bearer too-short")),
+            Err(StatusCode::UNAUTHORIZED)
+        );
+
+        let hash = owner(&headers(&format!("Bearer {}", token))).unwrap();
+        assert_eq!(hash.len(), 64);
+        assert_ne!(hash, token);
+        assert_eq!(owner(&headers(&format!("Bearer {}", token))), Ok(hash));
+    }
+}
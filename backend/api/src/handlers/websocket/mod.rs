@@ -1,5 +1,7 @@
 // backend/api/src/handlers/websocket/mod.rs
-use crate::AppState;
+mod subscription;
+
+use crate::{AppState, events::BusMessage};
 use axum::{
     extract::{
         State,
@@ -8,32 +10,113 @@ use axum::{
     response::Response,
 };
 use futures::{sink::SinkExt, stream::StreamExt};
+use models::live::{ChainEvent, EventCursor, Feed, PushedEvent};
 use serde::{Deserialize, Serialize};
-use std::{sync::Arc, time::Duration};
-use tokio::time::interval;
+use std::sync::Arc;
+use subscription::{Subscription, SubscriptionFilter, Topic};
+use tokio::sync::broadcast::error::RecvError;
 use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Most subscriptions a single connection may hold
+const MAX_SUBSCRIPTIONS: usize = 64;
+/// Most blocks read from the database to resume a subscription or a lagging connection
+const MAX_REPLAY_BLOCKS: u64 = 1000;
+/// Blocks read at once while replaying
+const REPLAY_PAGE_BLOCKS: u64 = 50;
+
+type WebSocketSender = futures::stream::SplitSink<WebSocket, Message>;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum WebSocketMessage {
+    /// `id` is generated when the client does not pick one. With `after`, the cursor of
+    /// the last update received, the updates since are replayed before the new ones
     #[serde(rename = "subscribe")]
-    Subscribe { topic: String },
+    Subscribe {
+        topic: String,
+        #[serde(default)]
+        id: Option<String>,
+        #[serde(default)]
+        filter: SubscriptionFilter,
+        #[serde(default)]
+        after: Option<String>,
+    },
+    /// Removes the subscription `id`, or every subscription to `topic`
     #[serde(rename = "unsubscribe")]
-    Unsubscribe { topic: String },
+    Unsubscribe {
+        #[serde(default)]
+        id: Option<String>,
+        #[serde(default)]
+        topic: Option<String>,
+    },
+    /// `cursor` is the place in the feed the subscription starts after
+    #[serde(rename = "subscribed")]
+    Subscribed {
+        id: String,
+        topic: String,
+        filter: SubscriptionFilter,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cursor: Option<String>,
+    },
+    #[serde(rename = "unsubscribed")]
+    Unsubscribed { id: String },
     #[serde(rename = "block_update")]
-    BlockUpdate { data: serde_json::Value },
+    BlockUpdate {
+        subscription: String,
+        cursor: String,
+        data: serde_json::Value,
+    },
     #[serde(rename = "transaction_update")]
-    TransactionUpdate { data: serde_json::Value },
+    TransactionUpdate {
+        subscription: String,
+        cursor: String,
+        data: serde_json::Value,
+    },
+    #[serde(rename = "token_transfer_update")]
+    TokenTransferUpdate {
+        subscription: String,
+        cursor: String,
+        data: serde_json::Value,
+    },
     #[serde(rename = "substrate_block_update")]
-    SubstrateBlockUpdate { data: serde_json::Value },
+    SubstrateBlockUpdate {
+        subscription: String,
+        cursor: String,
+        data: serde_json::Value,
+    },
+    #[serde(rename = "substrate_extrinsic_update")]
+    SubstrateExtrinsicUpdate {
+        subscription: String,
+        cursor: String,
+        data: serde_json::Value,
+    },
     #[serde(rename = "substrate_event_update")]
-    SubstrateEventUpdate { data: serde_json::Value },
+    SubstrateEventUpdate {
+        subscription: String,
+        cursor: String,
+        data: serde_json::Value,
+    },
     #[serde(rename = "error")]
-    Error { message: String },
+    Error {
+        message: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        subscription: Option<String>,
+    },
+    /// Sent before `subscribed` and `unsubscribed` for clients of the topic-only protocol
     #[serde(rename = "success")]
     Success { message: String },
 }
 
+impl WebSocketMessage {
+    fn error(message: String, subscription: Option<String>) -> Self {
+        WebSocketMessage::Error {
+            message,
+            subscription,
+        }
+    }
+}
+
 pub async fn websocket_handler(
     ws: WebSocketUpgrade,
     State(state): State<Arc<AppState>>,
@@ -43,8 +126,10 @@ pub async fn websocket_handler(
 
 async fn handle_socket(socket: WebSocket, state: Arc<AppState>) {
     let (mut sender, mut receiver) = socket.split();
-    let mut subscribed_topics = Vec::new();
-    let mut poll_interval = interval(Duration::from_secs(5)); // Poll every 5 seconds
+    let mut subscriptions = Vec::new();
+    // Subscribed before any client message, every record above the feed heights read
+    // afterwards arrives on this receiver
+    let mut events = state.events.subscribe();
 
     info!("WebSocket connection established");
 
@@ -56,7 +141,8 @@ async fn handle_socket(socket: WebSocket, state: Arc<AppState>) {
                     Some(Ok(Message::Text(text))) => {
                         if let Err(e) = handle_client_message(
                             &text,
-                            &mut subscribed_topics,
+                            &state,
+                            &mut subscriptions,
                             &mut sender
                         ).await {
                             error!("Error handling client message: {}", e);
@@ -76,32 +162,38 @@ async fn handle_socket(socket: WebSocket, state: Arc<AppState>) {
                 }
             }
 
-            // Handle periodic updates for all subscribed topics
-            _ = poll_interval.tick() => {
-                if subscribed_topics.contains(&"blocks".to_string()) {
-                    if let Err(e) = send_block_update(&state, &mut sender).await {
-                        error!("Error sending EVM block update: {}", e);
-                        break;
+            // Push each newly indexed record to the subscriptions it matches
+            event = events.recv() => {
+                match event {
+                    Ok(BusMessage::Record(record)) => {
+                        if let Err(e) = send_record(&record, &mut subscriptions, false, &mut sender).await {
+                            error!("Error sending update: {}", e);
+                            break;
+                        }
                     }
-                }
-
-                if subscribed_topics.contains(&"transactions".to_string()) {
-                    if let Err(e) = send_transaction_update(&state, &mut sender).await {
-                        error!("Error sending transaction update: {}", e);
-                        break;
+                    Ok(BusMessage::Rewind { feed, block_number }) => {
+                        rewind(&mut subscriptions, feed, block_number);
                     }
-                }
-
-                if subscribed_topics.contains(&"substrate_blocks".to_string()) {
-                    if let Err(e) = send_substrate_block_update(&state, &mut sender).await {
-                        error!("Error sending substrate block update: {}", e);
-                        break;
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("WebSocket client lagged behind by {} updates, replaying", skipped);
+                        match catch_up(&state, &mut subscriptions, &mut sender).await {
+                            Ok(true) => {}
+                            Ok(false) => {
+                                let message = WebSocketMessage::error(
+                                    "Connection too slow, resubscribe after the cursor of the last update received".to_string(),
+                                    None,
+                                );
+                                let _ = send_message(&message, &mut sender).await;
+                                break;
+                            }
+                            Err(e) => {
+                                error!("Error replaying updates: {}", e);
+                                break;
+                            }
+                        }
                     }
-                }
-
-                if subscribed_topics.contains(&"substrate_events".to_string()) {
-                    if let Err(e) = send_substrate_event_update(&state, &mut sender).await {
-                        error!("Error sending substrate event update: {}", e);
+                    Err(RecvError::Closed) => {
+                        error!("Event bus closed");
                         break;
                     }
                 }
@@ -112,53 +204,130 @@ async fn handle_socket(socket: WebSocket, state: Arc<AppState>) {
 
 async fn handle_client_message(
     text: &str,
-    subscribed_topics: &mut Vec<String>,
-    sender: &mut futures::stream::SplitSink<WebSocket, Message>,
+    state: &AppState,
+    subscriptions: &mut Vec<Subscription>,
+    sender: &mut WebSocketSender,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let msg: WebSocketMessage = serde_json::from_str(text)?;
+    let msg: WebSocketMessage = match serde_json::from_str(text) {
+        Ok(msg) => msg,
+        Err(e) => {
+            let message = WebSocketMessage::error(format!("Invalid message: {}", e), None);
+            return send_message(&message, sender).await;
+        }
+    };
 
     match msg {
-        WebSocketMessage::Subscribe { topic } => {
-            // Validate topic before subscribing
-            let valid_topics = [
-                "blocks",
-                "transactions",
-                "substrate_blocks",
-                "substrate_events",
-            ];
-            if !valid_topics.contains(&topic.as_str()) {
-                let error_response = WebSocketMessage::Error {
-                    message: format!(
+        WebSocketMessage::Subscribe {
+            topic,
+            id,
+            filter,
+            after,
+        } => {
+            let id = id.unwrap_or_else(|| Uuid::new_v4().to_string());
+
+            let Some(parsed_topic) = Topic::parse(&topic) else {
+                let valid_topics: Vec<_> = Topic::ALL.iter().map(|topic| topic.name()).collect();
+                let message = WebSocketMessage::error(
+                    format!(
                         "Invalid topic: {}. Valid topics are: {}",
                         topic,
                         valid_topics.join(", ")
                     ),
-                };
-                let error_text = serde_json::to_string(&error_response)?;
-                sender.send(Message::Text(error_text.into())).await?;
-                return Ok(());
+                    Some(id),
+                );
+                return send_message(&message, sender).await;
+            };
+
+            if subscriptions
+                .iter()
+                .any(|subscription| subscription.id == id)
+            {
+                let message =
+                    WebSocketMessage::error("Subscription id already in use".to_string(), Some(id));
+                return send_message(&message, sender).await;
             }
 
-            if !subscribed_topics.contains(&topic) {
-                subscribed_topics.push(topic.clone());
-                info!("Client subscribed to topic: {}", topic);
+            if subscriptions.len() >= MAX_SUBSCRIPTIONS {
+                let message = WebSocketMessage::error(
+                    format!("At most {} subscriptions per connection", MAX_SUBSCRIPTIONS),
+                    Some(id),
+                );
+                return send_message(&message, sender).await;
+            }
 
-                let response = WebSocketMessage::Success {
-                    message: format!("Subscribed to {}", topic),
-                };
-                let response_text = serde_json::to_string(&response)?;
-                sender.send(Message::Text(response_text.into())).await?;
+            let mut subscription = match Subscription::new(id.clone(), parsed_topic, filter) {
+                Ok(subscription) => subscription,
+                Err(message) => {
+                    return send_message(&WebSocketMessage::error(message, Some(id)), sender).await;
+                }
+            };
+
+            let feed = subscription.feed();
+            let height = state.events.height(feed);
+            subscription.position = match after {
+                Some(after) => match resume_position(&after, feed, height) {
+                    Ok(position) => Some(position),
+                    Err(message) => {
+                        return send_message(&WebSocketMessage::error(message, Some(id)), sender)
+                            .await;
+                    }
+                },
+                // Starts with the records the bus sends after this point
+                None => height.map(|height| EventCursor::end_of_block(feed, height)),
+            };
+
+            info!("Client subscribed to topic: {} ({})", topic, id);
+
+            let success = WebSocketMessage::Success {
+                message: format!("Subscribed to {}", topic),
+            };
+            send_message(&success, sender).await?;
+
+            let response = WebSocketMessage::Subscribed {
+                id,
+                topic,
+                filter: subscription.filter.clone(),
+                cursor: subscription.position.map(|position| position.to_string()),
+            };
+            subscriptions.push(subscription);
+            send_message(&response, sender).await?;
+
+            // Subscriptions already on the feed are past the replayed records and skip them
+            if let Some(height) = height {
+                replay(state, feed, height, subscriptions, sender).await?;
             }
         }
-        WebSocketMessage::Unsubscribe { topic } => {
-            subscribed_topics.retain(|t| t != &topic);
-            info!("Client unsubscribed from topic: {}", topic);
+        WebSocketMessage::Unsubscribe { id, topic } => {
+            let (removed, kept): (Vec<_>, Vec<_>) =
+                subscriptions.drain(..).partition(|subscription| {
+                    id.as_ref().is_some_and(|id| &subscription.id == id)
+                        || topic
+                            .as_ref()
+                            .is_some_and(|topic| subscription.topic.name() == topic)
+                });
+            *subscriptions = kept;
 
-            let response = WebSocketMessage::Success {
-                message: format!("Unsubscribed from {}", topic),
-            };
-            let response_text = serde_json::to_string(&response)?;
-            sender.send(Message::Text(response_text.into())).await?;
+            if removed.is_empty() {
+                let message = WebSocketMessage::error("No matching subscription".to_string(), id);
+                return send_message(&message, sender).await;
+            }
+
+            for subscription in removed {
+                info!(
+                    "Client unsubscribed from topic: {} ({})",
+                    subscription.topic.name(),
+                    subscription.id
+                );
+                let success = WebSocketMessage::Success {
+                    message: format!("Unsubscribed from {}", subscription.topic.name()),
+                };
+                send_message(&success, sender).await?;
+
+                let response = WebSocketMessage::Unsubscribed {
+                    id: subscription.id,
+                };
+                send_message(&response, sender).await?;
+            }
         }
         _ => {
             warn!("Unexpected message type from client");
@@ -168,124 +337,210 @@ async fn handle_client_message(
     Ok(())
 }
 
-async fn send_block_update(
-    state: &Arc<AppState>,
-    sender: &mut futures::stream::SplitSink<WebSocket, Message>,
-) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let block_service = state.db.evm_blocks();
-
-    match block_service.get_latest().await {
-        Ok(Some(block)) => {
-            let block_data = serde_json::to_value(&block)?;
-            let message = WebSocketMessage::BlockUpdate { data: block_data };
-            let message_text = serde_json::to_string(&message)?;
-
-            sender.send(Message::Text(message_text.into())).await?;
-        }
-        Ok(None) => {
-            // No blocks available yet
+/// Update message of a newly indexed record for one of its subscriptions
+fn chain_event_message(
+    subscription: String,
+    cursor: String,
+    data: serde_json::Value,
+    event: &ChainEvent,
+) -> WebSocketMessage {
+    match event {
+        ChainEvent::EvmBlock(_) | ChainEvent::FinalizedEvmBlock(_) => {
+            WebSocketMessage::BlockUpdate {
+                subscription,
+                cursor,
+                data,
+            }
         }
-        Err(e) => {
-            error!("Failed to get latest EVM block: {}", e);
-            let error_msg = WebSocketMessage::Error {
-                message: "Failed to get latest EVM block".to_string(),
-            };
-            let error_text = serde_json::to_string(&error_msg)?;
-            sender.send(Message::Text(error_text.into())).await?;
+        ChainEvent::EvmTransaction(_) => WebSocketMessage::TransactionUpdate {
+            subscription,
+            cursor,
+            data,
+        },
+        ChainEvent::TokenTransfer(_) => WebSocketMessage::TokenTransferUpdate {
+            subscription,
+            cursor,
+            data,
+        },
+        ChainEvent::SubstrateBlock(_) | ChainEvent::FinalizedSubstrateBlock(_) => {
+            WebSocketMessage::SubstrateBlockUpdate {
+                subscription,
+                cursor,
+                data,
+            }
         }
+        ChainEvent::SubstrateExtrinsic(_) => WebSocketMessage::SubstrateExtrinsicUpdate {
+            subscription,
+            cursor,
+            data,
+        },
+        ChainEvent::SubstrateEvent(_) => WebSocketMessage::SubstrateEventUpdate {
+            subscription,
+            cursor,
+            data,
+        },
     }
+}
 
-    Ok(())
+fn chain_event_data(event: &ChainEvent) -> Result<serde_json::Value, serde_json::Error> {
+    match event {
+        ChainEvent::EvmBlock(block) | ChainEvent::FinalizedEvmBlock(block) => {
+            serde_json::to_value(block)
+        }
+        ChainEvent::EvmTransaction(transaction) => serde_json::to_value(transaction),
+        ChainEvent::TokenTransfer(transfer) => serde_json::to_value(transfer),
+        ChainEvent::SubstrateBlock(block) | ChainEvent::FinalizedSubstrateBlock(block) => {
+            serde_json::to_value(block)
+        }
+        ChainEvent::SubstrateExtrinsic(extrinsic) => serde_json::to_value(extrinsic),
+        ChainEvent::SubstrateEvent(event) => serde_json::to_value(event),
+    }
 }
 
-async fn send_transaction_update(
-    state: &Arc<AppState>,
-    sender: &mut futures::stream::SplitSink<WebSocket, Message>,
-) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let transaction_service = state.db.transactions();
+/// Where a subscription resuming after the client's `after` cursor starts
+fn resume_position(after: &str, feed: Feed, height: Option<u64>) -> Result<EventCursor, String> {
+    let Some(position) = EventCursor::parse(after) else {
+        return Err(format!("Invalid cursor: {}", after));
+    };
+    if position.feed != feed {
+        return Err(format!(
+            "Cursor {} does not belong to the {} feed of this subscription",
+            after,
+            feed.name()
+        ));
+    }
+    if height.is_some_and(|height| height.saturating_sub(position.block_number) > MAX_REPLAY_BLOCKS)
+    {
+        return Err(format!(
+            "Cursor {} is too old, at most {} blocks are replayed",
+            after, MAX_REPLAY_BLOCKS
+        ));
+    }
 
-    match transaction_service.get_latest().await {
-        Ok(Some(transaction)) => {
-            let transaction_data = serde_json::to_value(&transaction)?;
-            let message = WebSocketMessage::TransactionUpdate {
-                data: transaction_data,
-            };
-            let message_text = serde_json::to_string(&message)?;
+    Ok(position)
+}
 
-            sender.send(Message::Text(message_text.into())).await?;
-        }
-        Ok(None) => {
-            // No transactions available yet
+/// Lowers the subscriptions past a reorganized block, so its new records are sent
+fn rewind(subscriptions: &mut [Subscription], feed: Feed, block_number: u64) {
+    let end = EventCursor::end_of_block(feed, block_number);
+
+    for subscription in subscriptions
+        .iter_mut()
+        .filter(|subscription| subscription.feed() == feed)
+    {
+        if subscription.position.is_some_and(|position| position > end) {
+            subscription.position = Some(end);
         }
-        Err(e) => {
-            error!("Failed to get latest transaction: {}", e);
-            let error_msg = WebSocketMessage::Error {
-                message: "Failed to get latest transaction".to_string(),
-            };
-            let error_text = serde_json::to_string(&error_msg)?;
-            sender.send(Message::Text(error_text.into())).await?;
+    }
+}
+
+/// Reads the records a lagging connection lost from the database. Returns `false`
+/// when a feed is too far behind to replay
+async fn catch_up(
+    state: &AppState,
+    subscriptions: &mut [Subscription],
+    sender: &mut WebSocketSender,
+) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+    for feed in Feed::ALL {
+        let Some(height) = state.events.height(feed) else {
+            continue;
+        };
+        // A reorganization notice may have been among the lost messages
+        rewind(subscriptions, feed, height);
+
+        let behind = subscriptions
+            .iter()
+            .filter(|subscription| subscription.feed() == feed)
+            .filter_map(|subscription| subscription.position)
+            .map(|position| height.saturating_sub(position.block_number))
+            .max();
+        if behind.is_some_and(|behind| behind > MAX_REPLAY_BLOCKS) {
+            return Ok(false);
         }
+
+        replay(state, feed, height, subscriptions, sender).await?;
     }
 
-    Ok(())
+    Ok(true)
 }
 
-async fn send_substrate_block_update(
-    state: &Arc<AppState>,
-    sender: &mut futures::stream::SplitSink<WebSocket, Message>,
+/// Sends the records of `feed` up to `height` that the subscriptions have not been handed
+async fn replay(
+    state: &AppState,
+    feed: Feed,
+    height: u64,
+    subscriptions: &mut [Subscription],
+    sender: &mut WebSocketSender,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let block_service = state.db.substrate_blocks();
-
-    match block_service.get_latest().await {
-        Ok(Some(block)) => {
-            let block_data = serde_json::to_value(&block)?;
-            let message = WebSocketMessage::SubstrateBlockUpdate { data: block_data };
-            let message_text = serde_json::to_string(&message)?;
+    // The block of a position is read again unless every record of it was handed over
+    let Some(mut from) = subscriptions
+        .iter()
+        .filter(|subscription| subscription.feed() == feed)
+        .filter_map(|subscription| subscription.position)
+        .map(|position| match position.position {
+            u32::MAX => position.block_number + 1,
+            _ => position.block_number,
+        })
+        .min()
+    else {
+        return Ok(());
+    };
 
-            sender.send(Message::Text(message_text.into())).await?;
-        }
-        Ok(None) => {
-            // No substrate blocks available yet
-        }
-        Err(e) => {
-            error!("Failed to get latest substrate block: {}", e);
-            let error_msg = WebSocketMessage::Error {
-                message: "Failed to get latest substrate block".to_string(),
-            };
-            let error_text = serde_json::to_string(&error_msg)?;
-            sender.send(Message::Text(error_text.into())).await?;
+    while from <= height {
+        let to = height.min(from + REPLAY_PAGE_BLOCKS - 1);
+        for record in state.db.live().records(feed, from, to).await? {
+            send_record(&record, subscriptions, true, sender).await?;
         }
+
+        from = to + 1;
     }
 
     Ok(())
 }
 
-async fn send_substrate_event_update(
-    state: &Arc<AppState>,
-    sender: &mut futures::stream::SplitSink<WebSocket, Message>,
+/// Sends a record to the subscriptions of its feed it is new to and matches. Replayed
+/// records skip the subscriptions with no position, which start with the live ones
+async fn send_record(
+    record: &PushedEvent,
+    subscriptions: &mut [Subscription],
+    replayed: bool,
+    sender: &mut WebSocketSender,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let event_service = state.db.substrate_events();
-
-    // Get recent events from the last hour with a limit of 10
-    match event_service.get_recent_events(1, 10).await {
-        Ok(events) => {
-            if !events.is_empty() {
-                let events_data = serde_json::to_value(&events)?;
-                let message = WebSocketMessage::SubstrateEventUpdate { data: events_data };
-                let message_text = serde_json::to_string(&message)?;
+    let mut data = None;
 
-                sender.send(Message::Text(message_text.into())).await?;
-            }
+    for subscription in subscriptions
+        .iter_mut()
+        .filter(|subscription| subscription.feed() == record.cursor.feed)
+    {
+        if !subscription.is_new(&record.cursor) || (replayed && subscription.position.is_none()) {
+            continue;
         }
-        Err(e) => {
-            error!("Failed to get recent substrate events: {}", e);
-            let error_msg = WebSocketMessage::Error {
-                message: "Failed to get recent substrate events".to_string(),
-            };
-            let error_text = serde_json::to_string(&error_msg)?;
-            sender.send(Message::Text(error_text.into())).await?;
+        subscription.position = Some(record.cursor);
+        if !subscription.matches(&record.event) {
+            continue;
+        }
+
+        if data.is_none() {
+            data = Some(chain_event_data(&record.event)?);
         }
+        let message = chain_event_message(
+            subscription.id.clone(),
+            record.cursor.to_string(),
+            data.clone().unwrap_or_default(),
+            &record.event,
+        );
+        send_message(&message, sender).await?;
     }
 
     Ok(())
 }
+
+async fn send_message(
+    message: &WebSocketMessage,
+    sender: &mut WebSocketSender,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let message_text = serde_json::to_string(message)?;
+    sender.send(Message::Text(message_text.into())).await?;
+
+    Ok(())
+}
@@ -0,0 +1,175 @@
+use models::live::{ChainEvent, EventCursor, Feed, name_matches, same_address};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Topic {
+    Blocks,
+    Transactions,
+    TokenTransfers,
+    SubstrateBlocks,
+    SubstrateExtrinsics,
+    SubstrateEvents,
+}
+
+impl Topic {
+    pub const ALL: [Topic; 6] = [
+        Topic::Blocks,
+        Topic::Transactions,
+        Topic::TokenTransfers,
+        Topic::SubstrateBlocks,
+        Topic::SubstrateExtrinsics,
+        Topic::SubstrateEvents,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Topic::Blocks => "blocks",
+            Topic::Transactions => "transactions",
+            Topic::TokenTransfers => "token_transfers",
+            Topic::SubstrateBlocks => "substrate_blocks",
+            Topic::SubstrateExtrinsics => "substrate_extrinsics",
+            Topic::SubstrateEvents => "substrate_events",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Topic> {
+        Self::ALL.into_iter().find(|topic| topic.name() == name)
+    }
+
+    /// Filter fields the topic can be narrowed by
+    fn filters(self) -> &'static [&'static str] {
+        match self {
+            Topic::Blocks | Topic::SubstrateBlocks => &["finalized"],
+            Topic::Transactions => &["address"],
+            Topic::TokenTransfers => &["address", "token"],
+            Topic::SubstrateExtrinsics | Topic::SubstrateEvents => &["address", "pallet", "name"],
+        }
+    }
+}
+
+/// Server-side filter of a subscription, every field that is set must match
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SubscriptionFilter {
+    /// Sender or recipient of a transaction or token transfer, signer of an
+    /// extrinsic or account mentioned by a Substrate event
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    /// Contract of a token transfer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// Pallet of an extrinsic or Substrate event
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pallet: Option<String>,
+    /// Call of an extrinsic or name of a Substrate event
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Push blocks once they are finalized instead of as soon as they are indexed
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub finalized: bool,
+}
+
+impl SubscriptionFilter {
+    fn fields(&self) -> Vec<&'static str> {
+        [
+            ("address", self.address.is_some()),
+            ("token", self.token.is_some()),
+            ("pallet", self.pallet.is_some()),
+            ("name", self.name.is_some()),
+            ("finalized", self.finalized),
+        ]
+        .into_iter()
+        .filter_map(|(field, set)| set.then_some(field))
+        .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Subscription {
+    pub id: String,
+    pub topic: Topic,
+    pub filter: SubscriptionFilter,
+    /// Last record of the feed handed to the subscription, sent or filtered out
+    pub position: Option<EventCursor>,
+}
+
+fn address_matches<'a>(
+    filter: &Option<String>,
+    mut addresses: impl Iterator<Item = &'a String>,
+) -> bool {
+    match filter {
+        Some(filter) => addresses.any(|address| same_address(filter, address)),
+        None => true,
+    }
+}
+
+impl Subscription {
+    /// Fails with a client facing message when the filter does not apply to the topic
+    pub fn new(id: String, topic: Topic, filter: SubscriptionFilter) -> Result<Self, String> {
+        if let Some(field) = filter
+            .fields()
+            .into_iter()
+            .find(|field| !topic.filters().contains(field))
+        {
+            return Err(format!(
+                "Filter {} is not supported by topic {}. Supported filters are: {}",
+                field,
+                topic.name(),
+                topic.filters().join(", ")
+            ));
+        }
+
+        Ok(Self {
+            id,
+            topic,
+            filter,
+            position: None,
+        })
+    }
+
+    pub fn feed(&self) -> Feed {
+        match self.topic {
+            Topic::Blocks if self.filter.finalized => Feed::FinalizedEvm,
+            Topic::Blocks | Topic::Transactions | Topic::TokenTransfers => Feed::Evm,
+            Topic::SubstrateBlocks if self.filter.finalized => Feed::FinalizedSubstrate,
+            Topic::SubstrateBlocks | Topic::SubstrateExtrinsics | Topic::SubstrateEvents => {
+                Feed::Substrate
+            }
+        }
+    }
+
+    /// Whether the record at `cursor` comes after everything the subscription was handed
+    pub fn is_new(&self, cursor: &EventCursor) -> bool {
+        self.position.is_none_or(|position| *cursor > position)
+    }
+
+    pub fn matches(&self, event: &ChainEvent) -> bool {
+        let filter = &self.filter;
+
+        match (self.topic, event) {
+            (Topic::Blocks, ChainEvent::EvmBlock(_))
+            | (Topic::SubstrateBlocks, ChainEvent::SubstrateBlock(_)) => !filter.finalized,
+            (Topic::Blocks, ChainEvent::FinalizedEvmBlock(_))
+            | (Topic::SubstrateBlocks, ChainEvent::FinalizedSubstrateBlock(_)) => filter.finalized,
+            (Topic::Transactions, ChainEvent::EvmTransaction(transaction)) => address_matches(
+                &filter.address,
+                std::iter::once(&transaction.from).chain(&transaction.to),
+            ),
+            (Topic::TokenTransfers, ChainEvent::TokenTransfer(transfer)) => {
+                address_matches(&filter.token, std::iter::once(&transfer.token_address))
+                    && address_matches(&filter.address, [&transfer.from, &transfer.to].into_iter())
+            }
+            (Topic::SubstrateExtrinsics, ChainEvent::SubstrateExtrinsic(extrinsic)) => {
+                address_matches(&filter.address, extrinsic.signer.iter())
+                    && name_matches(&filter.pallet, &extrinsic.call_module)
+                    && name_matches(&filter.name, &extrinsic.call_function)
+            }
+            (Topic::SubstrateEvents, ChainEvent::SubstrateEvent(event)) => {
+                address_matches(&filter.address, event.accounts.iter())
+                    && name_matches(&filter.pallet, &event.module)
+                    && name_matches(&filter.name, &event.event)
+            }
+            _ => false,
+        }
+    }
+}
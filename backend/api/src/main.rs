@@ -1,19 +1,43 @@
+pub mod events;
+pub mod graphql;
 pub mod handlers;
 pub mod middleware;
 pub mod routes;
 
+use blockscan::{
+    SubstrtaeGeneralQuery,
+    ethers::providers::{Http, Provider},
+};
 use config::{
     DATABASE_NAMESPACE, DATABASE_PASSWORD, DATABASE_TABLE, DATABASE_URL, DATABASE_USERNAME,
+    SUBSTRATE_URL,
 };
 use database::DatabaseService;
 use dotenv::dotenv;
+use graphql::ExplorerSchema;
 use middleware::create_app;
-use std::net::SocketAddr;
-use tokio::net::TcpListener;
+use std::{net::SocketAddr, sync::Arc};
+use tokio::{net::TcpListener, sync::OnceCell};
 
 #[derive(Clone)]
 pub struct AppState {
     pub db: DatabaseService,
+    /// Indexed records in order, see `events::spawn_event_bus`
+    pub events: events::EventBus,
+    pub graphql: ExplorerSchema,
+    /// EVM node, shared by every request that calls it
+    pub evm_provider: Arc<Provider<Http>>,
+    /// Substrate node, connected on first use and then shared
+    pub substrate: Arc<OnceCell<SubstrtaeGeneralQuery>>,
+}
+
+impl AppState {
+    /// Shared Substrate connection, a failed attempt is retried by the next caller
+    pub async fn substrate(&self) -> Result<&SubstrtaeGeneralQuery, subxt::Error> {
+        self.substrate
+            .get_or_try_init(|| SubstrtaeGeneralQuery::new(SUBSTRATE_URL.as_str()))
+            .await
+    }
 }
 
 #[tokio::main]
@@ -39,6 +63,7 @@ async fn main() -> anyhow::Result<()> {
     // Move the log statement BEFORE axum::serve() since serve() blocks indefinitely
     println!("🚀 Server running on http://{}", addr);
     println!("🔌 WebSocket endpoints available at ws://{}/ws/*", addr);
+    println!("🧭 GraphQL endpoint available at http://{}/graphql", addr);
 
     // This call blocks indefinitely until the server shuts down
     axum::serve(listener, app.into_make_service()).await?;
@@ -1,16 +1,30 @@
 use axum::{Router, http::Method};
+use blockscan::ethers::providers::{Http, Provider};
+use config::EVM_RPC_URL;
 use database::DatabaseService;
 use std::{sync::Arc, time::Duration};
+use tokio::sync::OnceCell;
 use tower_http::{
     cors::{Any, CorsLayer},
     timeout::TimeoutLayer,
     trace::TraceLayer,
 };
 
-use crate::{AppState, routes::create_api_routes};
+use crate::{AppState, events::spawn_event_bus, graphql::build_schema, routes::create_api_routes};
 
 pub async fn create_app(db: DatabaseService) -> Router {
-    let app_state = Arc::new(AppState { db });
+    let events = spawn_event_bus(db.clone());
+    let graphql = build_schema(db.clone());
+    let evm_provider = Arc::new(
+        Provider::<Http>::try_from(EVM_RPC_URL.as_str()).expect("RPC_URL must be a valid URL"),
+    );
+    let app_state = Arc::new(AppState {
+        db,
+        events,
+        graphql,
+        evm_provider,
+        substrate: Arc::new(OnceCell::new()),
+    });
 
     // Create the router first
     let app = create_api_routes().with_state(app_state);
@@ -20,7 +34,7 @@ pub async fn create_app(db: DatabaseService) -> Router {
         .layer(
             CorsLayer::new()
                 .allow_origin(Any)
-                .allow_methods([Method::GET])
+                .allow_methods([Method::GET, Method::POST, Method::DELETE])
                 .allow_headers(Any),
         )
         .layer(TimeoutLayer::new(Duration::from_secs(30)))
@@ -1,19 +1,52 @@
 use crate::AppState;
-use axum::{Router, routing::get};
+use axum::{
+    Router,
+    routing::{get, post},
+};
 use std::sync::Arc;
 
-use crate::handlers::{evm::*, substrate::*, websocket::websocket_handler};
+use crate::graphql::{graphiql, graphql_handler};
+use crate::handlers::{
+    account::*, etherscan::*, evm::*, identity::*, search::*, substrate::*, webhook::*,
+    websocket::websocket_handler,
+};
 
 pub fn create_api_routes() -> Router<Arc<AppState>> {
     Router::new()
         // ===== WEBSOCKET ENDPOINTS =====
         .route("/ws", get(websocket_handler))
+        // ===== GRAPHQL ENDPOINTS =====
+        .route("/graphql", get(graphiql).post(graphql_handler))
         // ===== NETWORK ENDPOINTS =====
         .route("/api/network", get(get_all_network_info))
         .route("/api/latest_block", get(get_substrate_latest_block))
         .route("/api/get_total_issuance", get(get_total_issuance))
         .route("/api/session_era", get(get_era_session))
         .route("/api/get_total_staking", get(get_total_staking))
+        // ===== SEARCH ENDPOINTS =====
+        .route("/api/search", get(search))
+        // ===== ACCOUNT ENDPOINTS =====
+        .route("/api/accounts/{address}", get(get_linked_account))
+        .route(
+            "/api/accounts/{address}/activity",
+            get(get_account_activity),
+        )
+        // ===== IDENTITY ENDPOINTS =====
+        .route("/api/identities/search", get(search_identities))
+        .route("/api/identities/{account}", get(get_identity))
+        // ===== ETHERSCAN COMPATIBLE ENDPOINTS =====
+        .route("/api/etherscan", get(etherscan_get).post(etherscan_post))
+        // ===== WEBHOOK ENDPOINTS =====
+        .route("/api/webhooks", get(get_webhooks).post(register_webhook))
+        .route(
+            "/api/webhooks/{id}",
+            get(get_webhook).delete(delete_webhook),
+        )
+        .route("/api/webhooks/{id}/deliveries", get(get_webhook_deliveries))
+        .route(
+            "/api/webhooks/{id}/dead-letters",
+            get(get_webhook_dead_letters),
+        )
         // ===== ADDRESS CONVERSION ENDPOINTS =====
         .route(
             "/api/convert/ss58_to_evm_address/{address}",
@@ -31,6 +64,11 @@ pub fn create_api_routes() -> Router<Arc<AppState>> {
         )
         .route("/api/evm/blocks/hash/{block_hash}", get(get_block_by_hash))
         .route("/api/evm/blocks/latest", get(get_latest_block))
+        .route("/api/evm/blocks/orphaned", get(get_orphaned_blocks))
+        .route(
+            "/api/evm/blocks/orphaned/number/{block_number}",
+            get(get_orphaned_blocks_by_number),
+        )
         // ===== EVM TRANSACTION ENDPOINTS =====
         .route("/api/evm/transactions", get(get_all_transactions))
         .route("/api/evm/transactions/latest", get(get_latest_transaction))
@@ -42,6 +80,19 @@ pub fn create_api_routes() -> Router<Arc<AppState>> {
             "/api/evm/transactions/hash/{tx_hash}",
             get(get_transaction_by_hash),
         )
+        .route(
+            "/api/evm/transactions/address/{address}",
+            get(get_transactions_by_address),
+        )
+        // ===== EVM INTERNAL TRANSACTION ENDPOINTS =====
+        .route(
+            "/api/evm/internal_transactions/tx/{tx_hash}",
+            get(get_internal_transactions_by_transaction),
+        )
+        .route(
+            "/api/evm/internal_transactions/address/{address}",
+            get(get_internal_transactions_by_address),
+        )
         // ===== EVM ACCOUNT ENDPOINTS =====
         .route("/api/evm/accounts", get(get_all_accounts))
         .route(
@@ -52,17 +103,47 @@ pub fn create_api_routes() -> Router<Arc<AppState>> {
             "/api/evm/accounts/balance",
             get(get_accounts_by_balance_range),
         )
+        .route(
+            "/api/evm/accounts/address/{address}/tokens",
+            get(get_account_tokens),
+        )
         // ===== EVM CONTRACT ENDPOINTS =====
         .route("/api/evm/contracts", get(get_all_contracts))
         .route(
             "/api/evm/contracts/address/{address}",
             get(get_contract_by_address),
         )
+        .route(
+            "/api/evm/contracts/address/{address}/source",
+            get(get_contract_source),
+        )
+        .route(
+            "/api/evm/contracts/address/{address}/implementations",
+            get(get_proxy_implementations),
+        )
         .route(
             "/api/evm/contracts/type/{contract_type}",
             get(get_contracts_by_type),
         )
         .route("/api/evm/contracts/verified", get(get_verified_contracts))
+        .route("/api/evm/contracts/verify", post(verify_contract))
+        // ===== EVM LOG ENDPOINTS =====
+        .route("/api/evm/logs", get(get_evm_logs))
+        // ===== EVM TOKEN ENDPOINTS =====
+        .route("/api/evm/tokens/{address}/holders", get(get_token_holders))
+        // ===== EVM TOKEN TRANSFER ENDPOINTS =====
+        .route(
+            "/api/evm/token_transfers/token/{address}",
+            get(get_token_transfers_by_token),
+        )
+        .route(
+            "/api/evm/token_transfers/address/{address}",
+            get(get_token_transfers_by_address),
+        )
+        .route(
+            "/api/evm/token_transfers/tx/{tx_hash}",
+            get(get_token_transfers_by_transaction),
+        )
         // ===== SUBSTRATE BLOCK ENDPOINTS =====
         .route("/api/substrate/blocks", get(get_all_substrate_blocks))
         .route(
@@ -77,6 +158,48 @@ pub fn create_api_routes() -> Router<Arc<AppState>> {
             "/api/substrate/blocks/latest",
             get(get_latest_substrate_block),
         )
+        // ===== SUBSTRATE ACCOUNT ENDPOINTS =====
+        .route(
+            "/api/substrate/accounts/{address}/extrinsics",
+            get(get_substrate_account_extrinsics),
+        )
+        .route(
+            "/api/substrate/accounts/{address}/events",
+            get(get_substrate_account_events),
+        )
+        // ===== SUBSTRATE VALIDATOR ENDPOINTS =====
+        .route("/api/substrate/validators", get(get_substrate_validators))
+        .route(
+            "/api/substrate/validators/{account}",
+            get(get_substrate_validator),
+        )
+        .route(
+            "/api/substrate/validators/{account}/nominators",
+            get(get_substrate_validator_nominators),
+        )
+        // ===== SUBSTRATE STAKING ENDPOINTS =====
+        .route(
+            "/api/substrate/staking/accounts/{stash}",
+            get(get_staking_account),
+        )
+        .route(
+            "/api/substrate/staking/rewards/{stash}",
+            get(get_staking_rewards_by_stash),
+        )
+        .route(
+            "/api/substrate/staking/slashes/{staker}",
+            get(get_staking_slashes_by_staker),
+        )
+        .route(
+            "/api/substrate/staking/validators/{validator}/eras",
+            get(get_validator_era_rewards),
+        )
+        .route("/api/substrate/staking/eras", get(get_era_payouts))
+        .route("/api/substrate/staking/eras/{era}", get(get_era_payout))
+        .route(
+            "/api/substrate/staking/eras/{era}/validators",
+            get(get_era_validator_points),
+        )
         // ===== SUBSTRATE EXTRINSIC ENDPOINTS =====
         .route(
             "/api/substrate/extrinsics",
@@ -94,6 +217,19 @@ pub fn create_api_routes() -> Router<Arc<AppState>> {
             "/api/substrate/extrinsics/module",
             get(get_substrate_extrinsics_by_module),
         )
+        .route(
+            "/api/substrate/extrinsics/{block_number}/{extrinsic_index}",
+            get(get_substrate_extrinsic_with_events),
+        )
+        // ===== SUBSTRATE TRANSFER ENDPOINTS =====
+        .route(
+            "/api/substrate/transfers/account/{address}",
+            get(get_substrate_transfers_by_account),
+        )
+        .route(
+            "/api/substrate/transfers/block/{block_number}",
+            get(get_substrate_transfers_by_block_number),
+        )
         // ===== SUBSTRATE EVENT ENDPOINTS =====
         .route("/api/substrate/events", get(get_all_substrate_events))
         .route(
@@ -104,6 +240,10 @@ pub fn create_api_routes() -> Router<Arc<AppState>> {
             "/api/substrate/events/module",
             get(get_substrate_events_by_module),
         )
+        .route(
+            "/api/substrate/events/field",
+            get(get_substrate_events_by_field),
+        )
         .route(
             "/api/substrate/events/name/{event_name}",
             get(get_substrate_events_by_event_name),
@@ -5,9 +5,9 @@ use crate::contract::EvmContractTypeInfo;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccountInfo {
     pub address: String,
-    pub balance: u128,
-    pub balance_token: f64,
-    pub free_balance: f64,
+    pub balance: String,       // Wei, decimal string
+    pub balance_token: String, // Exact amount in native token units
+    pub free_balance: String,
     pub nonce: u64,
     pub is_contract: bool,
     pub contract_type: Option<EvmContractTypeInfo>,
@@ -16,7 +16,7 @@ pub struct AccountInfo {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EvmAccount {
     pub address: String,
-    pub balance: u128,
+    pub balance: String,
     pub nonce: u64,
     pub is_contract: bool,
 }
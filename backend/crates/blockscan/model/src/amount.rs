@@ -0,0 +1,167 @@
+use ethers::types::U256;
+use serde::{Deserialize, Deserializer, Serializer};
+
+/// Decimals of the native token on both the EVM and the Substrate side
+pub const NATIVE_DECIMALS: u32 = 18;
+
+/// Exact decimal representation of `value / 10^decimals`, without trailing zeros
+pub fn format_units(value: impl Into<U256>, decimals: u32) -> String {
+    let digits = value.into().to_string();
+    let decimals = decimals as usize;
+
+    if decimals == 0 {
+        return digits;
+    }
+
+    let (integer, fraction) = if digits.len() > decimals {
+        let (integer, fraction) = digits.split_at(digits.len() - decimals);
+        (integer.to_string(), fraction.to_string())
+    } else {
+        (
+            "0".to_string(),
+            format!("{:0>width$}", digits, width = decimals),
+        )
+    };
+
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        integer
+    } else {
+        format!("{}.{}", integer, fraction)
+    }
+}
+
+/// Inverse of [`format_units`], `None` for malformed amounts or more than `decimals`
+/// fractional digits
+pub fn parse_units(amount: &str, decimals: u32) -> Option<U256> {
+    let (integer, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    let decimals = decimals as usize;
+
+    if integer.is_empty()
+        || fraction.len() > decimals
+        || !integer
+            .chars()
+            .chain(fraction.chars())
+            .all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let digits = format!("{}{:0<width$}", integer, fraction, width = decimals);
+    U256::from_dec_str(&digits).ok()
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrNumber {
+    String(String),
+    Number(u64), // Records stored before amounts became strings
+}
+
+impl StringOrNumber {
+    fn into_u128<E: serde::de::Error>(self) -> Result<u128, E> {
+        match self {
+            StringOrNumber::String(value) => value.parse().map_err(E::custom),
+            StringOrNumber::Number(value) => Ok(value as u128),
+        }
+    }
+}
+
+/// Deserializes a decimal string amount that older records stored as a JSON number,
+/// an integer in wei or a float in token units
+pub fn decimal_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Amount {
+        String(String),
+        Integer(u64),
+        Float(f64),
+    }
+
+    Ok(match Amount::deserialize(deserializer)? {
+        Amount::String(value) => value,
+        Amount::Integer(value) => value.to_string(),
+        // `Display` of an f64 never uses an exponent
+        Amount::Float(value) => value.to_string(),
+    })
+}
+
+/// Serializes a `u128` as a decimal string, JSON clients and the database only keep 53 or
+/// 64 bits of a number
+pub mod u128_string {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &u128, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u128, D::Error> {
+        StringOrNumber::deserialize(deserializer)?.into_u128()
+    }
+}
+
+/// [`u128_string`] for optional amounts
+pub mod option_u128_string {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<u128>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => serializer.serialize_some(&value.to_string()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<u128>, D::Error> {
+        Option::<StringOrNumber>::deserialize(deserializer)?
+            .map(StringOrNumber::into_u128)
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Record {
+        #[serde(deserialize_with = "decimal_string")]
+        amount: String,
+    }
+
+    fn amount(json: &str) -> String {
+        serde_json::from_str::<Record>(json).unwrap().amount
+    }
+
+    #[test]
+    fn decimal_string_accepts_legacy_numbers() {
+        assert_eq!(
+            amount(r#"{"amount": "340282366920938463463374607431768211455"}"#),
+            "340282366920938463463374607431768211455"
+        );
+        assert_eq!(amount(r#"{"amount": 21000}"#), "21000");
+        assert_eq!(amount(r#"{"amount": 1.5}"#), "1.5");
+        assert_eq!(amount(r#"{"amount": 1e21}"#), "1000000000000000000000");
+    }
+
+    #[test]
+    fn formats_and_parses_units() {
+        assert_eq!(
+            format_units(1_500_000_000_000_000_000u128, NATIVE_DECIMALS),
+            "1.5"
+        );
+        assert_eq!(format_units(1u64, NATIVE_DECIMALS), "0.000000000000000001");
+        assert_eq!(format_units(7u64, 0), "7");
+        assert_eq!(
+            parse_units("1.5", NATIVE_DECIMALS),
+            Some(U256::from(1_500_000_000_000_000_000u128))
+        );
+        assert_eq!(parse_units("1.5.0", NATIVE_DECIMALS), None);
+        assert_eq!(parse_units("-1", NATIVE_DECIMALS), None);
+        assert_eq!(parse_units("0.1", 0), None);
+    }
+}
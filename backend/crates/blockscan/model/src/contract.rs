@@ -8,6 +8,7 @@ pub struct EvmContractTypeInfo {
     pub symbol: Option<String>,
     pub decimals: Option<u8>,
     pub total_supply: Option<String>,
+    pub proxy: Option<ProxyInfo>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +23,25 @@ pub enum ContractType {
     Unknown,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ProxyKind {
+    MinimalProxy, // EIP-1167 clone
+    Eip1967,
+    Eip1822, // UUPS with the `PROXIABLE` slot
+    Transparent,
+    Beacon,
+}
+
+/// Implementation a proxy currently delegates to, the contract type of a proxy is the type
+/// of its implementation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProxyInfo {
+    pub kind: ProxyKind,
+    pub implementation: String,
+    pub beacon: Option<String>,
+    pub admin: Option<String>,
+}
+
 // Helper structs for metadata
 #[derive(Debug)]
 pub struct TokenMetadata {
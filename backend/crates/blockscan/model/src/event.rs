@@ -1,12 +1,16 @@
 use serde::{Deserialize, Serialize};
-use substrate_api_client::ac_primitives::H256;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FormattedEvent {
     pub index: usize,
     pub phase: String,
-    pub event: String,
-    pub topics: Vec<H256>,
+    pub extrinsic_index: Option<u32>,
+    pub pallet: String,
+    pub variant: String,
+    pub fields: serde_json::Value,
+    #[serde(default)]
+    pub accounts: Vec<String>, // SS58 accounts mentioned in `fields`
+    pub topics: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
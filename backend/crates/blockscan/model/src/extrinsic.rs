@@ -20,5 +20,15 @@ pub struct SignatureInfo {
 pub struct CallInfo {
     pub pallet: String,
     pub call: String,
-    pub args: Vec<(String, String)>,
+    pub args: serde_json::Value,
+}
+
+/// Result of applying an extrinsic, derived from the events it emitted
+#[derive(Debug, Clone, Default)]
+pub struct ExtrinsicOutcome {
+    pub success: bool,
+    pub dispatch_error: Option<String>,
+    pub actual_fee: Option<u128>,
+    pub tip: Option<u128>,
+    pub weight: Option<u64>,
 }
@@ -1,9 +1,14 @@
 pub mod account;
+pub mod amount;
 pub mod block;
 pub mod contract;
 pub mod event;
 pub mod extrinsic;
+pub mod log;
 pub mod method;
 pub mod netwiork;
+pub mod token_transfer;
+pub mod trace;
 pub mod transaction;
 pub mod validator;
+pub mod verification;
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// A receipt log with its parameters decoded when the event signature is known
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvmLogInfo {
+    pub address: String,
+    pub topics: Vec<String>,
+    pub data: String,
+    pub log_index: u64,
+    pub removed: bool,
+    pub decoded: Option<DecodedLog>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecodedLog {
+    pub name: String,
+    pub signature: String,
+    pub params: serde_json::Value, // Parameter name -> value
+}
@@ -8,6 +8,14 @@ pub struct FunctionInfo {
     pub signature: String,
 }
 
+/// Calldata decoded with the ABI of a verified contract
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecodedCall {
+    pub name: String,
+    pub signature: String,
+    pub params: serde_json::Value, // Parameter name -> value
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum TransactionMethod {
     // Basic transfers
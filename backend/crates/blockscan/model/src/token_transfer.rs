@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TokenStandard {
+    ERC20,
+    ERC721,
+    ERC1155,
+}
+
+/// A token movement decoded from a `Transfer`, `TransferSingle` or `TransferBatch` log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenTransferInfo {
+    pub token_address: String,
+    pub standard: TokenStandard,
+    pub operator: Option<String>,
+    pub from: String,
+    pub to: String,
+    pub value: Option<String>, // Decimal string, amount for ERC20/ERC1155
+    pub token_id: Option<String>, // Decimal string, ERC721/ERC1155 token id
+    pub log_index: u64,
+    pub batch_index: u32, // Position inside a TransferBatch, 0 otherwise
+}
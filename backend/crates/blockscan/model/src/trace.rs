@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+/// A call made during a transaction's execution, flattened from the `callTracer` tree
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InternalTransactionInfo {
+    pub trace_address: Vec<u32>, // Path from the top-level call, e.g. [0, 2]
+    pub call_type: String,       // CALL, STATICCALL, DELEGATECALL, CREATE, CREATE2, SELFDESTRUCT
+    pub from: String,
+    pub to: Option<String>,
+    pub value: String, // Decimal string in wei
+    pub gas: u64,
+    pub gas_used: u64,
+    pub input: String,
+    pub output: Option<String>,
+    pub error: Option<String>,
+}
+
+impl InternalTransactionInfo {
+    pub fn is_creation(&self) -> bool {
+        matches!(self.call_type.as_str(), "CREATE" | "CREATE2")
+    }
+}
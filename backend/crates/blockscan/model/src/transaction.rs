@@ -23,7 +23,7 @@ pub struct EvmTransactionInfo {
     pub timestamp: Option<u128>,
     pub from: String,
     pub to: Option<String>,
-    pub value: u128, // In wei as string to avoid precision loss
+    pub value: String, // In wei as string to avoid precision loss
     pub transaction_fee: TransactionFee,
     pub nonce: u64,
     pub input_data: String,
@@ -38,6 +38,6 @@ pub struct TransactionFee {
     pub gas_price: Option<u64>,
     pub max_fee_per_gas: Option<u64>,
     pub max_priority_fee_per_gas: Option<u64>,
-    pub total_fee: u64,
+    pub total_fee: String, // Wei, decimal string
     pub total_fee_eth: String,
 }
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::amount::u128_string;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ActiveValidator {
     pub account_id: String,
@@ -27,12 +29,44 @@ pub struct ActiveEra {
     pub start_session: u32,
     pub current_session: u32,
     pub end_session: u32,
+    #[serde(with = "u128_string")]
     pub total_stake: u128,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StakingInfo {
+    #[serde(with = "u128_string")]
     pub total: u128,
+    #[serde(with = "u128_string")]
     pub own: u128,
     pub nominator_count: u32,
 }
+
+/// Validator set of the active era as seen at a block of `session`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionValidators {
+    pub era: u32,
+    pub session: u32,
+    pub validators: Vec<SessionValidator>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionValidator {
+    pub validator: ActiveValidator,
+    pub blocks: u32, // Blocks produced so far in the session
+}
+
+/// Reward points earned by the validators of an era, from `ErasRewardPoints`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EraRewardPoints {
+    pub era: u32,
+    pub total: u32,
+    pub validators: Vec<ValidatorEraPoints>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidatorEraPoints {
+    pub account_id: String,
+    pub points: u32,
+    pub commission: f64, // Percent, from `ErasValidatorPrefs`
+}
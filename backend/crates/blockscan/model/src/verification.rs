@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+/// Sources submitted for verification, either as plain files plus compiler settings or as a
+/// complete solc standard-JSON input
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerificationRequest {
+    pub address: String,
+    pub contract_name: String, // `Token` or `contracts/Token.sol:Token`
+    #[serde(default)]
+    pub sources: Vec<SourceFile>,
+    #[serde(default)]
+    pub optimizer_enabled: bool,
+    #[serde(default = "default_optimizer_runs")]
+    pub optimizer_runs: u32,
+    #[serde(default)]
+    pub evm_version: Option<String>,
+    #[serde(default)]
+    pub standard_json: Option<serde_json::Value>,
+}
+
+fn default_optimizer_runs() -> u32 {
+    200
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceFile {
+    pub path: String,
+    pub content: String,
+}
+
+/// Compiler output of a contract whose runtime bytecode matched the deployed code
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifiedContract {
+    pub address: String,
+    pub contract_name: String,
+    pub source_path: String,
+    pub compiler_version: String,
+    pub abi: serde_json::Value,
+    pub sources: Vec<SourceFile>,
+    pub settings: serde_json::Value,
+    pub metadata: Option<String>,
+}
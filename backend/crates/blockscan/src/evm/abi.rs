@@ -0,0 +1,104 @@
+use blockscan_model::{log::DecodedLog, method::DecodedCall};
+use ethers::{
+    abi::{Abi, Param, RawLog, Token},
+    types::{H256, I256},
+};
+use serde_json::{Map, Value as JsonValue};
+
+/// Decodes transaction input with the ABI of the called contract
+pub fn decode_calldata(abi: &JsonValue, input: &str) -> Option<DecodedCall> {
+    let abi: Abi = serde_json::from_value(abi.clone()).ok()?;
+    let bytes = hex::decode(input.strip_prefix("0x").unwrap_or(input)).ok()?;
+    if bytes.len() < 4 {
+        return None;
+    }
+
+    let function = abi
+        .functions()
+        .find(|function| function.short_signature() == bytes[..4])?;
+    let tokens = function.decode_input(&bytes[4..]).ok()?;
+
+    Some(DecodedCall {
+        name: function.name.clone(),
+        signature: format!(
+            "{}({})",
+            function.name,
+            function
+                .inputs
+                .iter()
+                .map(|input| input.kind.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        ),
+        params: named_params(&function.inputs, &tokens),
+    })
+}
+
+/// Decodes a stored log with the ABI of the emitting contract
+pub fn decode_log_with_abi(abi: &JsonValue, topics: &[String], data: &str) -> Option<DecodedLog> {
+    let abi: Abi = serde_json::from_value(abi.clone()).ok()?;
+    let topics = topics
+        .iter()
+        .map(|topic| topic.parse::<H256>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    let topic0 = *topics.first()?;
+    let data = hex::decode(data.strip_prefix("0x").unwrap_or(data)).ok()?;
+
+    let event = abi
+        .events()
+        .find(|event| !event.anonymous && event.signature() == topic0)?;
+    let parsed = event.parse_log(RawLog { topics, data }).ok()?;
+
+    let mut params = Map::with_capacity(parsed.params.len());
+    for param in parsed.params {
+        params.insert(param.name, token_to_json(&param.value));
+    }
+
+    Some(DecodedLog {
+        name: event.name.clone(),
+        signature: format!(
+            "event {}({})",
+            event.name,
+            event
+                .inputs
+                .iter()
+                .map(|input| {
+                    let indexed = if input.indexed { " indexed" } else { "" };
+                    format!("{}{} {}", input.kind, indexed, input.name)
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        params: JsonValue::Object(params),
+    })
+}
+
+fn named_params(inputs: &[Param], tokens: &[Token]) -> JsonValue {
+    let mut params = Map::with_capacity(tokens.len());
+    for (index, (input, token)) in inputs.iter().zip(tokens).enumerate() {
+        let name = if input.name.is_empty() {
+            format!("arg{}", index)
+        } else {
+            input.name.clone()
+        };
+        params.insert(name, token_to_json(token));
+    }
+    JsonValue::Object(params)
+}
+
+/// Integers are decimal strings since they routinely exceed what JSON numbers can hold
+pub fn token_to_json(token: &Token) -> JsonValue {
+    match token {
+        Token::Address(address) => JsonValue::String(format!("{:#x}", address)),
+        Token::FixedBytes(bytes) | Token::Bytes(bytes) => {
+            JsonValue::String(format!("0x{}", hex::encode(bytes)))
+        }
+        Token::Int(value) => JsonValue::String(I256::from_raw(*value).to_string()),
+        Token::Uint(value) => JsonValue::String(value.to_string()),
+        Token::Bool(value) => JsonValue::Bool(*value),
+        Token::String(value) => JsonValue::String(value.clone()),
+        Token::FixedArray(tokens) | Token::Array(tokens) | Token::Tuple(tokens) => {
+            JsonValue::Array(tokens.iter().map(token_to_json).collect())
+        }
+    }
+}
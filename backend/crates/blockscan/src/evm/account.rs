@@ -1,5 +1,6 @@
 use blockscan_model::{
     account::AccountInfo,
+    amount::{NATIVE_DECIMALS, format_units},
     contract::{
         ContractCreationInfo, ContractType, EvmContractTypeInfo, NftMetadata, TokenMetadata,
     },
@@ -13,11 +14,13 @@ use ethers::{
 use std::{collections::HashMap, sync::Arc};
 use tokio::time::{Duration, timeout};
 
+use super::proxy::ProxyQuery;
+
 // Constants for function selectors
 mod selectors {
     pub const SUPPORTS_INTERFACE: [u8; 4] = [0x01, 0xff, 0xc9, 0xa7];
     pub const TOTAL_SUPPLY: [u8; 4] = [0x18, 0x16, 0x0d, 0xdd];
-    pub const BALANCE_OF_ERC721: [u8; 4] = [0x70, 0xa0, 0x82, 0x31];
+    pub const BALANCE_OF_ERC721: [u8; 4] = [0x70, 0xa0, 0x82, 0x31]; // Also ERC20 balanceOf(address)
     pub const BALANCE_OF_ERC1155: [u8; 4] = [0x00, 0xfd, 0xd5, 0x8e];
     pub const NAME: [u8; 4] = [0x06, 0xfd, 0xde, 0x03];
     pub const SYMBOL: [u8; 4] = [0x95, 0xd8, 0x9b, 0x41];
@@ -104,7 +107,7 @@ impl AccountQuery {
 
         Ok(AccountInfo {
             address: format!("{:#x}", addr),
-            balance: balance.as_u128(),
+            balance: balance.to_string(),
             balance_token: Self::format_balance_ether(balance),
             free_balance: Self::format_balance_ether(balance),
             nonce: nonce.as_u64(),
@@ -147,21 +150,85 @@ impl AccountQuery {
         }))
     }
 
+    /// `balanceOf` of a token holder, ERC1155 balances are read for a single token id
+    pub async fn token_balance_of(
+        &self,
+        token: &str,
+        holder: &str,
+        token_id: Option<&str>,
+    ) -> Result<U256, ServiceError> {
+        let token = self.parse_address(token)?;
+        let holder = self.parse_address(holder)?;
+
+        let mut calldata = Vec::with_capacity(68);
+        match token_id {
+            Some(token_id) => {
+                let token_id = U256::from_dec_str(token_id).map_err(|e| {
+                    ServiceError::InvalidTransactionData(format!(
+                        "Invalid token id {}: {}",
+                        token_id, e
+                    ))
+                })?;
+                calldata.extend_from_slice(&selectors::BALANCE_OF_ERC1155);
+                calldata.extend_from_slice(H256::from(holder).as_bytes());
+                let mut id_bytes = [0u8; 32];
+                token_id.to_big_endian(&mut id_bytes);
+                calldata.extend_from_slice(&id_bytes);
+            }
+            None => {
+                calldata.extend_from_slice(&selectors::BALANCE_OF_ERC721);
+                calldata.extend_from_slice(H256::from(holder).as_bytes());
+            }
+        }
+
+        self.call_uint256_function(token, calldata, "balanceOf")
+            .await
+    }
+
+    pub async fn token_total_supply(&self, token: &str) -> Result<U256, ServiceError> {
+        let token = self.parse_address(token)?;
+        self.call_total_supply_function(token).await
+    }
+
     async fn detect_contract_type(
         &self,
         address: Address,
         code: &Bytes,
     ) -> Result<EvmContractTypeInfo, ServiceError> {
-        // Try ERC165 first (most reliable)
-        if let Ok(contract_type) = self.detect_by_erc165(address).await {
-            if !matches!(contract_type, ContractType::Unknown) {
-                return self.create_contract_info(contract_type, address).await;
+        let proxy = ProxyQuery::new(self.provider.clone(), None)
+            .detect(address, code)
+            .await?;
+
+        // Calls are delegated, only the bytecode signatures must come from the implementation
+        let implementation_code = match &proxy {
+            Some(proxy) => {
+                let implementation = self.parse_address(&proxy.implementation)?;
+                self.provider.get_code(implementation, None).await?
             }
+            None => code.clone(),
+        };
+
+        let mut contract_type = ContractType::Unknown;
+
+        // Try ERC165 first (most reliable)
+        if let Ok(erc165_type) = self.detect_by_erc165(address).await {
+            contract_type = erc165_type;
         }
 
         // Fallback to function signature detection
-        let contract_type = self.detect_by_function_signatures(address, code).await?;
-        self.create_contract_info(contract_type, address).await
+        if matches!(contract_type, ContractType::Unknown) {
+            contract_type = self
+                .detect_by_function_signatures(address, &implementation_code)
+                .await?;
+        }
+
+        if proxy.is_some() && matches!(contract_type, ContractType::Unknown) {
+            contract_type = ContractType::Proxy;
+        }
+
+        let mut info = self.create_contract_info(contract_type, address).await?;
+        info.proxy = proxy;
+        Ok(info)
     }
 
     async fn detect_by_erc165(&self, address: Address) -> Result<ContractType, ServiceError> {
@@ -331,6 +398,7 @@ impl AccountQuery {
             symbol: None,
             decimals: None,
             total_supply: None,
+            proxy: None,
         };
 
         match contract_type {
@@ -426,9 +494,19 @@ impl AccountQuery {
     }
 
     async fn call_total_supply_function(&self, address: Address) -> Result<U256, ServiceError> {
+        self.call_uint256_function(address, selectors::TOTAL_SUPPLY.to_vec(), "totalSupply")
+            .await
+    }
+
+    async fn call_uint256_function(
+        &self,
+        address: Address,
+        calldata: Vec<u8>,
+        function: &str,
+    ) -> Result<U256, ServiceError> {
         let call_request = TransactionRequest::new()
             .to(address)
-            .data(Bytes::from(selectors::TOTAL_SUPPLY.to_vec()));
+            .data(Bytes::from(calldata));
 
         let result = timeout(
             self.call_timeout,
@@ -439,9 +517,10 @@ impl AccountQuery {
         .map_err(|e| ServiceError::InvalidTransactionData(format!("Call failed: {}", e)))?;
 
         if result.len() != 32 {
-            return Err(ServiceError::InvalidTransactionData(
-                "Invalid totalSupply response".to_string(),
-            ));
+            return Err(ServiceError::InvalidTransactionData(format!(
+                "Invalid {} response",
+                function
+            )));
         }
 
         Ok(U256::from_big_endian(&result))
@@ -460,8 +539,8 @@ impl AccountQuery {
             .map_err(|_| ServiceError::InvalidTransactionHash(tx_hash.to_string()))
     }
 
-    fn format_balance_ether(balance: U256) -> f64 {
-        balance.as_u128() as f64 / 1e18
+    fn format_balance_ether(balance: U256) -> String {
+        format_units(balance, NATIVE_DECIMALS)
     }
 
     fn decode_string_response(result: &Bytes) -> Result<String, ServiceError> {
@@ -0,0 +1,63 @@
+use std::sync::OnceLock;
+
+use blockscan_model::log::{DecodedLog, EvmLogInfo};
+use ethers::{
+    abi::{HumanReadableParser, RawLog},
+    types::Log,
+};
+use serde_json::{Map, Value as JsonValue};
+
+use super::{abi::token_to_json, signature_lookup::SignatureLookupService};
+
+static SIGNATURES: OnceLock<SignatureLookupService> = OnceLock::new();
+
+fn signatures() -> &'static SignatureLookupService {
+    SIGNATURES.get_or_init(SignatureLookupService::new)
+}
+
+/// Formats receipt logs, decoding the ones emitted by a known event
+pub fn format_logs(logs: &[Log]) -> Vec<EvmLogInfo> {
+    logs.iter()
+        .map(|log| EvmLogInfo {
+            address: format!("{:#x}", log.address),
+            topics: log
+                .topics
+                .iter()
+                .map(|topic| format!("{:#x}", topic))
+                .collect(),
+            data: format!("0x{}", hex::encode(&log.data)),
+            log_index: log.log_index.map(|index| index.as_u64()).unwrap_or(0),
+            removed: log.removed.unwrap_or(false),
+            decoded: decode_log(log),
+        })
+        .collect()
+}
+
+/// Tries every known event sharing the log's topic0 until one matches its layout
+pub fn decode_log(log: &Log) -> Option<DecodedLog> {
+    let topic0 = hex::encode(log.topics.first()?);
+
+    signatures()
+        .lookup_event(&topic0)
+        .iter()
+        .find_map(|event_sig| {
+            let event = HumanReadableParser::parse_event(&event_sig.signature).ok()?;
+            let parsed = event
+                .parse_log(RawLog {
+                    topics: log.topics.clone(),
+                    data: log.data.to_vec(),
+                })
+                .ok()?;
+
+            let mut params = Map::with_capacity(parsed.params.len());
+            for param in parsed.params {
+                params.insert(param.name, token_to_json(&param.value));
+            }
+
+            Some(DecodedLog {
+                name: event_sig.name.clone(),
+                signature: event_sig.signature.clone(),
+                params: JsonValue::Object(params),
+            })
+        })
+}
@@ -4,27 +4,32 @@ use blockscan_model::{
     transaction::EvmTransactionInfo,
 };
 use custom_error::ServiceError;
-use ethers::providers::{Http, Middleware, Provider};
-use std::sync::Arc;
 
 use super::signature_lookup::{FunctionSignature, SignatureCategory, SignatureLookupService};
 
 pub struct Method {
-    provider: Arc<Provider<Http>>,
     signature_lookup: SignatureLookupService,
 }
 
+impl Default for Method {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Method {
-    pub fn new(provider: Arc<Provider<Http>>) -> Self {
+    pub fn new() -> Self {
         Self {
-            provider,
             signature_lookup: SignatureLookupService::new(),
         }
     }
 
+    /// `contract_type` is the indexed type of the called contract, recorded when it was
+    /// created or upgraded, and only used when the selector is not known
     pub async fn analyze_transaction_method(
         &self,
         tx_info: &EvmTransactionInfo,
+        contract_type: Option<ContractType>,
     ) -> Result<TransactionMethod, ServiceError> {
         // Early returns for simple cases
         if self.is_simple_transfer(&tx_info.input_data) {
@@ -46,11 +51,9 @@ impl Method {
             return Ok(method);
         }
 
-        // Fallback to contract type analysis
-        if let Some(to_address) = &tx_info.to {
-            if let Ok(contract_type) = self.analyze_contract_type(to_address).await {
-                return Ok(self.create_contract_call_with_type(contract_type, &function_sig));
-            }
+        // Fallback to the type of the called contract
+        if let Some(contract_type) = contract_type {
+            return Ok(self.create_contract_call_with_type(contract_type, &function_sig));
         }
 
         // Final fallback
@@ -364,24 +367,4 @@ impl Method {
             function_signature: Some(function_sig.to_string()),
         }
     }
-
-    async fn analyze_contract_type(
-        &self,
-        contract_address: &str,
-    ) -> Result<ContractType, ServiceError> {
-        let address: ethers::types::Address = contract_address.parse().map_err(|_| {
-            ServiceError::InvalidTransactionData(format!("Invalid address: {}", contract_address))
-        })?;
-
-        let code = self.provider.get_code(address, None).await?;
-        if code.is_empty() {
-            return Err(ServiceError::InvalidTransactionData(
-                "Not a contract".to_string(),
-            ));
-        }
-
-        // TODO: Enhanced contract type detection
-        // Could analyze bytecode patterns, check for standard interfaces, etc.
-        Ok(ContractType::Unknown)
-    }
 }
@@ -1,25 +1,40 @@
+pub mod abi;
 pub mod account;
+pub mod log;
 pub mod method;
+pub mod proxy;
 pub mod signature_lookup;
+pub mod token_transfer;
+pub mod trace;
 pub mod utils;
+pub mod verify;
 
 use account::AccountQuery;
 use blockscan_model::{
     account::AccountInfo,
     block::EvmBlockInfo,
-    contract::ContractCreationInfo,
+    contract::{ContractCreationInfo, ContractType, ProxyInfo},
+    log::EvmLogInfo,
     method::TransactionMethod,
     netwiork::EvmNetworkInfo,
+    token_transfer::TokenTransferInfo,
+    trace::InternalTransactionInfo,
     transaction::{EvmTransactionInfo, TransactionStatus},
+    verification::{VerificationRequest, VerifiedContract},
 };
 use custom_error::ServiceError;
 use ethers::{
     providers::{Http, Middleware, Provider},
-    types::{BlockId, H256},
+    types::{Address, BlockId, H256, TransactionReceipt, U256},
 };
+use log::format_logs;
 use method::Method;
+use proxy::ProxyQuery;
 use std::sync::Arc;
+use token_transfer::decode_token_transfers;
+use trace::TraceQuery;
 use utils::calculate_transaction_fee;
+use verify::ContractVerifier;
 
 pub struct BlockStateQuery {
     pub provider: Arc<Provider<Http>>,
@@ -129,7 +144,7 @@ impl BlockStateQuery {
             timestamp,
             from: format!("{:#x}", tx.from),
             to: tx.to.map(|addr| format!("{:#x}", addr)),
-            value: tx.value.as_u128(),
+            value: tx.value.to_string(),
             transaction_fee,
             nonce: tx.nonce.as_u64(),
             transaction_index: tx.transaction_index.map(|idx| idx.as_u64() as u16),
@@ -141,18 +156,33 @@ impl BlockStateQuery {
         Ok(transaction_info)
     }
 
+    /// Calldata of a transaction as a hex string, `None` when the node does not know it
+    pub async fn transaction_input(&self, tx_hash: &str) -> Result<Option<String>, ServiceError> {
+        let hash: H256 = tx_hash
+            .parse()
+            .map_err(|_| ServiceError::InvalidTransactionHash(tx_hash.to_string()))?;
+
+        let tx = self.provider.get_transaction(hash).await?;
+        Ok(tx.map(|tx| format!("0x{}", hex::encode(&tx.input))))
+    }
+
     // New account query methods
     pub async fn query_account(&self, address: &str) -> Result<AccountInfo, ServiceError> {
         let account_query = AccountQuery::new(self.provider.clone());
         account_query.query_account(address).await
     }
 
+    /// Classifies a transaction by its selector, falling back to the indexed type of the
+    /// called contract
     pub async fn get_transaction_method(
         &self,
         tx_info: &EvmTransactionInfo,
+        contract_type: Option<ContractType>,
     ) -> Result<TransactionMethod, ServiceError> {
-        let method = Method::new(self.provider.clone());
-        Ok(method.analyze_transaction_method(tx_info).await?)
+        let method = Method::new();
+        Ok(method
+            .analyze_transaction_method(tx_info, contract_type)
+            .await?)
     }
 
     pub async fn transactions_hash_in_block(&self) -> Result<Vec<H256>, ServiceError> {
@@ -172,4 +202,94 @@ impl BlockStateQuery {
         let account_query = AccountQuery::new(self.provider.clone());
         account_query.get_contract_creation_info(tx_hash).await
     }
+
+    pub async fn token_transfers(
+        &self,
+        tx_hash: &str,
+    ) -> Result<Vec<TokenTransferInfo>, ServiceError> {
+        let receipt = self.transaction_receipt(tx_hash).await?;
+        Ok(decode_token_transfers(&receipt.logs))
+    }
+
+    pub async fn transaction_logs(&self, tx_hash: &str) -> Result<Vec<EvmLogInfo>, ServiceError> {
+        let receipt = self.transaction_receipt(tx_hash).await?;
+        Ok(format_logs(&receipt.logs))
+    }
+
+    async fn transaction_receipt(&self, tx_hash: &str) -> Result<TransactionReceipt, ServiceError> {
+        let hash: H256 = tx_hash
+            .parse()
+            .map_err(|_| ServiceError::InvalidTransactionHash(tx_hash.to_string()))?;
+
+        self.provider
+            .get_transaction_receipt(hash)
+            .await?
+            .ok_or_else(|| ServiceError::TransactionReceiptNotFound(tx_hash.to_string()))
+    }
+
+    pub async fn token_balance_of(
+        &self,
+        token: &str,
+        holder: &str,
+        token_id: Option<&str>,
+    ) -> Result<U256, ServiceError> {
+        let account_query = AccountQuery::new(self.provider.clone());
+        account_query
+            .token_balance_of(token, holder, token_id)
+            .await
+    }
+
+    pub async fn token_total_supply(&self, token: &str) -> Result<U256, ServiceError> {
+        let account_query = AccountQuery::new(self.provider.clone());
+        account_query.token_total_supply(token).await
+    }
+
+    /// Requires a node with the `debug` namespace enabled
+    pub async fn internal_transactions(
+        &self,
+        tx_hash: &str,
+    ) -> Result<Vec<InternalTransactionInfo>, ServiceError> {
+        let trace_query = TraceQuery::new(self.provider.clone());
+        trace_query.internal_transactions(tx_hash).await
+    }
+
+    /// Compiles the submitted sources with the local solc and matches them against the
+    /// deployed runtime code
+    pub async fn verify_contract(
+        &self,
+        request: &VerificationRequest,
+    ) -> Result<VerifiedContract, ServiceError> {
+        let verifier = ContractVerifier::new(self.provider.clone());
+        verifier.verify(request).await
+    }
+
+    /// Proxy standard and implementation of a contract as of this query's block
+    pub async fn proxy_info(&self, address: &str) -> Result<Option<ProxyInfo>, ServiceError> {
+        let addr: Address = address.parse().map_err(|_| {
+            ServiceError::InvalidTransactionData(format!("Invalid address: {}", address))
+        })?;
+        let code = self.provider.get_code(addr, Some(self.block_id)).await?;
+        if code.is_empty() {
+            return Ok(None);
+        }
+
+        let proxy_query = ProxyQuery::new(self.provider.clone(), Some(self.block_id));
+        proxy_query.detect(addr, &code).await
+    }
+
+    /// Implementation served by a beacon as of this query's block
+    pub async fn beacon_implementation(
+        &self,
+        beacon: &str,
+    ) -> Result<Option<String>, ServiceError> {
+        let beacon: Address = beacon.parse().map_err(|_| {
+            ServiceError::InvalidTransactionData(format!("Invalid address: {}", beacon))
+        })?;
+
+        let proxy_query = ProxyQuery::new(self.provider.clone(), Some(self.block_id));
+        Ok(proxy_query
+            .beacon_implementation(beacon)
+            .await?
+            .map(|implementation| format!("{:#x}", implementation)))
+    }
 }
@@ -0,0 +1,227 @@
+use std::sync::Arc;
+
+use blockscan_model::contract::{ProxyInfo, ProxyKind};
+use custom_error::ServiceError;
+use ethers::{
+    providers::{Http, Middleware, Provider},
+    types::{Address, BlockId, Bytes, H256, TransactionRequest},
+};
+use tokio::time::{Duration, timeout};
+
+// Storage slots holding the implementation, beacon and admin addresses
+mod slots {
+    use ethers::types::H256;
+
+    // bytes32(uint256(keccak256("eip1967.proxy.implementation")) - 1)
+    pub const EIP1967_IMPLEMENTATION: H256 = H256([
+        0x36, 0x08, 0x94, 0xa1, 0x3b, 0xa1, 0xa3, 0x21, 0x06, 0x67, 0xc8, 0x28, 0x49, 0x2d, 0xb9,
+        0x8d, 0xca, 0x3e, 0x20, 0x76, 0xcc, 0x37, 0x35, 0xa9, 0x20, 0xa3, 0xca, 0x50, 0x5d, 0x38,
+        0x2b, 0xbc,
+    ]);
+    // bytes32(uint256(keccak256("eip1967.proxy.beacon")) - 1)
+    pub const EIP1967_BEACON: H256 = H256([
+        0xa3, 0xf0, 0xad, 0x74, 0xe5, 0x42, 0x3a, 0xeb, 0xfd, 0x80, 0xd3, 0xef, 0x43, 0x46, 0x57,
+        0x83, 0x35, 0xa9, 0xa7, 0x2a, 0xea, 0xee, 0x59, 0xff, 0x6c, 0xb3, 0x58, 0x2b, 0x35, 0x13,
+        0x3d, 0x50,
+    ]);
+    // bytes32(uint256(keccak256("eip1967.proxy.admin")) - 1)
+    pub const EIP1967_ADMIN: H256 = H256([
+        0xb5, 0x31, 0x27, 0x68, 0x4a, 0x56, 0x8b, 0x31, 0x73, 0xae, 0x13, 0xb9, 0xf8, 0xa6, 0x01,
+        0x6e, 0x24, 0x3e, 0x63, 0xb6, 0xe8, 0xee, 0x11, 0x78, 0xd6, 0xa7, 0x17, 0x85, 0x0b, 0x5d,
+        0x61, 0x03,
+    ]);
+    // keccak256("org.zeppelinos.proxy.implementation"), OpenZeppelin proxies before EIP-1967
+    pub const ZEPPELINOS_IMPLEMENTATION: H256 = H256([
+        0x70, 0x50, 0xc9, 0xe0, 0xf4, 0xca, 0x76, 0x9c, 0x69, 0xbd, 0x3a, 0x8e, 0xf7, 0x40, 0xbc,
+        0x37, 0x93, 0x4f, 0x8e, 0x2c, 0x03, 0x6e, 0x5a, 0x72, 0x3f, 0xd8, 0xee, 0x04, 0x8e, 0xd3,
+        0xf8, 0xc3,
+    ]);
+    // keccak256("org.zeppelinos.proxy.admin")
+    pub const ZEPPELINOS_ADMIN: H256 = H256([
+        0x10, 0xd6, 0xa5, 0x4a, 0x47, 0x54, 0xc8, 0x86, 0x9d, 0x68, 0x86, 0xb5, 0xf5, 0xd7, 0xfb,
+        0xfa, 0x5b, 0x45, 0x22, 0x23, 0x7e, 0xa5, 0xc6, 0x0d, 0x11, 0xbc, 0x4e, 0x7a, 0x1f, 0xf9,
+        0x39, 0x0b,
+    ]);
+    // keccak256("PROXIABLE")
+    pub const EIP1822_PROXIABLE: H256 = H256([
+        0xc5, 0xf1, 0x6f, 0x0f, 0xcc, 0x63, 0x9f, 0xa4, 0x8a, 0x69, 0x47, 0x83, 0x6d, 0x98, 0x50,
+        0xf5, 0x04, 0x79, 0x85, 0x23, 0xbf, 0x8c, 0x9a, 0x3a, 0x87, 0xd5, 0x87, 0x6c, 0xf6, 0x22,
+        0xbc, 0xf7,
+    ]);
+}
+
+// EIP-1167 runtime code around the 20 byte implementation address
+const MINIMAL_PROXY_PREFIX: [u8; 10] = [0x36, 0x3d, 0x3d, 0x37, 0x3d, 0x3d, 0x3d, 0x36, 0x3d, 0x73];
+const MINIMAL_PROXY_SUFFIX: [u8; 15] = [
+    0x5a, 0xf4, 0x3d, 0x82, 0x80, 0x3e, 0x90, 0x3d, 0x91, 0x60, 0x2b, 0x57, 0xfd, 0x5b, 0xf3,
+];
+
+// implementation() on an EIP-1967 beacon
+const BEACON_IMPLEMENTATION: [u8; 4] = [0x5c, 0x60, 0xda, 0x1b];
+
+/// Upgraded(address indexed implementation)
+pub const UPGRADED_TOPIC: &str =
+    "0xbc7cd75a20ee27fd9adebab32041f755214dbc6bffa90cc0225b39da2e5c2d3b";
+/// BeaconUpgraded(address indexed beacon)
+pub const BEACON_UPGRADED_TOPIC: &str =
+    "0x1cf3b03a6cf19fa2baba4df148e9dcabedea7f8a5c07840e207e5c089be95d3e";
+
+pub struct ProxyQuery {
+    provider: Arc<Provider<Http>>,
+    block_id: Option<BlockId>,
+    call_timeout: Duration,
+}
+
+impl ProxyQuery {
+    pub fn new(provider: Arc<Provider<Http>>, block_id: Option<BlockId>) -> Self {
+        Self {
+            provider,
+            block_id,
+            call_timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// Recognizes EIP-1167 clones from their bytecode and the other proxy standards from
+    /// their storage slots, returns `None` for contracts that are not proxies
+    pub async fn detect(
+        &self,
+        address: Address,
+        code: &Bytes,
+    ) -> Result<Option<ProxyInfo>, ServiceError> {
+        if let Some(implementation) = Self::minimal_proxy_implementation(code) {
+            return Ok(Some(Self::proxy_info(
+                ProxyKind::MinimalProxy,
+                implementation,
+                None,
+                None,
+            )));
+        }
+
+        if let Some(implementation) = self
+            .read_address_slot(address, slots::EIP1967_IMPLEMENTATION)
+            .await?
+        {
+            let admin = self
+                .read_address_slot(address, slots::EIP1967_ADMIN)
+                .await?;
+            let kind = if admin.is_some() {
+                ProxyKind::Transparent
+            } else {
+                ProxyKind::Eip1967
+            };
+            return Ok(Some(Self::proxy_info(kind, implementation, None, admin)));
+        }
+
+        if let Some(beacon) = self
+            .read_address_slot(address, slots::EIP1967_BEACON)
+            .await?
+        {
+            if let Some(implementation) = self.beacon_implementation(beacon).await? {
+                return Ok(Some(Self::proxy_info(
+                    ProxyKind::Beacon,
+                    implementation,
+                    Some(beacon),
+                    None,
+                )));
+            }
+        }
+
+        if let Some(implementation) = self
+            .read_address_slot(address, slots::ZEPPELINOS_IMPLEMENTATION)
+            .await?
+        {
+            let admin = self
+                .read_address_slot(address, slots::ZEPPELINOS_ADMIN)
+                .await?;
+            return Ok(Some(Self::proxy_info(
+                ProxyKind::Transparent,
+                implementation,
+                None,
+                admin,
+            )));
+        }
+
+        if let Some(implementation) = self
+            .read_address_slot(address, slots::EIP1822_PROXIABLE)
+            .await?
+        {
+            return Ok(Some(Self::proxy_info(
+                ProxyKind::Eip1822,
+                implementation,
+                None,
+                None,
+            )));
+        }
+
+        Ok(None)
+    }
+
+    /// Calls `implementation()` on a beacon
+    pub async fn beacon_implementation(
+        &self,
+        beacon: Address,
+    ) -> Result<Option<Address>, ServiceError> {
+        let call_request = TransactionRequest::new()
+            .to(beacon)
+            .data(Bytes::from(BEACON_IMPLEMENTATION.to_vec()));
+
+        match timeout(
+            self.call_timeout,
+            self.provider.call(&call_request.into(), self.block_id),
+        )
+        .await
+        {
+            Ok(Ok(result)) if result.len() == 32 => {
+                Ok(Self::non_zero_address(H256::from_slice(&result)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    async fn read_address_slot(
+        &self,
+        address: Address,
+        slot: H256,
+    ) -> Result<Option<Address>, ServiceError> {
+        let value = self
+            .provider
+            .get_storage_at(address, slot, self.block_id)
+            .await?;
+
+        Ok(Self::non_zero_address(value))
+    }
+
+    fn non_zero_address(value: H256) -> Option<Address> {
+        let address = Address::from(value);
+        (!address.is_zero()).then_some(address)
+    }
+
+    fn minimal_proxy_implementation(code: &Bytes) -> Option<Address> {
+        let code = code.as_ref();
+        let expected_length = MINIMAL_PROXY_PREFIX.len() + 20 + MINIMAL_PROXY_SUFFIX.len();
+
+        if code.len() != expected_length
+            || !code.starts_with(&MINIMAL_PROXY_PREFIX)
+            || !code.ends_with(&MINIMAL_PROXY_SUFFIX)
+        {
+            return None;
+        }
+
+        let start = MINIMAL_PROXY_PREFIX.len();
+        Some(Address::from_slice(&code[start..start + 20]))
+    }
+
+    fn proxy_info(
+        kind: ProxyKind,
+        implementation: Address,
+        beacon: Option<Address>,
+        admin: Option<Address>,
+    ) -> ProxyInfo {
+        ProxyInfo {
+            kind,
+            implementation: format!("{:#x}", implementation),
+            beacon: beacon.map(|beacon| format!("{:#x}", beacon)),
+            admin: admin.map(|admin| format!("{:#x}", admin)),
+        }
+    }
+}
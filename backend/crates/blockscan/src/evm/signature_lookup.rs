@@ -1,9 +1,12 @@
+use ethers::abi::HumanReadableParser;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SignatureLookupService {
     signatures: HashMap<String, FunctionSignature>,
+    // Keyed by topic0, several events can share one (e.g. ERC20 and ERC721 `Transfer`)
+    events: HashMap<String, Vec<EventSignature>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,6 +18,15 @@ pub struct FunctionSignature {
     pub description: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventSignature {
+    pub signature: String, // Human readable with `indexed` markers and parameter names
+    pub name: String,
+    pub category: SignatureCategory,
+    pub protocol: Option<String>,
+    pub description: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SignatureCategory {
     ERC20,
@@ -40,8 +52,10 @@ impl SignatureLookupService {
     pub fn new() -> Self {
         let mut service = Self {
             signatures: HashMap::new(),
+            events: HashMap::new(),
         };
         service.initialize_signatures();
+        service.initialize_event_signatures();
         service
     }
 
@@ -53,6 +67,206 @@ impl SignatureLookupService {
         self.signatures.insert(sig, function_sig);
     }
 
+    /// Candidate events for a topic0 (hex without the 0x prefix)
+    pub fn lookup_event(&self, topic0: &str) -> &[EventSignature] {
+        self.events
+            .get(topic0)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Registers an event under the topic0 derived from its signature
+    pub fn add_event_signature(&mut self, event_sig: EventSignature) {
+        let Ok(event) = HumanReadableParser::parse_event(&event_sig.signature) else {
+            return;
+        };
+        let topic0 = hex::encode(event.signature());
+        self.events.entry(topic0).or_default().push(event_sig);
+    }
+
+    fn add_event(
+        &mut self,
+        signature: &str,
+        category: SignatureCategory,
+        protocol: Option<&str>,
+        description: &str,
+    ) {
+        let name = signature
+            .trim_start_matches("event ")
+            .split('(')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+
+        self.add_event_signature(EventSignature {
+            signature: signature.to_string(),
+            name,
+            category,
+            protocol: protocol.map(str::to_string),
+            description: Some(description.to_string()),
+        });
+    }
+
+    fn initialize_event_signatures(&mut self) {
+        // === ERC20 / ERC721 / ERC1155 Events ===
+        self.add_event(
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+            SignatureCategory::ERC20,
+            None,
+            "ERC20 token transfer",
+        );
+        self.add_event(
+            "event Transfer(address indexed from, address indexed to, uint256 indexed tokenId)",
+            SignatureCategory::ERC721,
+            None,
+            "ERC721 token transfer",
+        );
+        self.add_event(
+            "event Approval(address indexed owner, address indexed spender, uint256 value)",
+            SignatureCategory::ERC20,
+            None,
+            "ERC20 allowance approval",
+        );
+        self.add_event(
+            "event Approval(address indexed owner, address indexed approved, uint256 indexed tokenId)",
+            SignatureCategory::ERC721,
+            None,
+            "ERC721 token approval",
+        );
+        self.add_event(
+            "event ApprovalForAll(address indexed owner, address indexed operator, bool approved)",
+            SignatureCategory::ERC721,
+            None,
+            "Operator approval for all tokens",
+        );
+        self.add_event(
+            "event TransferSingle(address indexed operator, address indexed from, address indexed to, uint256 id, uint256 value)",
+            SignatureCategory::ERC1155,
+            None,
+            "ERC1155 single token transfer",
+        );
+        self.add_event(
+            "event TransferBatch(address indexed operator, address indexed from, address indexed to, uint256[] ids, uint256[] values)",
+            SignatureCategory::ERC1155,
+            None,
+            "ERC1155 batch token transfer",
+        );
+        self.add_event(
+            "event URI(string value, uint256 indexed id)",
+            SignatureCategory::ERC1155,
+            None,
+            "ERC1155 token URI update",
+        );
+
+        // === Wrapped Native Token Events ===
+        self.add_event(
+            "event Deposit(address indexed dst, uint256 wad)",
+            SignatureCategory::Generic,
+            None,
+            "Wrap native token",
+        );
+        self.add_event(
+            "event Withdrawal(address indexed src, uint256 wad)",
+            SignatureCategory::Generic,
+            None,
+            "Unwrap native token",
+        );
+
+        // === DEX Events ===
+        self.add_event(
+            "event PairCreated(address indexed token0, address indexed token1, address pair, uint256 index)",
+            SignatureCategory::UniswapV2,
+            Some("Uniswap V2"),
+            "New liquidity pair",
+        );
+        self.add_event(
+            "event Swap(address indexed sender, uint256 amount0In, uint256 amount1In, uint256 amount0Out, uint256 amount1Out, address indexed to)",
+            SignatureCategory::UniswapV2,
+            Some("Uniswap V2"),
+            "Pair swap",
+        );
+        self.add_event(
+            "event Sync(uint112 reserve0, uint112 reserve1)",
+            SignatureCategory::UniswapV2,
+            Some("Uniswap V2"),
+            "Pair reserves update",
+        );
+        self.add_event(
+            "event Mint(address indexed sender, uint256 amount0, uint256 amount1)",
+            SignatureCategory::UniswapV2,
+            Some("Uniswap V2"),
+            "Liquidity added",
+        );
+        self.add_event(
+            "event Burn(address indexed sender, uint256 amount0, uint256 amount1, address indexed to)",
+            SignatureCategory::UniswapV2,
+            Some("Uniswap V2"),
+            "Liquidity removed",
+        );
+        self.add_event(
+            "event Swap(address indexed sender, address indexed recipient, int256 amount0, int256 amount1, uint160 sqrtPriceX96, uint128 liquidity, int24 tick)",
+            SignatureCategory::UniswapV3,
+            Some("Uniswap V3"),
+            "Pool swap",
+        );
+
+        // === Access Control / Proxy Events ===
+        self.add_event(
+            "event OwnershipTransferred(address indexed previousOwner, address indexed newOwner)",
+            SignatureCategory::Generic,
+            None,
+            "Contract ownership transferred",
+        );
+        self.add_event(
+            "event RoleGranted(bytes32 indexed role, address indexed account, address indexed sender)",
+            SignatureCategory::Generic,
+            None,
+            "Access control role granted",
+        );
+        self.add_event(
+            "event RoleRevoked(bytes32 indexed role, address indexed account, address indexed sender)",
+            SignatureCategory::Generic,
+            None,
+            "Access control role revoked",
+        );
+        self.add_event(
+            "event Paused(address account)",
+            SignatureCategory::Generic,
+            None,
+            "Contract paused",
+        );
+        self.add_event(
+            "event Unpaused(address account)",
+            SignatureCategory::Generic,
+            None,
+            "Contract unpaused",
+        );
+        self.add_event(
+            "event Upgraded(address indexed implementation)",
+            SignatureCategory::Generic,
+            None,
+            "Proxy implementation upgraded",
+        );
+        self.add_event(
+            "event AdminChanged(address previousAdmin, address newAdmin)",
+            SignatureCategory::Generic,
+            None,
+            "Proxy admin changed",
+        );
+        self.add_event(
+            "event BeaconUpgraded(address indexed beacon)",
+            SignatureCategory::Generic,
+            None,
+            "Proxy beacon upgraded",
+        );
+        self.add_event(
+            "event Initialized(uint8 version)",
+            SignatureCategory::Generic,
+            None,
+            "Upgradeable contract initialized",
+        );
+    }
+
     fn initialize_signatures(&mut self) {
         // The signatures you encountered
         self.add_signature(
@@ -0,0 +1,144 @@
+use blockscan_model::token_transfer::{TokenStandard, TokenTransferInfo};
+use ethers::{
+    abi::{ParamType, Token, decode},
+    types::{Address, H256, Log, U256},
+    utils::keccak256,
+};
+
+// Event signatures
+mod topics {
+    pub const TRANSFER: &str = "Transfer(address,address,uint256)";
+    pub const TRANSFER_SINGLE: &str = "TransferSingle(address,address,address,uint256,uint256)";
+    pub const TRANSFER_BATCH: &str = "TransferBatch(address,address,address,uint256[],uint256[])";
+}
+
+/// Decodes every token transfer carried by a receipt's logs
+pub fn decode_token_transfers(logs: &[Log]) -> Vec<TokenTransferInfo> {
+    let transfer = H256::from(keccak256(topics::TRANSFER));
+    let transfer_single = H256::from(keccak256(topics::TRANSFER_SINGLE));
+    let transfer_batch = H256::from(keccak256(topics::TRANSFER_BATCH));
+
+    let mut transfers = Vec::new();
+
+    for log in logs {
+        let Some(topic0) = log.topics.first() else {
+            continue;
+        };
+
+        if *topic0 == transfer {
+            transfers.extend(decode_transfer(log));
+        } else if *topic0 == transfer_single {
+            transfers.extend(decode_transfer_single(log));
+        } else if *topic0 == transfer_batch {
+            transfers.extend(decode_transfer_batch(log));
+        }
+    }
+
+    transfers
+}
+
+/// ERC20 `Transfer` has the amount in data (3 topics), ERC721 indexes the token id (4 topics)
+fn decode_transfer(log: &Log) -> Option<TokenTransferInfo> {
+    let from = topic_address(log.topics.get(1)?);
+    let to = topic_address(log.topics.get(2)?);
+
+    let (standard, value, token_id) = match log.topics.len() {
+        3 if log.data.len() >= 32 => (
+            TokenStandard::ERC20,
+            Some(U256::from_big_endian(&log.data[..32]).to_string()),
+            None,
+        ),
+        4 => (
+            TokenStandard::ERC721,
+            None,
+            Some(U256::from_big_endian(log.topics[3].as_bytes()).to_string()),
+        ),
+        _ => return None,
+    };
+
+    Some(TokenTransferInfo {
+        token_address: format!("{:#x}", log.address),
+        standard,
+        operator: None,
+        from,
+        to,
+        value,
+        token_id,
+        log_index: log_index(log),
+        batch_index: 0,
+    })
+}
+
+fn decode_transfer_single(log: &Log) -> Option<TokenTransferInfo> {
+    if log.topics.len() != 4 || log.data.len() < 64 {
+        return None;
+    }
+
+    Some(TokenTransferInfo {
+        token_address: format!("{:#x}", log.address),
+        standard: TokenStandard::ERC1155,
+        operator: Some(topic_address(&log.topics[1])),
+        from: topic_address(&log.topics[2]),
+        to: topic_address(&log.topics[3]),
+        value: Some(U256::from_big_endian(&log.data[32..64]).to_string()),
+        token_id: Some(U256::from_big_endian(&log.data[..32]).to_string()),
+        log_index: log_index(log),
+        batch_index: 0,
+    })
+}
+
+fn decode_transfer_batch(log: &Log) -> Vec<TokenTransferInfo> {
+    if log.topics.len() != 4 {
+        return Vec::new();
+    }
+
+    let param_types = [
+        ParamType::Array(Box::new(ParamType::Uint(256))),
+        ParamType::Array(Box::new(ParamType::Uint(256))),
+    ];
+    let Ok(tokens) = decode(&param_types, &log.data) else {
+        return Vec::new();
+    };
+
+    let (ids, values) = match tokens.as_slice() {
+        [Token::Array(ids), Token::Array(values)] => (ids, values),
+        _ => return Vec::new(),
+    };
+
+    let operator = topic_address(&log.topics[1]);
+    let from = topic_address(&log.topics[2]);
+    let to = topic_address(&log.topics[3]);
+
+    ids.iter()
+        .zip(values.iter())
+        .enumerate()
+        .filter_map(|(index, (id, value))| {
+            Some(TokenTransferInfo {
+                token_address: format!("{:#x}", log.address),
+                standard: TokenStandard::ERC1155,
+                operator: Some(operator.clone()),
+                from: from.clone(),
+                to: to.clone(),
+                value: Some(id_or_value(value)?),
+                token_id: Some(id_or_value(id)?),
+                log_index: log_index(log),
+                batch_index: index as u32,
+            })
+        })
+        .collect()
+}
+
+fn topic_address(topic: &H256) -> String {
+    format!("{:#x}", Address::from(*topic))
+}
+
+fn log_index(log: &Log) -> u64 {
+    log.log_index.map(|index| index.as_u64()).unwrap_or(0)
+}
+
+fn id_or_value(token: &Token) -> Option<String> {
+    match token {
+        Token::Uint(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
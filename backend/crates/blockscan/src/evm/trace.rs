@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use blockscan_model::trace::InternalTransactionInfo;
+use custom_error::ServiceError;
+use ethers::{
+    providers::{Http, Middleware, Provider},
+    types::{
+        CallFrame, GethDebugBuiltInTracerType, GethDebugTracerType, GethDebugTracingOptions,
+        GethTrace, GethTraceFrame, H256, NameOrAddress,
+    },
+};
+
+pub struct TraceQuery {
+    provider: Arc<Provider<Http>>,
+}
+
+impl TraceQuery {
+    pub fn new(provider: Arc<Provider<Http>>) -> Self {
+        Self { provider }
+    }
+
+    /// Runs `debug_traceTransaction` with the built-in `callTracer` and flattens the
+    /// calls made below the top-level one
+    pub async fn internal_transactions(
+        &self,
+        tx_hash: &str,
+    ) -> Result<Vec<InternalTransactionInfo>, ServiceError> {
+        let hash: H256 = tx_hash
+            .parse()
+            .map_err(|_| ServiceError::InvalidTransactionHash(tx_hash.to_string()))?;
+
+        let options = GethDebugTracingOptions {
+            tracer: Some(GethDebugTracerType::BuiltInTracer(
+                GethDebugBuiltInTracerType::CallTracer,
+            )),
+            ..Default::default()
+        };
+
+        let trace = self.provider.debug_trace_transaction(hash, options).await?;
+
+        let root = match trace {
+            GethTrace::Known(GethTraceFrame::CallTracer(frame)) => frame,
+            _ => {
+                return Err(ServiceError::InvalidTransactionData(format!(
+                    "Unexpected trace format for {}",
+                    tx_hash
+                )));
+            }
+        };
+
+        let mut internal_transactions = Vec::new();
+        for (index, call) in root.calls.iter().flatten().enumerate() {
+            Self::flatten(call, vec![index as u32], &mut internal_transactions);
+        }
+
+        Ok(internal_transactions)
+    }
+
+    fn flatten(
+        frame: &CallFrame,
+        trace_address: Vec<u32>,
+        internal_transactions: &mut Vec<InternalTransactionInfo>,
+    ) {
+        internal_transactions.push(InternalTransactionInfo {
+            trace_address: trace_address.clone(),
+            call_type: frame.typ.to_uppercase(),
+            from: format!("{:#x}", frame.from),
+            to: frame.to.as_ref().and_then(|to| match to {
+                NameOrAddress::Address(address) => Some(format!("{:#x}", address)),
+                NameOrAddress::Name(_) => None,
+            }),
+            value: frame.value.unwrap_or_default().to_string(),
+            gas: frame.gas.low_u64(),
+            gas_used: frame.gas_used.low_u64(),
+            input: format!("0x{}", hex::encode(&frame.input)),
+            output: frame
+                .output
+                .as_ref()
+                .map(|output| format!("0x{}", hex::encode(output))),
+            error: frame.error.clone(),
+        });
+
+        for (index, call) in frame.calls.iter().flatten().enumerate() {
+            let mut child_address = trace_address.clone();
+            child_address.push(index as u32);
+            Self::flatten(call, child_address, internal_transactions);
+        }
+    }
+}
@@ -1,6 +1,9 @@
-use blockscan_model::transaction::TransactionFee;
+use blockscan_model::{
+    amount::{NATIVE_DECIMALS, format_units},
+    transaction::TransactionFee,
+};
 use custom_error::ServiceError;
-use ethers::types::{Transaction, TransactionReceipt};
+use ethers::types::{Transaction, TransactionReceipt, U256};
 
 pub fn calculate_transaction_fee(
     tx_info: &Transaction,
@@ -18,10 +21,10 @@ pub fn calculate_transaction_fee(
         .and_then(|price| Some(price.as_u64()))
         .or_else(|| tx_info.gas_price.map(|price| price.as_u64()));
 
-    let total_fee = if let Some(gas_price) = effective_gas_price {
-        gas_used * gas_price
-    } else {
-        0
+    // Multiplied in U256, the fee exceeds u64::MAX wei above about 18.4 native tokens
+    let total_fee = match effective_gas_price {
+        Some(gas_price) => U256::from(gas_used) * U256::from(gas_price),
+        None => U256::zero(),
     };
 
     Ok(TransactionFee {
@@ -30,7 +33,7 @@ pub fn calculate_transaction_fee(
         gas_price: effective_gas_price,
         max_fee_per_gas: tx_info.max_fee_per_gas.map(|fee| fee.as_u64()),
         max_priority_fee_per_gas: tx_info.max_priority_fee_per_gas.map(|fee| fee.as_u64()),
-        total_fee,
-        total_fee_eth: format!("{:.6}", total_fee as f64 / 1e18), // Convert wei to ETH
+        total_fee: total_fee.to_string(),
+        total_fee_eth: format_units(total_fee, NATIVE_DECIMALS),
     })
 }
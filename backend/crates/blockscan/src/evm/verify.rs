@@ -0,0 +1,512 @@
+use std::{path::Path, process::Stdio, sync::Arc, time::Duration};
+
+use blockscan_model::verification::{SourceFile, VerificationRequest, VerifiedContract};
+use config::SOLC_PATH;
+use custom_error::ServiceError;
+use ethers::{
+    providers::{Http, Middleware, Provider},
+    types::Address,
+};
+use serde_json::{Value as JsonValue, json};
+use tokio::{io::AsyncWriteExt, process::Command, sync::Semaphore, time::timeout};
+
+// Only what is needed to compare runtime code and to decode calls and logs afterwards
+const OUTPUT_SELECTION: [&str; 4] = [
+    "abi",
+    "evm.deployedBytecode.object",
+    "evm.deployedBytecode.immutableReferences",
+    "metadata",
+];
+
+/// Largest standard-JSON input accepted, sources included
+const MAX_INPUT_BYTES: usize = 5 * 1024 * 1024;
+/// A compilation running longer than this is killed
+const COMPILE_TIMEOUT: Duration = Duration::from_secs(120);
+/// solc is CPU and memory hungry, only this many run at once
+static COMPILATIONS: Semaphore = Semaphore::const_new(2);
+
+/// Compiles submitted sources with the local `solc` and checks the runtime bytecode
+/// against the code deployed at the address
+pub struct ContractVerifier {
+    provider: Arc<Provider<Http>>,
+    solc_path: String,
+}
+
+impl ContractVerifier {
+    pub fn new(provider: Arc<Provider<Http>>) -> Self {
+        Self {
+            provider,
+            solc_path: SOLC_PATH.clone(),
+        }
+    }
+
+    pub async fn verify(
+        &self,
+        request: &VerificationRequest,
+    ) -> Result<VerifiedContract, ServiceError> {
+        let address: Address = request.address.parse().map_err(|_| {
+            ServiceError::VerificationError(format!("Invalid address: {}", request.address))
+        })?;
+
+        let deployed = self.provider.get_code(address, None).await?;
+        if deployed.is_empty() {
+            return Err(ServiceError::VerificationError(format!(
+                "No contract code at {}",
+                request.address
+            )));
+        }
+
+        let input = Self::standard_json_input(request)?;
+        let output = self.compile(&input).await?;
+
+        let (source_path, contract_name, contract) =
+            Self::find_contract(&output, &request.contract_name)?;
+
+        let compiled_hex = contract["evm"]["deployedBytecode"]["object"]
+            .as_str()
+            .unwrap_or_default();
+        if compiled_hex.is_empty() {
+            return Err(ServiceError::VerificationError(format!(
+                "{} has no runtime bytecode, abstract contracts and interfaces cannot be verified",
+                contract_name
+            )));
+        }
+        if compiled_hex.contains("__$") {
+            return Err(ServiceError::VerificationError(
+                "Contracts with unlinked libraries are not supported".to_string(),
+            ));
+        }
+        let compiled = hex::decode(compiled_hex).map_err(|e| {
+            ServiceError::VerificationError(format!("Invalid compiler bytecode: {}", e))
+        })?;
+
+        let mut onchain = deployed.to_vec();
+        Self::zero_immutables(
+            &mut onchain,
+            &contract["evm"]["deployedBytecode"]["immutableReferences"],
+        );
+
+        if Self::strip_metadata(&onchain) != Self::strip_metadata(&compiled) {
+            return Err(ServiceError::VerificationError(format!(
+                "Compiled runtime bytecode of {} does not match the code at {}",
+                contract_name, request.address
+            )));
+        }
+
+        let metadata = contract["metadata"].as_str().map(str::to_string);
+        let compiler_version = metadata
+            .as_deref()
+            .and_then(|metadata| serde_json::from_str::<JsonValue>(metadata).ok())
+            .and_then(|metadata| metadata["compiler"]["version"].as_str().map(str::to_string))
+            .unwrap_or_else(|| "unknown".to_string());
+
+        let mut settings = input["settings"].clone();
+        if let Some(settings) = settings.as_object_mut() {
+            settings.remove("outputSelection");
+        }
+
+        Ok(VerifiedContract {
+            address: format!("{:#x}", address),
+            contract_name,
+            source_path,
+            compiler_version,
+            abi: contract["abi"].clone(),
+            sources: Self::input_sources(&input),
+            settings,
+            metadata,
+        })
+    }
+
+    /// Uses the submitted standard-JSON as is, or builds one from the plain sources, and
+    /// always overrides the output selection
+    fn standard_json_input(request: &VerificationRequest) -> Result<JsonValue, ServiceError> {
+        let mut input = match &request.standard_json {
+            Some(standard_json) => standard_json.clone(),
+            None => {
+                if request.sources.is_empty() {
+                    return Err(ServiceError::VerificationError(
+                        "Either sources or standard_json must be provided".to_string(),
+                    ));
+                }
+
+                let sources: serde_json::Map<String, JsonValue> = request
+                    .sources
+                    .iter()
+                    .map(|source| (source.path.clone(), json!({ "content": source.content })))
+                    .collect();
+
+                let mut settings = json!({
+                    "optimizer": {
+                        "enabled": request.optimizer_enabled,
+                        "runs": request.optimizer_runs,
+                    },
+                });
+                if let Some(evm_version) = &request.evm_version {
+                    settings["evmVersion"] = json!(evm_version);
+                }
+
+                json!({
+                    "language": "Solidity",
+                    "sources": sources,
+                    "settings": settings,
+                })
+            }
+        };
+
+        if !input.is_object() || !input["sources"].is_object() {
+            return Err(ServiceError::VerificationError(
+                "standard_json must be an object with sources".to_string(),
+            ));
+        }
+        Self::check_sources(&input)?;
+
+        if !input["settings"].is_object() {
+            input["settings"] = json!({});
+        }
+        input["settings"]["outputSelection"] = json!({ "*": { "*": OUTPUT_SELECTION } });
+
+        Ok(input)
+    }
+
+    /// Sources must be inline and may only refer to each other, solc must not read files
+    /// from the host
+    fn check_sources(input: &JsonValue) -> Result<(), ServiceError> {
+        if input.to_string().len() > MAX_INPUT_BYTES {
+            return Err(ServiceError::VerificationError(format!(
+                "Sources exceed {} bytes",
+                MAX_INPUT_BYTES
+            )));
+        }
+
+        for (path, source) in input["sources"].as_object().into_iter().flatten() {
+            if !is_contained_path(path) {
+                return Err(ServiceError::VerificationError(format!(
+                    "Source path {} must be relative without ..",
+                    path
+                )));
+            }
+
+            let Some(content) = source["content"].as_str() else {
+                return Err(ServiceError::VerificationError(format!(
+                    "Source {} must have its content inline",
+                    path
+                )));
+            };
+
+            if let Some(import) = import_paths(content)
+                .into_iter()
+                .find(|import| !is_contained_path(import))
+            {
+                return Err(ServiceError::VerificationError(format!(
+                    "Import {} in {} must be relative without ..",
+                    import, path
+                )));
+            }
+        }
+
+        let remappings = input["settings"]["remappings"]
+            .as_array()
+            .into_iter()
+            .flatten();
+        for remapping in remappings {
+            let target = remapping
+                .as_str()
+                .and_then(|remapping| remapping.split_once('='))
+                .map(|(_, target)| target);
+            if !target.is_some_and(is_contained_path) {
+                return Err(ServiceError::VerificationError(format!(
+                    "Remapping {} must target a relative path without ..",
+                    remapping
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Runs solc in an empty scratch directory that is also the only path it may read
+    async fn compile(&self, input: &JsonValue) -> Result<JsonValue, ServiceError> {
+        let _permit = COMPILATIONS
+            .acquire()
+            .await
+            .map_err(|e| ServiceError::VerificationError(format!("Compiler unavailable: {}", e)))?;
+
+        let work_dir = std::env::temp_dir().join(format!("solc-{}", uuid::Uuid::new_v4()));
+        tokio::fs::create_dir(&work_dir).await.map_err(|e| {
+            ServiceError::VerificationError(format!("Failed to create work directory: {}", e))
+        })?;
+
+        let output = self.run_solc(input, &work_dir).await;
+        let _ = tokio::fs::remove_dir_all(&work_dir).await;
+
+        output
+    }
+
+    async fn run_solc(
+        &self,
+        input: &JsonValue,
+        work_dir: &Path,
+    ) -> Result<JsonValue, ServiceError> {
+        let mut child = Command::new(&self.solc_path)
+            .arg("--standard-json")
+            .arg("--base-path")
+            .arg(work_dir)
+            .arg("--allow-paths")
+            .arg(work_dir)
+            .current_dir(work_dir)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| {
+                ServiceError::VerificationError(format!(
+                    "Failed to start {}: {}",
+                    self.solc_path, e
+                ))
+            })?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(input.to_string().as_bytes())
+                .await
+                .map_err(|e| {
+                    ServiceError::VerificationError(format!("Failed to write solc input: {}", e))
+                })?;
+        }
+
+        // Dropping the child on timeout kills it
+        let output = timeout(COMPILE_TIMEOUT, child.wait_with_output())
+            .await
+            .map_err(|_| {
+                ServiceError::VerificationError(format!(
+                    "Compilation took longer than {} seconds",
+                    COMPILE_TIMEOUT.as_secs()
+                ))
+            })?
+            .map_err(|e| ServiceError::VerificationError(format!("Failed to run solc: {}", e)))?;
+
+        let output: JsonValue = serde_json::from_slice(&output.stdout).map_err(|e| {
+            ServiceError::VerificationError(format!(
+                "Invalid solc output: {} {}",
+                e,
+                String::from_utf8_lossy(&output.stderr)
+            ))
+        })?;
+
+        let errors: Vec<String> = output["errors"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|error| error["severity"] == "error")
+            .map(|error| {
+                error["formattedMessage"]
+                    .as_str()
+                    .or(error["message"].as_str())
+                    .unwrap_or_default()
+                    .to_string()
+            })
+            .collect();
+        if !errors.is_empty() {
+            return Err(ServiceError::VerificationError(format!(
+                "Compilation failed: {}",
+                errors.join("\n")
+            )));
+        }
+
+        Ok(output)
+    }
+
+    /// Finds `Name` or `path:Name` in the compiler output
+    fn find_contract(
+        output: &JsonValue,
+        requested: &str,
+    ) -> Result<(String, String, JsonValue), ServiceError> {
+        let (path, name) = match requested.rsplit_once(':') {
+            Some((path, name)) => (Some(path), name),
+            None => (None, requested),
+        };
+
+        let mut matches: Vec<(String, String, JsonValue)> = output["contracts"]
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter(|(source_path, _)| path.is_none_or(|path| path == source_path.as_str()))
+            .filter_map(|(source_path, contracts)| {
+                contracts
+                    .get(name)
+                    .map(|contract| (source_path.clone(), name.to_string(), contract.clone()))
+            })
+            .collect();
+
+        match matches.len() {
+            0 => Err(ServiceError::VerificationError(format!(
+                "Contract {} not found in compiler output",
+                requested
+            ))),
+            1 => Ok(matches.remove(0)),
+            _ => Err(ServiceError::VerificationError(format!(
+                "Contract name {} is ambiguous, use path:{}",
+                name, name
+            ))),
+        }
+    }
+
+    /// Immutables are filled in at deploy time, solc leaves zeros at their offsets
+    fn zero_immutables(code: &mut [u8], references: &JsonValue) {
+        let ranges = references
+            .as_object()
+            .into_iter()
+            .flat_map(|references| references.values())
+            .filter_map(JsonValue::as_array)
+            .flatten();
+
+        for range in ranges {
+            let (Some(start), Some(length)) = (range["start"].as_u64(), range["length"].as_u64())
+            else {
+                continue;
+            };
+            let (start, end) = (start as usize, (start + length) as usize);
+            if end <= code.len() {
+                code[start..end].fill(0);
+            }
+        }
+    }
+
+    /// Drops the trailing CBOR metadata, its length is encoded in the last two bytes
+    fn strip_metadata(code: &[u8]) -> &[u8] {
+        if code.len() < 2 {
+            return code;
+        }
+
+        let metadata_length =
+            u16::from_be_bytes([code[code.len() - 2], code[code.len() - 1]]) as usize;
+        if metadata_length + 2 > code.len() {
+            return code;
+        }
+
+        let metadata_start = code.len() - 2 - metadata_length;
+        // CBOR map header, solc emits between one and three entries
+        match code[metadata_start] {
+            0xa1..=0xa3 => &code[..metadata_start],
+            _ => code,
+        }
+    }
+
+    fn input_sources(input: &JsonValue) -> Vec<SourceFile> {
+        input["sources"]
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter_map(|(path, source)| {
+                source["content"].as_str().map(|content| SourceFile {
+                    path: path.clone(),
+                    content: content.to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// A relative path that stays inside the directory it is resolved against
+fn is_contained_path(path: &str) -> bool {
+    !path.is_empty()
+        && !path.starts_with('/')
+        && !path.starts_with('\\')
+        && !path.contains(':')
+        && !path.split(['/', '\\']).any(|segment| segment == "..")
+}
+
+/// Paths of the `import` directives of a Solidity source
+fn import_paths(content: &str) -> Vec<&str> {
+    let mut paths = Vec::new();
+    let mut rest = content;
+
+    while let Some(start) = rest.find("import") {
+        let preceded_by_ident = rest[..start]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_');
+        rest = &rest[start + "import".len()..];
+        if preceded_by_ident {
+            continue;
+        }
+
+        let statement = rest.split(';').next().unwrap_or_default();
+        if let Some(path) = quoted(statement) {
+            paths.push(path);
+        }
+    }
+
+    paths
+}
+
+/// Last quoted string of an import statement, the path comes after any `from`
+fn quoted(statement: &str) -> Option<&str> {
+    let end = statement.rfind(['"', '\''])?;
+    let quote = statement[end..].chars().next()?;
+    let start = statement[..end].rfind(quote)?;
+    Some(&statement[start + 1..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contained_paths() {
+        assert!(is_contained_path("contracts/Token.sol"));
+        assert!(is_contained_path("./Token.sol"));
+        assert!(is_contained_path(
+            "@openzeppelin/contracts/token/ERC20/ERC20.sol"
+        ));
+        assert!(!is_contained_path("/etc/passwd"));
+        assert!(!is_contained_path("../secret.sol"));
+        assert!(!is_contained_path("contracts/../../secret.sol"));
+        assert!(!is_contained_path("C:\\secret.sol"));
+        assert!(!is_contained_path(""));
+    }
+
+    #[test]
+    fn finds_import_paths() {
+        let source = r#"
+            pragma solidity ^0.8.0;
+            import "./A.sol";
+            import {B} from '../B.sol';
+            import * as C from "lib/C.sol";
+            contract reimport {}
+        "#;
+
+        assert_eq!(
+            import_paths(source),
+            vec!["./A.sol", "../B.sol", "lib/C.sol"]
+        );
+    }
+
+    #[test]
+    fn rejects_escaping_sources() {
+        let input = json!({
+            "sources": { "A.sol": { "content": "import \"/etc/passwd\";" } },
+            "settings": {},
+        });
+        assert!(ContractVerifier::check_sources(&input).is_err());
+
+        let input = json!({
+            "sources": { "A.sol": { "urls": ["/etc/passwd"] } },
+            "settings": {},
+        });
+        assert!(ContractVerifier::check_sources(&input).is_err());
+
+        let input = json!({
+            "sources": { "A.sol": { "content": "import \"./B.sol\";" }, "B.sol": { "content": "" } },
+            "settings": { "remappings": ["x/=/home/"] },
+        });
+        assert!(ContractVerifier::check_sources(&input).is_err());
+
+        let input = json!({
+            "sources": { "A.sol": { "content": "import \"./B.sol\";" }, "B.sol": { "content": "" } },
+            "settings": { "remappings": ["x/=lib/x/"] },
+        });
+        assert!(ContractVerifier::check_sources(&input).is_ok());
+    }
+}
@@ -3,5 +3,9 @@ mod substrate;
 pub use ethers;
 
 pub use evm::BlockStateQuery;
+pub use evm::abi::{decode_calldata, decode_log_with_abi};
+pub use evm::proxy::{BEACON_UPGRADED_TOPIC, UPGRADED_TOPIC};
 pub use substrate::SubstrtaeBlockQuery;
+pub use substrate::address::{evm_to_ss58, normalize_ss58, ss58_to_evm_address};
 pub use substrate::substrate_subxt::SubstrtaeGeneralQuery;
+pub use substrate::{json_accounts, json_to_u128};
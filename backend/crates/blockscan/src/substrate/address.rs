@@ -0,0 +1,96 @@
+use config::SS58_PREFIX;
+use custom_error::ServiceError;
+use sp_core::{
+    H160,
+    crypto::{AccountId32, Ss58AddressFormat, Ss58Codec},
+};
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+/// EVM address made of the first 20 bytes of an SS58 account
+pub fn ss58_to_evm_address(ss58_address: &str) -> Result<String, ServiceError> {
+    // Parse SS58 string to AccountId32
+    let account_id = AccountId32::from_ss58check(ss58_address)
+        .map_err(|e| ServiceError::SubstrateError(format!("Failed to parse SS58: {:?}", e)))?;
+
+    // Convert AccountId32 to EVM address (first 20 bytes)
+    let account_bytes: &[u8; 32] = account_id.as_ref();
+    let mut evm_bytes = [0u8; 20];
+    evm_bytes.copy_from_slice(&account_bytes[0..20]);
+    let address = H160(evm_bytes);
+
+    Ok(format!("0x{:x}", address))
+}
+
+/// SS58 account holding the native balance of an EVM address, `blake2("evm:" ++ address)`
+pub fn evm_to_ss58(evm_address: &str) -> Result<String, ServiceError> {
+    let evm_address = parse_evm_address(evm_address)?;
+
+    let mut data = [0u8; 24];
+    data[0..4].copy_from_slice(b"evm:"); // 4-byte prefix
+    data[4..24].copy_from_slice(&evm_address[..]); // 20-byte EVM address
+    let hash = BlakeTwo256::hash(&data);
+
+    let substrate_account = AccountId32::from(hash.0);
+
+    let ss58_address =
+        substrate_account.to_ss58check_with_version(Ss58AddressFormat::custom(SS58_PREFIX));
+    Ok(ss58_address)
+}
+
+/// Re-encodes an SS58 address of any network prefix with the chain's prefix, the form
+/// accounts are stored in
+pub fn normalize_ss58(address: &str) -> Result<String, ServiceError> {
+    let (account, _) = AccountId32::from_ss58check_with_version(address)
+        .map_err(|e| ServiceError::SubstrateError(format!("Failed to parse SS58: {:?}", e)))?;
+
+    Ok(account.to_ss58check_with_version(Ss58AddressFormat::custom(SS58_PREFIX)))
+}
+
+/// Helper function to parse EVM address string
+fn parse_evm_address(address_str: &str) -> Result<H160, ServiceError> {
+    // Remove "0x" prefix if present
+    let clean_address = if address_str.starts_with("0x") || address_str.starts_with("0X") {
+        &address_str[2..]
+    } else {
+        address_str
+    };
+
+    // Check length (40 hex characters = 20 bytes)
+    if clean_address.len() != 40 {
+        return Err(ServiceError::SubstrateError(format!(
+            "EVM address must be 40 hex characters, got {}",
+            clean_address.len()
+        )));
+    }
+
+    // Parse hex string to bytes
+    let bytes = hex::decode(clean_address)
+        .map_err(|e| ServiceError::SubstrateError(format!("Invalid hex: {:?}", e)))?;
+
+    if bytes.len() != 20 {
+        return Err(ServiceError::SubstrateError(format!(
+            "EVM address must be 20 bytes, got {}",
+            bytes.len()
+        )));
+    }
+
+    let mut address_bytes = [0u8; 20];
+    address_bytes.copy_from_slice(&bytes);
+    Ok(H160(address_bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Alice on the generic substrate prefix (42) and on Polkadot (0)
+    const ALICE_GENERIC: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+    const ALICE_POLKADOT: &str = "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5";
+
+    #[test]
+    fn normalizes_any_prefix() {
+        assert_eq!(normalize_ss58(ALICE_GENERIC).unwrap(), ALICE_GENERIC);
+        assert_eq!(normalize_ss58(ALICE_POLKADOT).unwrap(), ALICE_GENERIC);
+        assert!(normalize_ss58("not an address").is_err());
+    }
+}
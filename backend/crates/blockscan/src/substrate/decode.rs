@@ -0,0 +1,295 @@
+use std::sync::OnceLock;
+
+use codec::Decode;
+use config::SS58_PREFIX;
+use custom_error::ServiceError;
+use ethers::types::U256;
+use scale_info::{PortableRegistry, TypeDef, TypeDefPrimitive};
+use serde_json::{Map, Value as JsonValue, json};
+use sp_core::crypto::{AccountId32, Ss58AddressFormat, Ss58Codec};
+use subxt::{
+    Metadata,
+    ext::scale_value::{Composite, Primitive, Value, ValueDef},
+};
+
+static METADATA: OnceLock<Metadata> = OnceLock::new();
+
+/// Runtime metadata bundled with the config crate, decoded once per process
+pub fn runtime_metadata() -> Result<&'static Metadata, ServiceError> {
+    if let Some(metadata) = METADATA.get() {
+        return Ok(metadata);
+    }
+
+    let metadata = Metadata::decode(&mut &config::SELENDRA_METADATA[..]).map_err(|e| {
+        ServiceError::SubstrateError(format!("Failed to decode runtime metadata: {:?}", e))
+    })?;
+
+    Ok(METADATA.get_or_init(|| metadata))
+}
+
+/// Formats a 32 byte account id as an SS58 address
+pub fn account_to_ss58(bytes: &[u8]) -> Option<String> {
+    let bytes: [u8; 32] = bytes.try_into().ok()?;
+    Some(AccountId32::from(bytes).to_ss58check_with_version(Ss58AddressFormat::custom(SS58_PREFIX)))
+}
+
+/// SS58 accounts found anywhere in decoded fields, in order of first appearance
+pub fn json_accounts(value: &JsonValue) -> Vec<String> {
+    let mut accounts = Vec::new();
+    collect_accounts(value, &mut accounts);
+    accounts
+}
+
+fn collect_accounts(value: &JsonValue, accounts: &mut Vec<String>) {
+    match value {
+        JsonValue::String(text) => {
+            if !accounts.contains(text) && AccountId32::from_ss58check(text).is_ok() {
+                accounts.push(text.clone());
+            }
+        }
+        JsonValue::Array(values) => {
+            for value in values {
+                collect_accounts(value, accounts);
+            }
+        }
+        JsonValue::Object(map) => {
+            for value in map.values() {
+                collect_accounts(value, accounts);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Converts decoded call or event fields into JSON
+pub fn composite_to_json(composite: &Composite<u32>, types: &PortableRegistry) -> JsonValue {
+    match composite {
+        Composite::Named(fields) => {
+            let mut map = Map::with_capacity(fields.len());
+            for (name, value) in fields {
+                map.insert(name.clone(), value_to_json(value, types));
+            }
+            JsonValue::Object(map)
+        }
+        Composite::Unnamed(values) => {
+            JsonValue::Array(values.iter().map(|v| value_to_json(v, types)).collect())
+        }
+    }
+}
+
+/// Converts a decoded SCALE value into JSON using the type registry for context.
+///
+/// Byte sequences become hex strings, account ids become SS58 addresses, nested
+/// `RuntimeCall`s become `{pallet, call, args}` objects and enum variants become
+/// `{"Variant": fields}` (or just the variant name when it carries no data).
+pub fn value_to_json(value: &Value<u32>, types: &PortableRegistry) -> JsonValue {
+    let type_name = type_name(value.context, types);
+
+    if type_name == Some("RuntimeCall") {
+        if let Some(call) = runtime_call_to_json(value, types) {
+            return call;
+        }
+    }
+
+    if let Some(bytes) = value_bytes(value, types) {
+        if type_name == Some("AccountId32") {
+            if let Some(address) = account_to_ss58(&bytes) {
+                return JsonValue::String(address);
+            }
+        }
+        return JsonValue::String(format!("0x{}", hex::encode(bytes)));
+    }
+
+    match &value.value {
+        ValueDef::Composite(Composite::Unnamed(values)) if values.len() == 1 => {
+            // Newtype wrappers such as H256 or Perbill collapse to their inner value
+            value_to_json(&values[0], types)
+        }
+        ValueDef::Composite(composite) => composite_to_json(composite, types),
+        ValueDef::Variant(variant) => {
+            if composite_is_empty(&variant.values) {
+                JsonValue::String(variant.name.clone())
+            } else {
+                // Single unnamed payloads such as `MultiAddress::Id(..)` map straight to the value
+                let fields = match &variant.values {
+                    Composite::Unnamed(values) if values.len() == 1 => {
+                        value_to_json(&values[0], types)
+                    }
+                    values => composite_to_json(values, types),
+                };
+                let mut map = Map::with_capacity(1);
+                map.insert(variant.name.clone(), fields);
+                JsonValue::Object(map)
+            }
+        }
+        ValueDef::BitSequence(bits) => {
+            JsonValue::String(bits.iter().map(|b| if b { '1' } else { '0' }).collect())
+        }
+        ValueDef::Primitive(primitive) => {
+            primitive_to_json(primitive, is_wide_integer(value.context, types))
+        }
+    }
+}
+
+/// Converts a `RuntimeCall` value into `{pallet, call, args}`
+pub fn runtime_call_to_json(value: &Value<u32>, types: &PortableRegistry) -> Option<JsonValue> {
+    let ValueDef::Variant(pallet) = &value.value else {
+        return None;
+    };
+
+    let call = match &pallet.values {
+        Composite::Unnamed(values) => values.first()?,
+        Composite::Named(fields) => &fields.first()?.1,
+    };
+
+    let ValueDef::Variant(call) = &call.value else {
+        return None;
+    };
+
+    Some(json!({
+        "pallet": pallet.name,
+        "call": call.name,
+        "args": composite_to_json(&call.values, types),
+    }))
+}
+
+/// Converts a primitive into JSON. `wide` marks values typed as 128-bit integers, which
+/// are always kept exact as decimal strings so one field never mixes numbers and strings.
+fn primitive_to_json(primitive: &Primitive, wide: bool) -> JsonValue {
+    match primitive {
+        Primitive::Bool(b) => JsonValue::Bool(*b),
+        Primitive::Char(c) => JsonValue::String(c.to_string()),
+        Primitive::String(s) => JsonValue::String(s.clone()),
+        // scale-value widens every integer to 128 bits, narrower types stay JSON numbers
+        Primitive::U128(n) => match u64::try_from(*n) {
+            Ok(n) if !wide => json!(n),
+            _ => JsonValue::String(n.to_string()),
+        },
+        Primitive::I128(n) => match i64::try_from(*n) {
+            Ok(n) if !wide => json!(n),
+            _ => JsonValue::String(n.to_string()),
+        },
+        Primitive::U256(bytes) => JsonValue::String(U256::from_little_endian(bytes).to_string()),
+        Primitive::I256(bytes) => JsonValue::String(format!("0x{}", hex::encode(bytes))),
+    }
+}
+
+/// Whether the type is a `u128`/`i128`, or a compact one, such as a balance
+fn is_wide_integer(type_id: u32, types: &PortableRegistry) -> bool {
+    match types.resolve(type_id).map(|ty| &ty.type_def) {
+        Some(TypeDef::Primitive(TypeDefPrimitive::U128 | TypeDefPrimitive::I128)) => true,
+        Some(TypeDef::Compact(compact)) => is_wide_integer(compact.type_param.id, types),
+        _ => false,
+    }
+}
+
+fn composite_is_empty(composite: &Composite<u32>) -> bool {
+    match composite {
+        Composite::Named(fields) => fields.is_empty(),
+        Composite::Unnamed(values) => values.is_empty(),
+    }
+}
+
+/// Last segment of the type path, e.g. `AccountId32` for `sp_core::crypto::AccountId32`
+fn type_name(type_id: u32, types: &PortableRegistry) -> Option<&str> {
+    types
+        .resolve(type_id)
+        .and_then(|ty| ty.path.segments.last())
+        .map(|name| name.as_str())
+}
+
+fn is_u8(type_id: u32, types: &PortableRegistry) -> bool {
+    matches!(
+        types.resolve(type_id).map(|ty| &ty.type_def),
+        Some(TypeDef::Primitive(TypeDefPrimitive::U8))
+    )
+}
+
+fn is_byte_container(type_id: u32, types: &PortableRegistry) -> bool {
+    match types.resolve(type_id).map(|ty| &ty.type_def) {
+        Some(TypeDef::Sequence(seq)) => is_u8(seq.type_param.id, types),
+        Some(TypeDef::Array(array)) => is_u8(array.type_param.id, types),
+        _ => false,
+    }
+}
+
+/// Extracts raw bytes from `Vec<u8>`/`[u8; N]` values, looking through newtype wrappers
+fn value_bytes(value: &Value<u32>, types: &PortableRegistry) -> Option<Vec<u8>> {
+    let ValueDef::Composite(Composite::Unnamed(values)) = &value.value else {
+        return None;
+    };
+
+    if is_byte_container(value.context, types) {
+        return values
+            .iter()
+            .map(|v| match &v.value {
+                ValueDef::Primitive(Primitive::U128(n)) => u8::try_from(*n).ok(),
+                _ => None,
+            })
+            .collect();
+    }
+
+    match values.as_slice() {
+        [inner] => value_bytes(inner, types),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scale_info::{Registry, meta_type};
+
+    #[test]
+    fn wide_integers_are_always_strings() {
+        assert_eq!(primitive_to_json(&Primitive::U128(5), true), json!("5"));
+        assert_eq!(primitive_to_json(&Primitive::I128(-5), true), json!("-5"));
+        assert_eq!(
+            primitive_to_json(&Primitive::U128(u128::MAX), true),
+            json!(u128::MAX.to_string())
+        );
+    }
+
+    #[test]
+    fn narrow_integers_stay_numbers() {
+        assert_eq!(primitive_to_json(&Primitive::U128(42), false), json!(42));
+        assert_eq!(primitive_to_json(&Primitive::I128(-42), false), json!(-42));
+    }
+
+    #[test]
+    fn other_primitives() {
+        assert_eq!(
+            primitive_to_json(&Primitive::Bool(true), false),
+            json!(true)
+        );
+        assert_eq!(primitive_to_json(&Primitive::Char('a'), false), json!("a"));
+        assert_eq!(
+            primitive_to_json(&Primitive::String("x".to_string()), false),
+            json!("x")
+        );
+
+        let mut bytes = [0u8; 32];
+        bytes[0] = 1;
+        assert_eq!(
+            primitive_to_json(&Primitive::U256(bytes), false),
+            json!("1")
+        );
+    }
+
+    #[test]
+    fn wide_integer_types() {
+        let mut registry = Registry::new();
+        let u128_id = registry.register_type(&meta_type::<u128>()).id;
+        let i128_id = registry.register_type(&meta_type::<i128>()).id;
+        let u64_id = registry.register_type(&meta_type::<u64>()).id;
+        let compact_id = registry
+            .register_type(&meta_type::<codec::Compact<u128>>())
+            .id;
+        let types = PortableRegistry::from(registry);
+
+        assert!(is_wide_integer(u128_id, &types));
+        assert!(is_wide_integer(i128_id, &types));
+        assert!(is_wide_integer(compact_id, &types));
+        assert!(!is_wide_integer(u64_id, &types));
+    }
+}
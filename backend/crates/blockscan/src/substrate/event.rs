@@ -1,19 +1,23 @@
 use blockscan_model::event::{EventsResponse, FormattedEvent};
-use config::{DECIMALS, DECIMALS_F64};
 use custom_error::ServiceError;
+use sp_core::twox_128;
 use substrate_api_client::{
     Api, GetStorage,
-    ac_primitives::{DefaultRuntimeConfig, H256},
+    ac_primitives::{DefaultRuntimeConfig, H256, StorageKey},
     rpc::JsonrpseeClient,
 };
+use subxt::{
+    PolkadotConfig,
+    ext::subxt_core::events::{self, EventDetails, Phase},
+};
+
+use super::decode::{composite_to_json, json_accounts, runtime_metadata};
 
 pub struct EventInfo {
     pub api: Api<DefaultRuntimeConfig, JsonrpseeClient>,
     pub block_hash: Option<H256>,
 }
 
-type EventRecord = frame_system::EventRecord<selendra_runtime::RuntimeEvent, H256>;
-
 impl EventInfo {
     #[inline]
     pub const fn new(
@@ -24,29 +28,33 @@ impl EventInfo {
     }
 
     pub async fn get_events(&self) -> Result<EventsResponse, ServiceError> {
-        let events = self
+        let metadata = runtime_metadata()?;
+
+        // System.Events is a plain storage value: twox128("System") ++ twox128("Events")
+        let storage_key = [twox_128(b"System"), twox_128(b"Events")].concat();
+        let event_bytes = self
             .api
-            .get_storage::<Vec<EventRecord>>("System", "Events", self.block_hash)
+            .get_opaque_storage_by_key(StorageKey(storage_key), self.block_hash)
             .await
             .map_err(|e| ServiceError::SubstrateError(format!("Failed to get events: {:?}", e)))?;
 
-        let Some(events) = events else {
+        let Some(event_bytes) = event_bytes else {
             return Ok(EventsResponse {
                 total_count: 0,
                 events: Vec::new(),
             });
         };
 
+        let events = events::decode_from::<PolkadotConfig>(event_bytes, metadata.clone());
+
         // Pre-allocate vector with known capacity
-        let mut formatted_events = Vec::with_capacity(events.len());
+        let mut formatted_events = Vec::with_capacity(events.len() as usize);
 
-        for (index, event_record) in events.iter().enumerate() {
-            formatted_events.push(FormattedEvent {
-                index: index + 1,
-                phase: Self::format_phase(&event_record.phase),
-                event: self.format_event(&event_record.event),
-                topics: event_record.topics.clone(),
-            });
+        for event in events.iter() {
+            let event = event.map_err(|e| {
+                ServiceError::SubstrateError(format!("Failed to decode event: {:?}", e))
+            })?;
+            formatted_events.push(Self::format_event(&event, metadata)?);
         }
 
         Ok(EventsResponse {
@@ -55,88 +63,49 @@ impl EventInfo {
         })
     }
 
-    #[inline]
-    fn format_phase(phase: &frame_system::Phase) -> String {
-        match phase {
-            frame_system::Phase::ApplyExtrinsic(index) => format!("Extrinsic #{index}"),
-            frame_system::Phase::Finalization => "Finalization".to_string(),
-            frame_system::Phase::Initialization => "Initialization".to_string(),
-        }
-    }
+    fn format_event(
+        event: &EventDetails<PolkadotConfig>,
+        metadata: &subxt::Metadata,
+    ) -> Result<FormattedEvent, ServiceError> {
+        let fields = event.field_values().map_err(|e| {
+            ServiceError::SubstrateError(format!(
+                "Failed to decode fields of {}.{}: {:?}",
+                event.pallet_name(),
+                event.variant_name(),
+                e
+            ))
+        })?;
 
-    fn format_event(&self, event: &selendra_runtime::RuntimeEvent) -> String {
-        match event {
-            selendra_runtime::RuntimeEvent::System(system_event) => {
-                format!("System::{}", Self::format_system_event(system_event))
-            }
-            selendra_runtime::RuntimeEvent::Balances(balance_event) => {
-                format!("Balances::{}", self.format_balance_event(balance_event))
-            }
-            _ => format!("{event:?}"),
-        }
-    }
+        let phase = event.phase();
+        let extrinsic_index = match &phase {
+            Phase::ApplyExtrinsic(index) => Some(*index),
+            _ => None,
+        };
 
-    fn format_balance_event(
-        &self,
-        event: &pallet_balances::pallet::Event<selendra_runtime::Runtime>,
-    ) -> String {
-        match event {
-            pallet_balances::pallet::Event::Transfer { from, to, amount } => {
-                format!(
-                    "Transfer {{ from: {from}, to: {to}, amount: {} }}",
-                    Self::format_balance(*amount)
-                )
-            }
-            pallet_balances::pallet::Event::Withdraw { who, amount } => {
-                format!(
-                    "Withdraw {{ who: {who}, amount: {} }}",
-                    Self::format_balance(*amount)
-                )
-            }
-            pallet_balances::pallet::Event::Endowed {
-                account,
-                free_balance,
-            } => {
-                format!(
-                    "Endowed {{ account: {account}, balance: {} }}",
-                    Self::format_balance(*free_balance)
-                )
-            }
-            pallet_balances::pallet::Event::Deposit { who, amount } => {
-                format!(
-                    "Deposit {{ who: {who}, amount: {} }}",
-                    Self::format_balance(*amount)
-                )
-            }
-            _ => format!("{event:?}"),
-        }
-    }
+        let fields = composite_to_json(&fields, metadata.types());
 
-    fn format_system_event(
-        event: &frame_system::pallet::Event<selendra_runtime::Runtime>,
-    ) -> String {
-        match event {
-            frame_system::pallet::Event::ExtrinsicSuccess { dispatch_info } => {
-                format!(
-                    "ExtrinsicSuccess (weight: {}, class: {:?})",
-                    dispatch_info.weight.ref_time(),
-                    dispatch_info.class
-                )
-            }
-            frame_system::pallet::Event::NewAccount { account } => {
-                format!("NewAccount {{ account: {account} }}")
-            }
-            _ => format!("{event:?}"),
-        }
+        Ok(FormattedEvent {
+            index: event.index() as usize,
+            phase: Self::format_phase(&phase),
+            extrinsic_index,
+            pallet: event.pallet_name().to_string(),
+            variant: event.variant_name().to_string(),
+            accounts: json_accounts(&fields),
+            fields,
+            topics: event
+                .topics()
+                .iter()
+                .map(|topic| format!("{:#x}", topic))
+                .collect(),
+        })
     }
 
     #[inline]
-    const fn format_balance(amount: u128) -> f64 {
-        // More efficient conversion avoiding floating point division when possible
-        if amount >= DECIMALS {
-            (amount / DECIMALS) as f64 + ((amount % DECIMALS) as f64 / DECIMALS_F64)
-        } else {
-            amount as f64 / DECIMALS_F64
+    fn format_phase(phase: &Phase) -> String {
+        match phase {
+            Phase::ApplyExtrinsic(index) => format!("Extrinsic #{index}"),
+            Phase::Finalization => "Finalization".to_string(),
+            Phase::Initialization => "Initialization".to_string(),
         }
     }
 }
@@ -1,12 +1,22 @@
+pub mod address;
+mod decode;
 mod event;
 mod extrinsic;
+mod outcome;
 pub mod substrate_subxt;
 mod validator;
 
+use std::collections::HashMap;
+
 use blockscan_model::{
-    event::EventsResponse, extrinsic::ExtrinsicDetails, validator::ActiveValidator,
+    event::EventsResponse,
+    extrinsic::{ExtrinsicDetails, ExtrinsicOutcome},
+    validator::{ActiveValidator, EraRewardPoints, SessionValidator, SessionValidators},
 };
 use custom_error::ServiceError;
+pub use decode::json_accounts;
+use futures::future::try_join_all;
+pub use outcome::json_to_u128;
 pub use substrate_api_client::rpc::JsonrpseeClient;
 use substrate_api_client::{
     Api, GetChainInfo, GetStorage,
@@ -109,11 +119,55 @@ impl SubstrtaeBlockQuery {
         event.get_events().await
     }
 
+    /// Success, fee and weight of each extrinsic, keyed by extrinsic index
+    pub fn extrinsic_outcomes(
+        &self,
+        events: &EventsResponse,
+    ) -> Result<HashMap<u32, ExtrinsicOutcome>, ServiceError> {
+        let metadata = decode::runtime_metadata()?;
+        Ok(outcome::extrinsic_outcomes(&events.events, metadata))
+    }
+
     pub async fn active_validaora(&self) -> Result<Vec<ActiveValidator>, ServiceError> {
         let validator = validator::ValidatorInfo::new(self.api.clone(), self.block_hash);
         validator.get_all_validators().await
     }
 
+    /// Validators of the active era with the blocks each produced in the current session
+    pub async fn session_validators(&self) -> Result<SessionValidators, ServiceError> {
+        let validator = validator::ValidatorInfo::new(self.api.clone(), self.block_hash);
+        let (era, session) =
+            futures::try_join!(validator.active_era(), validator.current_session())?;
+        let validators = validator.get_validators_of_era(era).await?;
+
+        let block_counts = try_join_all(
+            validators
+                .iter()
+                .map(|active| validator.session_block_count(&active.account_id)),
+        )
+        .await?;
+
+        Ok(SessionValidators {
+            era,
+            session,
+            validators: validators
+                .into_iter()
+                .zip(block_counts)
+                .map(|(validator, blocks)| SessionValidator { validator, blocks })
+                .collect(),
+        })
+    }
+
+    pub async fn active_era(&self) -> Result<u32, ServiceError> {
+        let validator = validator::ValidatorInfo::new(self.api.clone(), self.block_hash);
+        validator.active_era().await
+    }
+
+    pub async fn era_reward_points(&self, era: u32) -> Result<EraRewardPoints, ServiceError> {
+        let validator = validator::ValidatorInfo::new(self.api.clone(), self.block_hash);
+        validator.era_reward_points(era).await
+    }
+
     pub async fn get_block_timestamp(&self) -> Result<u64, ServiceError> {
         match self
             .api
@@ -140,6 +194,16 @@ impl SubstrtaeBlockQuery {
             return Ok(true);
         }
 
+        let finalized_block_number = self.finalized_block_number().await?;
+
+        Ok(self.block_number <= finalized_block_number)
+    }
+
+    pub async fn finalized_block_number(&self) -> Result<u32, ServiceError> {
+        let finalized_head = self.api.get_finalized_head().await.map_err(|e| {
+            ServiceError::SubstrateError(format!("Error getting finalized head: {:?}", e))
+        })?;
+
         let finalized_block = self
             .api
             .get_block(finalized_head)
@@ -149,8 +213,6 @@ impl SubstrtaeBlockQuery {
             })?
             .ok_or_else(|| ServiceError::SubstrateError("Finalized block not found".to_string()))?;
 
-        let finalized_block_number = finalized_block.header.number;
-
-        Ok(self.block_number <= finalized_block_number)
+        Ok(finalized_block.header.number)
     }
 }
@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+
+use blockscan_model::{event::FormattedEvent, extrinsic::ExtrinsicOutcome};
+use serde_json::Value as JsonValue;
+use subxt::Metadata;
+
+/// Builds the outcome of every extrinsic in a block from the block's decoded events.
+///
+/// `System.ExtrinsicSuccess`/`System.ExtrinsicFailed` provide the status and weight,
+/// `TransactionPayment.TransactionFeePaid` provides the fee and tip.
+pub fn extrinsic_outcomes(
+    events: &[FormattedEvent],
+    metadata: &Metadata,
+) -> HashMap<u32, ExtrinsicOutcome> {
+    let mut outcomes: HashMap<u32, ExtrinsicOutcome> = HashMap::new();
+
+    for event in events {
+        let Some(extrinsic_index) = event.extrinsic_index else {
+            continue;
+        };
+
+        let outcome = outcomes.entry(extrinsic_index).or_default();
+
+        match (event.pallet.as_str(), event.variant.as_str()) {
+            ("System", "ExtrinsicSuccess") => {
+                outcome.success = true;
+                outcome.weight = dispatch_weight(&event.fields);
+            }
+            ("System", "ExtrinsicFailed") => {
+                outcome.success = false;
+                outcome.weight = dispatch_weight(&event.fields);
+                outcome.dispatch_error = event
+                    .fields
+                    .get("dispatch_error")
+                    .map(|error| dispatch_error_name(error, metadata));
+            }
+            ("TransactionPayment", "TransactionFeePaid") => {
+                outcome.actual_fee = event.fields.get("actual_fee").and_then(json_to_u128);
+                outcome.tip = event.fields.get("tip").and_then(json_to_u128);
+            }
+            _ => {}
+        }
+    }
+
+    outcomes
+}
+
+/// `ref_time` of the `dispatch_info.weight` carried by the system extrinsic events
+fn dispatch_weight(fields: &JsonValue) -> Option<u64> {
+    fields
+        .get("dispatch_info")?
+        .get("weight")?
+        .get("ref_time")?
+        .as_u64()
+}
+
+/// Resolves a decoded `DispatchError` into a readable name such as `Balances.InsufficientBalance`
+fn dispatch_error_name(error: &JsonValue, metadata: &Metadata) -> String {
+    if let Some(module_error) = error.get("Module") {
+        if let Some(name) = module_error_name(module_error, metadata) {
+            return name;
+        }
+    }
+
+    // Other variants decode as `"BadOrigin"` or `{"Token": "FundsUnavailable"}`
+    match error {
+        JsonValue::String(name) => name.clone(),
+        JsonValue::Object(map) if map.len() == 1 => match map.iter().next() {
+            Some((kind, JsonValue::String(detail))) => format!("{}.{}", kind, detail),
+            Some((kind, detail)) => format!("{}.{}", kind, detail),
+            None => error.to_string(),
+        },
+        _ => error.to_string(),
+    }
+}
+
+/// Looks up the pallet error variant of a `ModuleError { index, error }`
+fn module_error_name(module_error: &JsonValue, metadata: &Metadata) -> Option<String> {
+    let pallet_index = u8::try_from(module_error.get("index")?.as_u64()?).ok()?;
+    let error_bytes = module_error.get("error")?.as_str()?;
+    let error_index = hex_first_byte(error_bytes)?;
+
+    let pallet = metadata.pallet_by_index(pallet_index)?;
+    let variant = pallet.error_variant_by_index(error_index)?;
+
+    Some(format!("{}.{}", pallet.name(), variant.name))
+}
+
+fn hex_first_byte(value: &str) -> Option<u8> {
+    let hex = value.strip_prefix("0x").unwrap_or(value);
+    u8::from_str_radix(hex.get(0..2)?, 16).ok()
+}
+
+/// Balances decode as decimal strings, rows stored before that may still hold numbers
+pub fn json_to_u128(value: &JsonValue) -> Option<u128> {
+    match value {
+        JsonValue::Number(n) => n.as_u64().map(u128::from),
+        JsonValue::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
@@ -1,13 +1,9 @@
-use sp_core::{
-    H160,
-    crypto::{AccountId32, Ss58Codec},
-};
-use sp_runtime::traits::{BlakeTwo256, Hash};
-
 use config::selendra;
 use custom_error::ServiceError;
 use subxt::{OnlineClient, SubstrateConfig};
 
+use super::address;
+
 pub struct SubstrtaeGeneralQuery {
     pub api: OnlineClient<SubstrateConfig>,
 }
@@ -171,65 +167,11 @@ impl SubstrtaeGeneralQuery {
     }
 
     pub fn ss58_to_evm_address(&self, ss58_address: &str) -> Result<String, ServiceError> {
-        // Parse SS58 string to AccountId32
-        let account_id = AccountId32::from_ss58check(ss58_address)
-            .map_err(|e| ServiceError::SubstrateError(format!("Failed to parse SS58: {:?}", e)))?;
-
-        // Convert AccountId32 to EVM address (first 20 bytes)
-        let account_bytes: &[u8; 32] = account_id.as_ref();
-        let mut evm_bytes = [0u8; 20];
-        evm_bytes.copy_from_slice(&account_bytes[0..20]);
-        let address = H160(evm_bytes);
-
-        Ok(format!("0x{:x}", address))
+        address::ss58_to_evm_address(ss58_address)
     }
 
     /// Convert EVM address (string) to SS58 address (string)
     pub fn evm_to_ss58(&self, evm_address: &str) -> Result<String, ServiceError> {
-        let evm_address = self.parse_evm_address(evm_address)?;
-
-        let mut data = [0u8; 24];
-        data[0..4].copy_from_slice(b"evm:"); // 4-byte prefix
-        data[4..24].copy_from_slice(&evm_address[..]); // 20-byte EVM address
-        let hash = BlakeTwo256::hash(&data);
-
-        let substrate_account = AccountId32::from(hash.0);
-
-        let ss58_address = substrate_account
-            .to_ss58check_with_version(sp_core::crypto::Ss58AddressFormat::custom(42));
-        Ok(ss58_address)
-    }
-
-    /// Helper function to parse EVM address string
-    fn parse_evm_address(&self, address_str: &str) -> Result<H160, ServiceError> {
-        // Remove "0x" prefix if present
-        let clean_address = if address_str.starts_with("0x") || address_str.starts_with("0X") {
-            &address_str[2..]
-        } else {
-            address_str
-        };
-
-        // Check length (40 hex characters = 20 bytes)
-        if clean_address.len() != 40 {
-            return Err(ServiceError::SubstrateError(format!(
-                "EVM address must be 40 hex characters, got {}",
-                clean_address.len()
-            )));
-        }
-
-        // Parse hex string to bytes
-        let bytes = hex::decode(clean_address)
-            .map_err(|e| ServiceError::SubstrateError(format!("Invalid hex: {:?}", e)))?;
-
-        if bytes.len() != 20 {
-            return Err(ServiceError::SubstrateError(format!(
-                "EVM address must be 20 bytes, got {}",
-                bytes.len()
-            )));
-        }
-
-        let mut address_bytes = [0u8; 20];
-        address_bytes.copy_from_slice(&bytes);
-        Ok(H160(address_bytes))
+        address::evm_to_ss58(evm_address)
     }
 }
@@ -1,4 +1,7 @@
-use blockscan_model::validator::{ActiveValidator, StakingInfo, ValidatorPrefs, ValidatorType};
+use blockscan_model::validator::{
+    ActiveValidator, EraRewardPoints, StakingInfo, ValidatorEraPoints, ValidatorPrefs,
+    ValidatorType,
+};
 use codec::{Decode, Encode};
 use config::COMMISSION_DENOMINATOR;
 use custom_error::ServiceError;
@@ -43,8 +46,16 @@ impl ValidatorInfo {
     }
 
     pub async fn get_all_validators(&self) -> Result<Vec<ActiveValidator>, ServiceError> {
-        let era_validators = self.get_era_validators().await?;
         let current_era = self.current_era().await?;
+        self.get_validators_of_era(current_era).await
+    }
+
+    /// Validators elected into `Elections.CurrentEraValidators` with their stake in `era`
+    pub async fn get_validators_of_era(
+        &self,
+        current_era: u32,
+    ) -> Result<Vec<ActiveValidator>, ServiceError> {
+        let era_validators = self.get_era_validators().await?;
 
         // Pre-allocate vector with known size
         let total_validators = era_validators.reserved.len() + era_validators.non_reserved.len();
@@ -173,6 +184,38 @@ impl ValidatorInfo {
             })
     }
 
+    /// Blocks produced by a validator in the current session
+    pub async fn session_block_count(&self, account_id: &str) -> Result<u32, ServiceError> {
+        let account = AccountId32::from_ss58check(account_id)
+            .map_err(|e| ServiceError::SubstrateError(format!("Failed to parse SS58: {:?}", e)))?;
+
+        self.api
+            .get_storage_map::<AccountId32, u32>(
+                "CommitteeManagement",
+                "SessionValidatorBlockCount",
+                account,
+                self.block_hash,
+            )
+            .await
+            .map(|count| count.unwrap_or_default())
+            .map_err(|e| {
+                ServiceError::SubstrateError(format!(
+                    "Failed to get session block count for {}: {:?}",
+                    account_id, e
+                ))
+            })
+    }
+
+    pub async fn current_session(&self) -> Result<u32, ServiceError> {
+        self.api
+            .get_storage::<u32>("Session", "CurrentIndex", self.block_hash)
+            .await
+            .map_err(|e| {
+                ServiceError::SubstrateError(format!("Failed to get current session: {:?}", e))
+            })?
+            .ok_or_else(|| ServiceError::SubstrateError("No current session found".to_string()))
+    }
+
     async fn current_era(&self) -> Result<u32, ServiceError> {
         self.api
             .get_storage::<u32>("Staking", "CurrentEra", self.block_hash)
@@ -182,4 +225,79 @@ impl ValidatorInfo {
             })?
             .ok_or_else(|| ServiceError::SubstrateError("No current era found".to_string()))
     }
+
+    pub async fn active_era(&self) -> Result<u32, ServiceError> {
+        self.api
+            .get_storage::<ActiveEraInfo>("Staking", "ActiveEra", self.block_hash)
+            .await
+            .map_err(|e| {
+                ServiceError::SubstrateError(format!("Failed to get active era: {:?}", e))
+            })?
+            .map(|info| info.index)
+            .ok_or_else(|| ServiceError::SubstrateError("No active era found".to_string()))
+    }
+
+    /// Reward points of an era with each validator's commission for that era
+    pub async fn era_reward_points(&self, era: u32) -> Result<EraRewardPoints, ServiceError> {
+        let points = self
+            .api
+            .get_storage_map::<u32, pallet_staking::EraRewardPoints<AccountId32>>(
+                "Staking",
+                "ErasRewardPoints",
+                era,
+                self.block_hash,
+            )
+            .await
+            .map_err(|e| {
+                ServiceError::SubstrateError(format!(
+                    "Failed to get reward points for era {}: {:?}",
+                    era, e
+                ))
+            })?
+            .unwrap_or_default();
+
+        let futures = points
+            .individual
+            .into_iter()
+            .map(|(account_id, points)| async move {
+                let prefs = self.get_era_validator_prefs(era, &account_id).await?;
+                Ok::<_, ServiceError>(ValidatorEraPoints {
+                    account_id: account_id.to_ss58check(),
+                    points,
+                    commission: prefs.map_or(0.0, |p| {
+                        p.commission.deconstruct() as f64 / COMMISSION_DENOMINATOR
+                    }),
+                })
+            });
+
+        Ok(EraRewardPoints {
+            era,
+            total: points.total,
+            validators: try_join_all(futures).await?,
+        })
+    }
+
+    async fn get_era_validator_prefs(
+        &self,
+        era: u32,
+        account_id: &AccountId32,
+    ) -> Result<Option<pallet_staking::ValidatorPrefs>, ServiceError> {
+        self.api
+            .get_storage_double_map::<u32, AccountId32, pallet_staking::ValidatorPrefs>(
+                "Staking",
+                "ErasValidatorPrefs",
+                era,
+                account_id.clone(),
+                self.block_hash,
+            )
+            .await
+            .map_err(|e| {
+                ServiceError::SubstrateError(format!(
+                    "Failed to get era {} prefs for {}: {:?}",
+                    era,
+                    account_id.to_ss58check(),
+                    e
+                ))
+            })
+    }
 }
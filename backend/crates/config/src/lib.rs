@@ -3,18 +3,52 @@ use lazy_static::lazy_static;
 use std::env;
 
 pub const ACCOUNTS_TABLE: &'static str = "accounts";
+pub const ACCOUNT_LINKS_TABLE: &'static str = "account_links";
 
 pub const EVM_BLOCK_TABLE: &'static str = "evm_blocks";
 pub const EVM_TXS_TABLE: &'static str = "evm_transaction";
 pub const EVM_CONTRACTS_TABLE: &'static str = "evm_contracts";
+pub const EVM_ORPHANED_BLOCKS_TABLE: &'static str = "evm_orphaned_blocks";
+pub const TOKEN_TRANSFERS_TABLE: &'static str = "token_transfers";
+pub const TOKEN_BALANCES_TABLE: &'static str = "token_balances";
+pub const EVM_LOGS_TABLE: &'static str = "evm_logs";
+pub const EVM_INTERNAL_TXS_TABLE: &'static str = "evm_internal_transactions";
+pub const EVM_CONTRACT_SOURCES_TABLE: &'static str = "evm_contract_sources";
+pub const EVM_PROXY_IMPLEMENTATIONS_TABLE: &'static str = "evm_proxy_implementations";
 
 pub const SUBSTRATE_BLOCKS_TABLE: &str = "substrate_blocks";
 pub const SUBSTRATE_EXTRINSICS_TABLE: &str = "substrate_extrinsics";
 pub const SUBSTRATE_EVENTS_TABLE: &str = "substrate_events";
+pub const STAKING_REWARDS_TABLE: &str = "staking_rewards";
+pub const STAKING_SLASHES_TABLE: &str = "staking_slashes";
+pub const ERA_PAYOUTS_TABLE: &str = "era_payouts";
+pub const ERA_VALIDATOR_POINTS_TABLE: &str = "era_validator_points";
+pub const VALIDATOR_SNAPSHOTS_TABLE: &str = "validator_snapshots";
+pub const STAKING_LEDGERS_TABLE: &str = "staking_ledgers";
+pub const IDENTITIES_TABLE: &str = "identities";
+pub const SUBSTRATE_TRANSFERS_TABLE: &str = "substrate_transfers";
+
+pub const SYNC_STATE_TABLE: &str = "sync_state";
+pub const INDEXED_BLOCKS_TABLE: &str = "indexed_blocks";
+
+pub const WEBHOOKS_TABLE: &str = "webhooks";
+pub const WEBHOOK_DELIVERIES_TABLE: &str = "webhook_deliveries";
+pub const WEBHOOK_DEAD_LETTERS_TABLE: &str = "webhook_dead_letters";
+
+// Indexer identifiers used as sync_state record ids
+pub const EVM_INDEXER: &str = "evm";
+pub const SUBSTRATE_INDEXER: &str = "substrate";
+pub const ACCOUNT_INDEXER: &str = "account";
+pub const TRANSFER_BACKFILL: &str = "substrate_transfers"; // Blocks indexed before transfers
+pub const WEBHOOK_EVM_MATCHER: &str = "webhook_evm"; // Last EVM block matched against webhooks
+pub const WEBHOOK_SUBSTRATE_MATCHER: &str = "webhook_substrate";
 
 #[subxt::subxt(runtime_metadata_path = "selendra_metadata.scale")]
 pub mod selendra {}
 
+/// Raw runtime metadata used for dynamic SCALE decoding
+pub const SELENDRA_METADATA: &[u8] = include_bytes!("../selendra_metadata.scale");
+
 lazy_static! {
     pub static ref EVM_RPC_URL: String = {
         dotenv().ok();
@@ -32,8 +66,26 @@ lazy_static! {
         env::var("DATABASE_TABLE").expect("DATABASE_TABLE must be set");
     pub static ref SUBSTRATE_URL: String =
         env::var("SUBSTRATE_URL").expect("SUBSTRATE_URL must be set");
+    // Only nodes running with the debug namespace can serve debug_traceTransaction
+    pub static ref EVM_TRACING_ENABLED: bool = env::var("EVM_TRACING_ENABLED")
+        .map(|value| value == "true" || value == "1")
+        .unwrap_or(false);
+    // Rolls back and re-indexes every orphaned EVM block from this number, the way out of a
+    // reorganization deeper than the indexer searches on its own
+    pub static ref EVM_RESYNC_FROM: Option<u64> = env::var("EVM_RESYNC_FROM")
+        .ok()
+        .and_then(|value| value.parse().ok());
+    pub static ref SOLC_PATH: String =
+        env::var("SOLC_PATH").unwrap_or_else(|_| "solc".to_string());
+    // Lets webhooks point at loopback and private networks, only for testing with a local receiver
+    pub static ref WEBHOOK_ALLOW_PRIVATE_URLS: bool = env::var("WEBHOOK_ALLOW_PRIVATE_URLS")
+        .map(|value| value == "true" || value == "1")
+        .unwrap_or(false);
 }
 
+/// SS58 address format of the chain, every stored account uses it
+pub const SS58_PREFIX: u16 = 42;
+
 pub const SESSIONS_PER_ERA: u32 = 96;
 pub const BLOCKS_PER_SESSION: u32 = 900;
 pub const BLOCKS_PER_ERA: u32 = SESSIONS_PER_ERA * BLOCKS_PER_SESSION;
@@ -0,0 +1,74 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use config::ACCOUNT_LINKS_TABLE;
+use custom_error::ServiceError;
+use models::{AccountLink, AccountLinkKind};
+use surrealdb::{Surreal, engine::any::Any};
+
+pub struct AccountLinkService<'a> {
+    pub db: &'a Surreal<Any>,
+}
+
+// Linked identities, one record per (evm_address, substrate_address)
+impl<'a> AccountLinkService<'a> {
+    /// Links the two forms of an account, created now unless the link already exists
+    pub async fn link(
+        &self,
+        evm_address: &str,
+        substrate_address: &str,
+        kind: AccountLinkKind,
+    ) -> Result<(), ServiceError> {
+        let link = AccountLink {
+            evm_address: evm_address.to_lowercase(),
+            substrate_address: substrate_address.to_string(),
+            kind,
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis())
+                .unwrap_or(0),
+        };
+
+        self.save(&link).await
+    }
+
+    /// Records a link, the creation time of an existing one is kept
+    pub async fn save(&self, link: &AccountLink) -> Result<(), ServiceError> {
+        self.db
+            .query(
+                "UPSERT type::thing($table, [$evm_address, $substrate_address]) SET evm_address = $evm_address, substrate_address = $substrate_address, kind = $kind, created_at = created_at ?? $created_at",
+            )
+            .bind(("table", ACCOUNT_LINKS_TABLE))
+            .bind(("evm_address", link.evm_address.to_lowercase()))
+            .bind(("substrate_address", link.substrate_address.clone()))
+            .bind(("kind", link.kind))
+            .bind(("created_at", link.created_at))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Account link save failed: {}", e)))?
+            .check()
+            .map_err(|e| ServiceError::DatabaseError(format!("Account link save failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Links of an address, whichever side of the link it is on
+    pub async fn get_by_address(&self, address: &str) -> Result<Vec<AccountLink>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE evm_address = $address OR substrate_address = $address",
+            ACCOUNT_LINKS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("address", address.to_string()))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Account links query failed: {}", e))
+            })?;
+
+        let links: Vec<AccountLink> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Account links extraction failed: {}", e))
+        })?;
+
+        Ok(links)
+    }
+}
@@ -0,0 +1,76 @@
+use custom_error::ServiceError;
+use models::{
+    activity::{AccountActivity, ActivityCursor, ActivityFilter},
+    evm::EvmAddressTxFilter,
+    substrate::SubstrateAccountFilter,
+};
+use surrealdb::{Surreal, engine::any::Any};
+
+use crate::{
+    evm::TransactionService,
+    substrate::{SubstrateEventService, SubstrateExtrinsicService},
+};
+
+pub struct ActivityService<'a> {
+    pub db: &'a Surreal<Any>,
+}
+
+// Activity timeline merged from EVM transactions, signed extrinsics and events
+impl<'a> ActivityService<'a> {
+    /// Newest-first activity of EVM addresses and SS58 accounts. Each source is read up
+    /// to `limit` items past the cursor, so the merged head is complete
+    pub async fn get_by_addresses(
+        &self,
+        evm_addresses: &[String],
+        accounts: &[String],
+        filter: &ActivityFilter,
+        cursor: Option<&ActivityCursor>,
+        limit: u32,
+    ) -> Result<Vec<AccountActivity>, ServiceError> {
+        let mut activity = Vec::new();
+
+        let tx_filter = EvmAddressTxFilter {
+            from_timestamp: filter
+                .from_timestamp
+                .map(|timestamp| timestamp.div_ceil(1000)),
+            to_timestamp: filter.to_timestamp.map(|timestamp| timestamp / 1000),
+            ..Default::default()
+        };
+        let tx_cursor = cursor.map(ActivityCursor::evm_transactions);
+        let transactions = TransactionService { db: self.db };
+        for address in evm_addresses {
+            let txs = transactions
+                .get_by_address(address, &tx_filter, tx_cursor.as_ref(), limit)
+                .await?;
+            activity.extend(txs.into_iter().map(AccountActivity::EvmTransaction));
+        }
+
+        let substrate_filter = SubstrateAccountFilter {
+            from_timestamp: filter.from_timestamp.map(|timestamp| timestamp as u64),
+            to_timestamp: filter.to_timestamp.map(|timestamp| timestamp as u64),
+            ..Default::default()
+        };
+        let extrinsic_cursor = cursor.map(ActivityCursor::extrinsics);
+        let event_cursor = cursor.map(ActivityCursor::events);
+        let extrinsics = SubstrateExtrinsicService { db: self.db };
+        let events = SubstrateEventService { db: self.db };
+        for account in accounts {
+            let signed = extrinsics
+                .get_by_account(account, &substrate_filter, extrinsic_cursor.as_ref(), limit)
+                .await?;
+            activity.extend(signed.into_iter().map(AccountActivity::Extrinsic));
+
+            let mentioned = events
+                .get_by_account(account, &substrate_filter, event_cursor.as_ref(), limit)
+                .await?;
+            activity.extend(mentioned.into_iter().map(AccountActivity::Event));
+        }
+
+        activity.sort_by_key(|item| std::cmp::Reverse(item.sort_key()));
+        // The same item can be reached through several addresses
+        activity.dedup_by_key(|item| item.sort_key());
+        activity.truncate(limit as usize);
+
+        Ok(activity)
+    }
+}
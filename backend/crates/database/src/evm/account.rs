@@ -1,6 +1,6 @@
 use config::ACCOUNTS_TABLE;
 use custom_error::ServiceError;
-use models::AccountInfo;
+use models::{AccountInfo, AddressType};
 
 use super::AccountService;
 
@@ -40,21 +40,29 @@ impl<'a> AccountService<'a> {
         Ok(accounts)
     }
 
+    /// Accounts whose balance, in token units, lies in the range. Bounds are decimal strings
+    /// compared as SurrealDB decimals so large balances keep their precision.
     pub async fn get_accounts_by_balance_range(
         &self,
-        min_balance: f64,
-        max_balance: f64,
+        min_balance: &str,
+        max_balance: Option<&str>,
         limit: u32,
     ) -> Result<Vec<AccountInfo>, ServiceError> {
+        let mut conditions = vec!["<decimal> balance_token >= <decimal> $min_balance"];
+        if max_balance.is_some() {
+            conditions.push("<decimal> balance_token <= <decimal> $max_balance");
+        }
+
         let query = format!(
-            "SELECT * FROM {} WHERE balance_token >= $min_balance AND balance_token <= $max_balance ORDER BY balance_token DESC LIMIT $limit",
-            ACCOUNTS_TABLE
+            "SELECT *, <decimal> balance_token AS balance_rank FROM {} WHERE {} ORDER BY balance_rank DESC LIMIT $limit",
+            ACCOUNTS_TABLE,
+            conditions.join(" AND ")
         );
         let mut result = self
             .db
             .query(query)
-            .bind(("min_balance", min_balance))
-            .bind(("max_balance", max_balance))
+            .bind(("min_balance", min_balance.to_string()))
+            .bind(("max_balance", max_balance.map(str::to_string)))
             .bind(("limit", limit))
             .await
             .map_err(|e| {
@@ -91,25 +99,21 @@ impl<'a> AccountService<'a> {
         &self,
         address: &str,
         last_activity: Option<u128>,
-        balance_token: Option<f64>,
-        free_balance: Option<f64>,
+        balance_token: Option<&str>,
+        free_balance: Option<&str>,
     ) -> Result<Option<AccountInfo>, ServiceError> {
         let mut set_clauses = Vec::new();
-        let mut bindings = vec![("address", address.to_string())];
 
-        if let Some(timestamp) = last_activity {
+        if last_activity.is_some() {
             set_clauses.push("last_activity = $timestamp");
-            bindings.push(("timestamp", timestamp.to_string()));
         }
 
-        if let Some(balance) = balance_token {
+        if balance_token.is_some() {
             set_clauses.push("balance_token = $balance_token");
-            bindings.push(("balance_token", balance.to_string()));
         }
 
-        if let Some(balance) = free_balance {
+        if free_balance.is_some() {
             set_clauses.push("free_balance = $free_balance");
-            bindings.push(("free_balance", balance.to_string()));
         }
 
         if set_clauses.is_empty() {
@@ -124,12 +128,14 @@ impl<'a> AccountService<'a> {
             set_clauses.join(", ")
         );
 
-        let mut query_builder = self.db.query(query);
-        for (key, value) in bindings {
-            query_builder = query_builder.bind((key, value));
-        }
-
-        let mut result = query_builder
+        // Every value keeps its own type, the timestamp stays a number
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("address", address.to_string()))
+            .bind(("timestamp", last_activity))
+            .bind(("balance_token", balance_token.map(str::to_string)))
+            .bind(("free_balance", free_balance.map(str::to_string)))
             .await
             .map_err(|e| ServiceError::DatabaseError(format!("Account update failed: {}", e)))?;
 
@@ -158,4 +164,57 @@ impl<'a> AccountService<'a> {
 
         Ok(count.unwrap_or(0) > 0)
     }
+
+    /// Addresses of a type in address order, a page after `after`
+    pub async fn get_addresses_by_type(
+        &self,
+        address_type: &AddressType,
+        after: Option<&str>,
+        limit: u32,
+    ) -> Result<Vec<String>, ServiceError> {
+        let query = format!(
+            "SELECT VALUE address FROM {} WHERE address_type = $address_type AND ($after = NONE OR address > $after) ORDER BY address ASC LIMIT $limit",
+            ACCOUNTS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("address_type", address_type.clone()))
+            .bind(("after", after.map(str::to_string)))
+            .bind(("limit", limit))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        let addresses: Vec<String> = result
+            .take(0)
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        Ok(addresses)
+    }
+
+    pub async fn get_by_addresses(
+        &self,
+        addresses: &[String],
+    ) -> Result<Vec<AccountInfo>, ServiceError> {
+        if addresses.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let query = format!(
+            "SELECT * FROM {} WHERE address IN $addresses",
+            ACCOUNTS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("addresses", addresses.to_vec()))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        let accounts: Vec<AccountInfo> = result
+            .take(0)
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        Ok(accounts)
+    }
 }
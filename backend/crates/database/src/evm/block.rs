@@ -56,6 +56,39 @@ impl<'a> EvmBlockService<'a> {
         Ok(block)
     }
 
+    /// Last block at or before `timestamp`, or the first one at or after it
+    pub async fn get_closest_by_timestamp(
+        &self,
+        timestamp: u128,
+        before: bool,
+    ) -> Result<Option<EvmBlock>, ServiceError> {
+        let query = if before {
+            format!(
+                "SELECT * FROM {} WHERE timestamp <= $timestamp ORDER BY timestamp DESC, number DESC LIMIT 1",
+                EVM_BLOCK_TABLE
+            )
+        } else {
+            format!(
+                "SELECT * FROM {} WHERE timestamp >= $timestamp ORDER BY timestamp ASC, number ASC LIMIT 1",
+                EVM_BLOCK_TABLE
+            )
+        };
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("timestamp", timestamp))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Block by timestamp query failed: {}", e))
+            })?;
+
+        let block: Option<EvmBlock> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Block by timestamp extraction failed: {}", e))
+        })?;
+
+        Ok(block)
+    }
+
     /// Retrieves an EVM block by block number
     pub async fn get_by_number(&self, block_number: u32) -> Result<Option<EvmBlock>, ServiceError> {
         let query = format!(
@@ -118,4 +151,112 @@ impl<'a> EvmBlockService<'a> {
 
         Ok(count.unwrap_or(0) > 0)
     }
+
+    /// Retrieves all stored blocks from the given block number upwards, lowest first
+    pub async fn get_from(&self, block_number: u32) -> Result<Vec<EvmBlock>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE number >= $block_number ORDER BY number ASC",
+            EVM_BLOCK_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("block_number", block_number))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Block range query failed: {}", e)))?;
+
+        let blocks: Vec<EvmBlock> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Block range extraction failed: {}", e))
+        })?;
+
+        Ok(blocks)
+    }
+
+    pub async fn delete_by_number(&self, block_number: u32) -> Result<(), ServiceError> {
+        let query = format!(
+            "DELETE FROM {} WHERE number = $block_number",
+            EVM_BLOCK_TABLE
+        );
+        self.db
+            .query(query)
+            .bind(("block_number", block_number))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Block delete failed: {}", e)))?
+            .check()
+            .map_err(|e| ServiceError::DatabaseError(format!("Block delete failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Up to `limit` blocks below `before`, or the latest ones, highest first
+    pub async fn get_before(
+        &self,
+        before: Option<u32>,
+        limit: u32,
+    ) -> Result<Vec<EvmBlock>, ServiceError> {
+        let condition = if before.is_some() {
+            "WHERE number < $before"
+        } else {
+            ""
+        };
+        let query = format!(
+            "SELECT * FROM {} {} ORDER BY number DESC LIMIT $limit",
+            EVM_BLOCK_TABLE, condition
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("before", before))
+            .bind(("limit", limit))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Block page query failed: {}", e)))?;
+
+        let blocks: Vec<EvmBlock> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Block page extraction failed: {}", e))
+        })?;
+
+        Ok(blocks)
+    }
+
+    /// Stored blocks with numbers in `from..=to`, lowest first
+    pub async fn get_range(&self, from: u32, to: u32) -> Result<Vec<EvmBlock>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE number >= $from AND number <= $to ORDER BY number ASC",
+            EVM_BLOCK_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("from", from))
+            .bind(("to", to))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Block range query failed: {}", e)))?;
+
+        let blocks: Vec<EvmBlock> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Block range extraction failed: {}", e))
+        })?;
+
+        Ok(blocks)
+    }
+
+    /// Stored blocks among `numbers`, in no particular order
+    pub async fn get_by_numbers(&self, numbers: &[u32]) -> Result<Vec<EvmBlock>, ServiceError> {
+        if numbers.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let query = format!("SELECT * FROM {} WHERE number IN $numbers", EVM_BLOCK_TABLE);
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("numbers", numbers.to_vec()))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Blocks query failed: {}", e)))?;
+
+        let blocks: Vec<EvmBlock> = result
+            .take(0)
+            .map_err(|e| ServiceError::DatabaseError(format!("Blocks extraction failed: {}", e)))?;
+
+        Ok(blocks)
+    }
 }
@@ -1,8 +1,9 @@
 use config::EVM_CONTRACTS_TABLE;
 use custom_error::ServiceError;
-use models::evm::{ContractType, EvmContract};
+use models::evm::{ContractType, EvmContract, ProxyInfo};
 
 use super::ContractService;
+use crate::schema::prefix_range;
 
 // Contract service implementation
 impl<'a> ContractService<'a> {
@@ -126,6 +127,125 @@ impl<'a> ContractService<'a> {
         Ok(contract)
     }
 
+    pub async fn update_total_supply(
+        &self,
+        address: &str,
+        total_supply: &str,
+    ) -> Result<(), ServiceError> {
+        let query = format!(
+            "UPDATE {} SET total_supply = $total_supply WHERE address = $address",
+            EVM_CONTRACTS_TABLE
+        );
+        self.db
+            .query(query)
+            .bind(("address", address.to_string()))
+            .bind(("total_supply", total_supply.to_string()))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Contract total supply update failed: {}", e))
+            })?
+            .check()
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Contract total supply update failed: {}", e))
+            })?;
+
+        Ok(())
+    }
+
+    /// Store the current implementation of a proxy and the type it gives the proxy
+    pub async fn update_proxy(
+        &self,
+        address: &str,
+        contract_type: &ContractType,
+        proxy: Option<&ProxyInfo>,
+    ) -> Result<(), ServiceError> {
+        let query = format!(
+            "UPDATE {} SET contract_type = $contract_type, proxy = $proxy WHERE address = $address",
+            EVM_CONTRACTS_TABLE
+        );
+        self.db
+            .query(query)
+            .bind(("address", address.to_string()))
+            .bind(("contract_type", contract_type.clone()))
+            .bind(("proxy", proxy.cloned()))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Contract proxy update failed: {}", e))
+            })?
+            .check()
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Contract proxy update failed: {}", e))
+            })?;
+
+        Ok(())
+    }
+
+    pub async fn get_by_addresses(
+        &self,
+        addresses: &[String],
+    ) -> Result<Vec<EvmContract>, ServiceError> {
+        if addresses.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let query = format!(
+            "SELECT * FROM {} WHERE address IN $addresses",
+            EVM_CONTRACTS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("addresses", addresses.to_vec()))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Contracts query failed: {}", e)))?;
+
+        let contracts: Vec<EvmContract> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Contracts extraction failed: {}", e))
+        })?;
+
+        Ok(contracts)
+    }
+
+    /// Contracts whose name or symbol starts with `prefix`, ignoring case, up to `limit` of
+    /// each with the shortest completions first
+    pub async fn search_by_prefix(
+        &self,
+        prefix: &str,
+        limit: u32,
+    ) -> Result<Vec<EvmContract>, ServiceError> {
+        let (start, end) = prefix_range(prefix);
+        let query = format!(
+            "SELECT *, string::len(name_lower) AS match_length FROM {0} WHERE name_lower >= $start AND name_lower < $end ORDER BY match_length ASC, name_lower ASC LIMIT $limit;\n\
+             SELECT *, string::len(symbol_lower) AS match_length FROM {0} WHERE symbol_lower >= $start AND symbol_lower < $end ORDER BY match_length ASC, symbol_lower ASC LIMIT $limit;",
+            EVM_CONTRACTS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("start", start))
+            .bind(("end", end))
+            .bind(("limit", limit))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Contract search query failed: {}", e))
+            })?;
+
+        let mut contracts: Vec<EvmContract> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Contract search extraction failed: {}", e))
+        })?;
+        let by_symbol: Vec<EvmContract> = result.take(1).map_err(|e| {
+            ServiceError::DatabaseError(format!("Contract search extraction failed: {}", e))
+        })?;
+
+        for contract in by_symbol {
+            if !contracts.iter().any(|c| c.address == contract.address) {
+                contracts.push(contract);
+            }
+        }
+
+        Ok(contracts)
+    }
+
     pub async fn is_exist_by_address(&self, address: &str) -> Result<bool, ServiceError> {
         let query = format!(
             "SELECT VALUE count() FROM {} WHERE address = $address",
@@ -144,4 +264,21 @@ impl<'a> ContractService<'a> {
 
         Ok(count.unwrap_or(0) > 0)
     }
+
+    /// Delete contracts whose creation happened in the given block
+    pub async fn delete_by_creation_block(&self, block_number: u64) -> Result<(), ServiceError> {
+        let query = format!(
+            "DELETE FROM {} WHERE creator_info.block_number = $block_number",
+            EVM_CONTRACTS_TABLE
+        );
+        self.db
+            .query(query)
+            .bind(("block_number", block_number))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Contract delete failed: {}", e)))?
+            .check()
+            .map_err(|e| ServiceError::DatabaseError(format!("Contract delete failed: {}", e)))?;
+
+        Ok(())
+    }
 }
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use config::{EVM_CONTRACT_SOURCES_TABLE, EVM_CONTRACTS_TABLE};
+use custom_error::ServiceError;
+use models::evm::EvmContractSource;
+use serde::Deserialize;
+
+use super::ContractSourceService;
+use crate::schema::prefix_range;
+
+#[derive(Debug, Deserialize)]
+struct ContractName {
+    address: String,
+    contract_name: String,
+}
+
+// Contract source service implementation, one record per contract address
+impl<'a> ContractSourceService<'a> {
+    /// Store the verified source of a contract, replacing an earlier verification
+    pub async fn save(&self, source: &EvmContractSource) -> Result<(), ServiceError> {
+        self.db
+            .query("UPSERT type::thing($table, $address) CONTENT $source")
+            .bind(("table", EVM_CONTRACT_SOURCES_TABLE))
+            .bind(("address", source.address.to_lowercase()))
+            .bind(("source", source.clone()))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Contract source save failed: {}", e))
+            })?
+            .check()
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Contract source save failed: {}", e))
+            })?;
+
+        Ok(())
+    }
+
+    pub async fn get_by_address(
+        &self,
+        address: &str,
+    ) -> Result<Option<EvmContractSource>, ServiceError> {
+        let mut result = self
+            .db
+            .query("SELECT * FROM type::thing($table, $address)")
+            .bind(("table", EVM_CONTRACT_SOURCES_TABLE))
+            .bind(("address", address.to_lowercase()))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Contract source query failed: {}", e))
+            })?;
+
+        let source: Option<EvmContractSource> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Contract source extraction failed: {}", e))
+        })?;
+
+        Ok(source)
+    }
+
+    /// Verified contract names starting with `prefix`, ignoring case, keyed by address, the
+    /// `limit` shortest completions
+    pub async fn contract_names_by_prefix(
+        &self,
+        prefix: &str,
+        limit: u32,
+    ) -> Result<HashMap<String, String>, ServiceError> {
+        let (start, end) = prefix_range(prefix);
+        let query = format!(
+            "SELECT address, contract_name, string::len(contract_name_lower) AS match_length FROM {} WHERE contract_name_lower >= $start AND contract_name_lower < $end ORDER BY match_length ASC, contract_name_lower ASC LIMIT $limit",
+            EVM_CONTRACT_SOURCES_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("start", start))
+            .bind(("end", end))
+            .bind(("limit", limit))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Contract name search query failed: {}", e))
+            })?;
+
+        let names: Vec<ContractName> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Contract name search extraction failed: {}", e))
+        })?;
+
+        Ok(names
+            .into_iter()
+            .map(|name| (name.address, name.contract_name))
+            .collect())
+    }
+
+    /// ABI used to decode calls to and logs of an address, a proxy without verified source
+    /// of its own uses the ABI of its current implementation
+    pub async fn get_decoding_abi(
+        &self,
+        address: &str,
+    ) -> Result<Option<serde_json::Value>, ServiceError> {
+        if let Some(source) = self.get_by_address(address).await? {
+            return Ok(Some(source.abi));
+        }
+
+        let query = format!(
+            "SELECT VALUE proxy.implementation FROM {} WHERE address = $address AND proxy != NONE LIMIT 1",
+            EVM_CONTRACTS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("address", address.to_lowercase()))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Proxy implementation query failed: {}", e))
+            })?;
+
+        let implementation: Option<String> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Proxy implementation extraction failed: {}", e))
+        })?;
+
+        match implementation {
+            Some(implementation) => Ok(self
+                .get_by_address(&implementation)
+                .await?
+                .map(|source| source.abi)),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use config::EVM_INTERNAL_TXS_TABLE;
+use custom_error::ServiceError;
+use models::evm::EvmInternalTransaction;
+
+use super::InternalTransactionService;
+
+impl<'a> InternalTransactionService<'a> {
+    pub async fn save(
+        &self,
+        internal_tx: &EvmInternalTransaction,
+    ) -> Result<EvmInternalTransaction, ServiceError> {
+        let created: EvmInternalTransaction = self
+            .db
+            .create(EVM_INTERNAL_TXS_TABLE)
+            .content(internal_tx.clone())
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Internal transaction save failed: {}", e))
+            })?
+            .ok_or_else(|| {
+                ServiceError::DatabaseError(
+                    "Failed to create internal transaction record".to_string(),
+                )
+            })?;
+
+        Ok(created)
+    }
+
+    /// Get the call tree of a transaction in execution order
+    pub async fn get_by_transaction(
+        &self,
+        transaction_hash: &str,
+    ) -> Result<Vec<EvmInternalTransaction>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE transaction_hash = $transaction_hash ORDER BY trace_address ASC",
+            EVM_INTERNAL_TXS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("transaction_hash", transaction_hash.to_lowercase()))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        let internal_txs: Vec<EvmInternalTransaction> = result
+            .take(0)
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        Ok(internal_txs)
+    }
+
+    /// Get paginated internal transactions sent or received by an address
+    pub async fn get_by_address(
+        &self,
+        address: &str,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<EvmInternalTransaction>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE from = $address OR to = $address ORDER BY block_number DESC LIMIT $limit START $offset",
+            EVM_INTERNAL_TXS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("address", address.to_lowercase()))
+            .bind(("limit", limit))
+            .bind(("offset", offset))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        let internal_txs: Vec<EvmInternalTransaction> = result
+            .take(0)
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        Ok(internal_txs)
+    }
+
+    pub async fn delete_by_transaction(&self, transaction_hash: &str) -> Result<(), ServiceError> {
+        let query = format!(
+            "DELETE FROM {} WHERE transaction_hash = $transaction_hash",
+            EVM_INTERNAL_TXS_TABLE
+        );
+        self.db
+            .query(query)
+            .bind(("transaction_hash", transaction_hash.to_lowercase()))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Internal transaction delete failed: {}", e))
+            })?
+            .check()
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Internal transaction delete failed: {}", e))
+            })?;
+
+        Ok(())
+    }
+
+    pub async fn delete_by_block_number(&self, block_number: u64) -> Result<(), ServiceError> {
+        let query = format!(
+            "DELETE FROM {} WHERE block_number = $block_number",
+            EVM_INTERNAL_TXS_TABLE
+        );
+        self.db
+            .query(query)
+            .bind(("block_number", block_number))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Internal transaction delete failed: {}", e))
+            })?
+            .check()
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Internal transaction delete failed: {}", e))
+            })?;
+
+        Ok(())
+    }
+}
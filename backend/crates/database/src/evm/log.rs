@@ -0,0 +1,127 @@
+use config::EVM_LOGS_TABLE;
+use custom_error::ServiceError;
+use models::evm::{EvmLog, EvmLogFilter};
+
+use super::LogService;
+
+const TOPIC_FIELDS: [&str; 4] = ["topic0", "topic1", "topic2", "topic3"];
+
+impl<'a> LogService<'a> {
+    pub async fn save(&self, log: &EvmLog) -> Result<EvmLog, ServiceError> {
+        let created: EvmLog = self
+            .db
+            .create(EVM_LOGS_TABLE)
+            .content(log.clone())
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Log save failed: {}", e)))?
+            .ok_or_else(|| {
+                ServiceError::DatabaseError("Failed to create log record".to_string())
+            })?;
+
+        Ok(created)
+    }
+
+    /// Canonical logs matching the filter in chain order, like `eth_getLogs`
+    pub async fn get_by_filter(
+        &self,
+        filter: &EvmLogFilter,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<EvmLog>, ServiceError> {
+        let mut conditions = vec!["removed = false".to_string()];
+
+        if !filter.addresses.is_empty() {
+            conditions.push("address IN $addresses".to_string());
+        }
+        for (field, topics) in TOPIC_FIELDS.iter().zip(filter.topics.iter()) {
+            if !topics.is_empty() {
+                conditions.push(format!("{} IN ${}", field, field));
+            }
+        }
+        if filter.from_block.is_some() {
+            conditions.push("block_number >= $from_block".to_string());
+        }
+        if filter.to_block.is_some() {
+            conditions.push("block_number <= $to_block".to_string());
+        }
+
+        let query = format!(
+            "SELECT * FROM {} WHERE {} ORDER BY block_number ASC, log_index ASC LIMIT $limit START $offset",
+            EVM_LOGS_TABLE,
+            conditions.join(" AND ")
+        );
+
+        let mut query_builder = self
+            .db
+            .query(query)
+            .bind((
+                "addresses",
+                filter
+                    .addresses
+                    .iter()
+                    .map(|address| address.to_lowercase())
+                    .collect::<Vec<_>>(),
+            ))
+            .bind(("from_block", filter.from_block))
+            .bind(("to_block", filter.to_block))
+            .bind(("limit", limit))
+            .bind(("offset", offset));
+
+        for (field, topics) in TOPIC_FIELDS.iter().zip(filter.topics.iter()) {
+            query_builder = query_builder.bind((
+                *field,
+                topics
+                    .iter()
+                    .map(|topic| topic.to_lowercase())
+                    .collect::<Vec<_>>(),
+            ));
+        }
+
+        let mut result = query_builder
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Logs query failed: {}", e)))?;
+
+        let logs: Vec<EvmLog> = result
+            .take(0)
+            .map_err(|e| ServiceError::DatabaseError(format!("Logs extraction failed: {}", e)))?;
+
+        Ok(logs)
+    }
+
+    /// Delete the canonical logs of a transaction, logs already marked removed are kept
+    pub async fn delete_by_transaction(&self, transaction_hash: &str) -> Result<(), ServiceError> {
+        let query = format!(
+            "DELETE FROM {} WHERE transaction_hash = $transaction_hash AND removed = false",
+            EVM_LOGS_TABLE
+        );
+        self.db
+            .query(query)
+            .bind(("transaction_hash", transaction_hash.to_lowercase()))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Log delete failed: {}", e)))?
+            .check()
+            .map_err(|e| ServiceError::DatabaseError(format!("Log delete failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Flag the logs of an orphaned block as removed
+    pub async fn mark_removed_by_block_number(
+        &self,
+        block_number: u64,
+    ) -> Result<(), ServiceError> {
+        let query = format!(
+            "UPDATE {} SET removed = true WHERE block_number = $block_number AND removed = false",
+            EVM_LOGS_TABLE
+        );
+        self.db
+            .query(query)
+            .bind(("block_number", block_number))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Log update failed: {}", e)))?
+            .check()
+            .map_err(|e| ServiceError::DatabaseError(format!("Log update failed: {}", e)))?;
+
+        Ok(())
+    }
+}
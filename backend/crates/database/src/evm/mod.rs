@@ -1,6 +1,13 @@
 mod account;
 mod block;
 mod contract;
+mod contract_source;
+mod internal_transaction;
+mod log;
+mod orphaned_block;
+mod proxy_implementation;
+mod token_balance;
+mod token_transfer;
 mod transaction;
 
 use surrealdb::{Surreal, engine::any::Any};
@@ -20,3 +27,31 @@ pub struct AccountService<'a> {
 pub struct ContractService<'a> {
     pub db: &'a Surreal<Any>,
 }
+
+pub struct ContractSourceService<'a> {
+    pub db: &'a Surreal<Any>,
+}
+
+pub struct OrphanedBlockService<'a> {
+    pub db: &'a Surreal<Any>,
+}
+
+pub struct ProxyImplementationService<'a> {
+    pub db: &'a Surreal<Any>,
+}
+
+pub struct TokenTransferService<'a> {
+    pub db: &'a Surreal<Any>,
+}
+
+pub struct TokenBalanceService<'a> {
+    pub db: &'a Surreal<Any>,
+}
+
+pub struct LogService<'a> {
+    pub db: &'a Surreal<Any>,
+}
+
+pub struct InternalTransactionService<'a> {
+    pub db: &'a Surreal<Any>,
+}
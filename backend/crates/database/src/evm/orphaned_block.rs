@@ -0,0 +1,68 @@
+use config::EVM_ORPHANED_BLOCKS_TABLE;
+use custom_error::ServiceError;
+use models::evm::EvmOrphanedBlock;
+
+use super::OrphanedBlockService;
+
+impl<'a> OrphanedBlockService<'a> {
+    pub async fn save(&self, block: &EvmOrphanedBlock) -> Result<EvmOrphanedBlock, ServiceError> {
+        let created: EvmOrphanedBlock = self
+            .db
+            .create(EVM_ORPHANED_BLOCKS_TABLE)
+            .content(block.clone())
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Orphaned block save failed: {}", e)))?
+            .ok_or_else(|| {
+                ServiceError::DatabaseError("Failed to create orphaned block record".to_string())
+            })?;
+
+        Ok(created)
+    }
+
+    pub async fn get_all(
+        &self,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<EvmOrphanedBlock>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} ORDER BY orphaned_at DESC LIMIT $limit START $offset",
+            EVM_ORPHANED_BLOCKS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("limit", limit))
+            .bind(("offset", offset))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        let blocks: Vec<EvmOrphanedBlock> = result
+            .take(0)
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        Ok(blocks)
+    }
+
+    /// Retrieves orphaned blocks recorded at a given height
+    pub async fn get_by_number(
+        &self,
+        block_number: u32,
+    ) -> Result<Vec<EvmOrphanedBlock>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE number = $block_number ORDER BY orphaned_at DESC",
+            EVM_ORPHANED_BLOCKS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("block_number", block_number))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        let blocks: Vec<EvmOrphanedBlock> = result
+            .take(0)
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        Ok(blocks)
+    }
+}
@@ -0,0 +1,95 @@
+use config::EVM_PROXY_IMPLEMENTATIONS_TABLE;
+use custom_error::ServiceError;
+use models::evm::EvmProxyImplementation;
+
+use super::ProxyImplementationService;
+
+// Proxy implementation history, one record per (proxy, block, implementation)
+impl<'a> ProxyImplementationService<'a> {
+    pub async fn save(&self, implementation: &EvmProxyImplementation) -> Result<(), ServiceError> {
+        self.db
+            .query(
+                "UPSERT type::thing($table, [$proxy_address, $block_number, $implementation]) CONTENT $record",
+            )
+            .bind(("table", EVM_PROXY_IMPLEMENTATIONS_TABLE))
+            .bind(("proxy_address", implementation.proxy_address.to_lowercase()))
+            .bind(("block_number", implementation.block_number))
+            .bind(("implementation", implementation.implementation.to_lowercase()))
+            .bind(("record", implementation.clone()))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Proxy implementation save failed: {}", e))
+            })?
+            .check()
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Proxy implementation save failed: {}", e))
+            })?;
+
+        Ok(())
+    }
+
+    /// Implementations of a proxy, most recent first
+    pub async fn get_by_proxy(
+        &self,
+        proxy_address: &str,
+    ) -> Result<Vec<EvmProxyImplementation>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE proxy_address = $proxy_address ORDER BY block_number DESC",
+            EVM_PROXY_IMPLEMENTATIONS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("proxy_address", proxy_address.to_lowercase()))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        let implementations: Vec<EvmProxyImplementation> = result
+            .take(0)
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        Ok(implementations)
+    }
+
+    pub async fn get_by_block_number(
+        &self,
+        block_number: u64,
+    ) -> Result<Vec<EvmProxyImplementation>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE block_number = $block_number",
+            EVM_PROXY_IMPLEMENTATIONS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("block_number", block_number))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        let implementations: Vec<EvmProxyImplementation> = result
+            .take(0)
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        Ok(implementations)
+    }
+
+    pub async fn delete_by_block_number(&self, block_number: u64) -> Result<(), ServiceError> {
+        let query = format!(
+            "DELETE FROM {} WHERE block_number = $block_number",
+            EVM_PROXY_IMPLEMENTATIONS_TABLE
+        );
+        self.db
+            .query(query)
+            .bind(("block_number", block_number))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Proxy implementation delete failed: {}", e))
+            })?
+            .check()
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Proxy implementation delete failed: {}", e))
+            })?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+use config::TOKEN_BALANCES_TABLE;
+use custom_error::ServiceError;
+use models::evm::TokenBalance;
+
+use super::TokenBalanceService;
+
+// Token balance service implementation, one record per (token, holder, token id)
+impl<'a> TokenBalanceService<'a> {
+    pub async fn get(
+        &self,
+        token_address: &str,
+        holder: &str,
+        token_id: Option<&str>,
+    ) -> Result<Option<TokenBalance>, ServiceError> {
+        let mut result = self
+            .db
+            .query("SELECT * FROM type::thing($table, [$token_address, $holder, $token_id])")
+            .bind(("table", TOKEN_BALANCES_TABLE))
+            .bind(("token_address", token_address.to_lowercase()))
+            .bind(("holder", holder.to_lowercase()))
+            .bind(("token_id", token_id.unwrap_or_default().to_string()))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Token balance query failed: {}", e))
+            })?;
+
+        let balance: Option<TokenBalance> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Token balance extraction failed: {}", e))
+        })?;
+
+        Ok(balance)
+    }
+
+    pub async fn save(&self, balance: &TokenBalance) -> Result<(), ServiceError> {
+        self.db
+            .query(
+                "UPSERT type::thing($table, [$token_address, $holder, $token_id]) CONTENT $balance",
+            )
+            .bind(("table", TOKEN_BALANCES_TABLE))
+            .bind(("token_address", balance.token_address.clone()))
+            .bind(("holder", balance.holder.clone()))
+            .bind(("token_id", balance.token_id.clone().unwrap_or_default()))
+            .bind(("balance", balance.clone()))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Token balance save failed: {}", e)))?
+            .check()
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Token balance save failed: {}", e))
+            })?;
+
+        Ok(())
+    }
+
+    /// Get paginated non-zero holders of a token, largest balance first
+    pub async fn get_holders(
+        &self,
+        token_address: &str,
+        token_id: Option<&str>,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<TokenBalance>, ServiceError> {
+        let token_id_condition = if token_id.is_some() {
+            " AND token_id = $token_id"
+        } else {
+            ""
+        };
+        // Balances are decimal strings without leading zeros, so a longer one is larger and
+        // equal lengths compare as text, exactly for any size
+        let query = format!(
+            "SELECT *, string::len(balance) AS balance_digits FROM {} WHERE token_address = $token_address{} AND balance != '0' AND !string::starts_with(balance, '-') ORDER BY balance_digits DESC, balance DESC LIMIT $limit START $offset",
+            TOKEN_BALANCES_TABLE, token_id_condition
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("token_address", token_address.to_lowercase()))
+            .bind(("token_id", token_id.map(str::to_string)))
+            .bind(("limit", limit))
+            .bind(("offset", offset))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        let balances: Vec<TokenBalance> = result
+            .take(0)
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        Ok(balances)
+    }
+
+    /// Get paginated non-zero token balances of a holder
+    pub async fn get_by_holder(
+        &self,
+        holder: &str,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<TokenBalance>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE holder = $holder AND balance != '0' ORDER BY token_address ASC LIMIT $limit START $offset",
+            TOKEN_BALANCES_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("holder", holder.to_lowercase()))
+            .bind(("limit", limit))
+            .bind(("offset", offset))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        let balances: Vec<TokenBalance> = result
+            .take(0)
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        Ok(balances)
+    }
+
+    /// Balances that were never reconciled come first, then the oldest reconciled ones
+    pub async fn get_least_recently_reconciled(
+        &self,
+        limit: u32,
+    ) -> Result<Vec<TokenBalance>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} ORDER BY reconciled_at ASC LIMIT $limit",
+            TOKEN_BALANCES_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("limit", limit))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        let balances: Vec<TokenBalance> = result
+            .take(0)
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        Ok(balances)
+    }
+}
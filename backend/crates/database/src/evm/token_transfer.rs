@@ -0,0 +1,244 @@
+use config::TOKEN_TRANSFERS_TABLE;
+use custom_error::ServiceError;
+use models::evm::{TokenTransfer, TokenTransferFilter};
+
+use super::TokenTransferService;
+
+impl<'a> TokenTransferService<'a> {
+    pub async fn save(&self, transfer: &TokenTransfer) -> Result<TokenTransfer, ServiceError> {
+        let created: TokenTransfer = self
+            .db
+            .create(TOKEN_TRANSFERS_TABLE)
+            .content(transfer.clone())
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Token transfer save failed: {}", e)))?
+            .ok_or_else(|| {
+                ServiceError::DatabaseError("Failed to create token transfer record".to_string())
+            })?;
+
+        Ok(created)
+    }
+
+    /// Get paginated transfers of a token contract
+    pub async fn get_by_token(
+        &self,
+        token_address: &str,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<TokenTransfer>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE token_address = $token_address ORDER BY block_number DESC, log_index DESC, batch_index ASC LIMIT $limit START $offset",
+            TOKEN_TRANSFERS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("token_address", token_address.to_lowercase()))
+            .bind(("limit", limit))
+            .bind(("offset", offset))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        let transfers: Vec<TokenTransfer> = result
+            .take(0)
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        Ok(transfers)
+    }
+
+    /// Get paginated transfers sent or received by an address
+    pub async fn get_by_address(
+        &self,
+        address: &str,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<TokenTransfer>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE from = $address OR to = $address ORDER BY block_number DESC, log_index DESC, batch_index ASC LIMIT $limit START $offset",
+            TOKEN_TRANSFERS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("address", address.to_lowercase()))
+            .bind(("limit", limit))
+            .bind(("offset", offset))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        let transfers: Vec<TokenTransfer> = result
+            .take(0)
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        Ok(transfers)
+    }
+
+    /// Transfers matching the filter, in chain order or newest first
+    pub async fn get_by_filter(
+        &self,
+        filter: &TokenTransferFilter,
+        ascending: bool,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<TokenTransfer>, ServiceError> {
+        let mut conditions = vec!["true"];
+        if filter.address.is_some() {
+            conditions.push("(from = $address OR to = $address)");
+        }
+        if filter.token.is_some() {
+            conditions.push("token_address = $token_address");
+        }
+        if !filter.standards.is_empty() {
+            conditions.push("standard IN $standards");
+        }
+        if filter.from_block.is_some() {
+            conditions.push("block_number >= $from_block");
+        }
+        if filter.to_block.is_some() {
+            conditions.push("block_number <= $to_block");
+        }
+
+        let order = if ascending { "ASC" } else { "DESC" };
+        let query = format!(
+            "SELECT * FROM {} WHERE {} ORDER BY block_number {}, log_index {}, batch_index ASC LIMIT $limit START $offset",
+            TOKEN_TRANSFERS_TABLE,
+            conditions.join(" AND "),
+            order,
+            order
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("address", filter.address.as_ref().map(|a| a.to_lowercase())))
+            .bind((
+                "token_address",
+                filter.token.as_ref().map(|t| t.to_lowercase()),
+            ))
+            .bind(("standards", filter.standards.clone()))
+            .bind(("from_block", filter.from_block))
+            .bind(("to_block", filter.to_block))
+            .bind(("limit", limit))
+            .bind(("offset", offset))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Token transfers query failed: {}", e))
+            })?;
+
+        let transfers: Vec<TokenTransfer> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Token transfers extraction failed: {}", e))
+        })?;
+
+        Ok(transfers)
+    }
+
+    /// Get all transfers emitted by a transaction, in log order
+    pub async fn get_by_transaction(
+        &self,
+        transaction_hash: &str,
+    ) -> Result<Vec<TokenTransfer>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE transaction_hash = $transaction_hash ORDER BY log_index ASC, batch_index ASC",
+            TOKEN_TRANSFERS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("transaction_hash", transaction_hash.to_lowercase()))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        let transfers: Vec<TokenTransfer> = result
+            .take(0)
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        Ok(transfers)
+    }
+
+    /// Get all transfers indexed for a block
+    pub async fn get_by_block_number(
+        &self,
+        block_number: u64,
+    ) -> Result<Vec<TokenTransfer>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE block_number = $block_number",
+            TOKEN_TRANSFERS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("block_number", block_number))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        let transfers: Vec<TokenTransfer> = result
+            .take(0)
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        Ok(transfers)
+    }
+
+    /// Transfers of the blocks `from..=to`, in block and log order
+    pub async fn get_by_block_range(
+        &self,
+        from: u64,
+        to: u64,
+    ) -> Result<Vec<TokenTransfer>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE block_number >= $from AND block_number <= $to ORDER BY block_number ASC, log_index ASC, batch_index ASC",
+            TOKEN_TRANSFERS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("from", from))
+            .bind(("to", to))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        let transfers: Vec<TokenTransfer> = result
+            .take(0)
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        Ok(transfers)
+    }
+
+    pub async fn delete_by_transaction(&self, transaction_hash: &str) -> Result<(), ServiceError> {
+        let query = format!(
+            "DELETE FROM {} WHERE transaction_hash = $transaction_hash",
+            TOKEN_TRANSFERS_TABLE
+        );
+        self.db
+            .query(query)
+            .bind(("transaction_hash", transaction_hash.to_lowercase()))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Token transfer delete failed: {}", e))
+            })?
+            .check()
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Token transfer delete failed: {}", e))
+            })?;
+
+        Ok(())
+    }
+
+    pub async fn delete_by_block_number(&self, block_number: u64) -> Result<(), ServiceError> {
+        let query = format!(
+            "DELETE FROM {} WHERE block_number = $block_number",
+            TOKEN_TRANSFERS_TABLE
+        );
+        self.db
+            .query(query)
+            .bind(("block_number", block_number))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Token transfer delete failed: {}", e))
+            })?
+            .check()
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Token transfer delete failed: {}", e))
+            })?;
+
+        Ok(())
+    }
+}
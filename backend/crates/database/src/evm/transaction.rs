@@ -1,6 +1,9 @@
 use config::EVM_TXS_TABLE;
 use custom_error::ServiceError;
-use models::evm::EvmTransaction;
+use models::{
+    activity::Cursor,
+    evm::{DecodedCall, EvmAddressTxFilter, EvmTransaction, TransactionDirection},
+};
 
 use super::TransactionService;
 
@@ -100,6 +103,182 @@ impl<'a> TransactionService<'a> {
         Ok(transactions.into_iter().next())
     }
 
+    /// Transactions sent or received by an address, newest first
+    pub async fn get_by_address(
+        &self,
+        address: &str,
+        filter: &EvmAddressTxFilter,
+        cursor: Option<&Cursor<String>>,
+        limit: u32,
+    ) -> Result<Vec<EvmTransaction>, ServiceError> {
+        let mut conditions = vec![
+            match filter.direction {
+                TransactionDirection::All => "(from = $address OR to = $address)",
+                TransactionDirection::Sent => "from = $address",
+                TransactionDirection::Received => "to = $address",
+            }
+            .to_string(),
+        ];
+
+        if filter.from_timestamp.is_some() {
+            conditions.push("timestamp >= $from_timestamp".to_string());
+        }
+        if filter.to_timestamp.is_some() {
+            conditions.push("timestamp <= $to_timestamp".to_string());
+        }
+
+        // A 4-byte selector matches the recorded signature, anything else the function name
+        let selector = filter.method.as_deref().and_then(|method| {
+            let selector = method.strip_prefix("0x").unwrap_or(method);
+            (selector.len() == 8 && selector.chars().all(|c| c.is_ascii_hexdigit()))
+                .then(|| selector.to_lowercase())
+        });
+        if selector.is_some() {
+            conditions
+                .push("transaction_method.ContractCall.function_signature = $selector".to_string());
+        } else if filter.method.is_some() {
+            conditions.push(
+                "(decoded_input.name = $method OR transaction_method.ContractCall.function_name = $method)"
+                    .to_string(),
+            );
+        }
+
+        if let Some(cursor) = cursor {
+            conditions.push(match cursor.key {
+                Some(_) => "(timestamp < $cursor_timestamp OR (timestamp = $cursor_timestamp AND hash < $cursor_hash))".to_string(),
+                None => "timestamp < $cursor_timestamp".to_string(),
+            });
+        }
+
+        let query = format!(
+            "SELECT * FROM {} WHERE {} ORDER BY timestamp DESC, hash DESC LIMIT $limit",
+            EVM_TXS_TABLE,
+            conditions.join(" AND ")
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("address", address.to_lowercase()))
+            .bind(("from_timestamp", filter.from_timestamp))
+            .bind(("to_timestamp", filter.to_timestamp))
+            .bind(("selector", selector))
+            .bind(("method", filter.method.clone()))
+            .bind(("cursor_timestamp", cursor.map(|cursor| cursor.timestamp)))
+            .bind(("cursor_hash", cursor.and_then(|cursor| cursor.key.clone())))
+            .bind(("limit", limit))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Transactions by address query failed: {}", e))
+            })?;
+
+        let transactions: Vec<EvmTransaction> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Transactions by address extraction failed: {}", e))
+        })?;
+
+        Ok(transactions)
+    }
+
+    /// Transactions sent or received by an address within a block range, in chain order
+    /// or newest first
+    pub async fn get_by_address_in_blocks(
+        &self,
+        address: &str,
+        from_block: Option<u64>,
+        to_block: Option<u64>,
+        ascending: bool,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<EvmTransaction>, ServiceError> {
+        let mut conditions = vec!["(from = $address OR to = $address)"];
+        if from_block.is_some() {
+            conditions.push("block_number >= $from_block");
+        }
+        if to_block.is_some() {
+            conditions.push("block_number <= $to_block");
+        }
+
+        let order = if ascending { "ASC" } else { "DESC" };
+        let query = format!(
+            "SELECT * FROM {} WHERE {} ORDER BY block_number {}, hash {} LIMIT $limit START $offset",
+            EVM_TXS_TABLE,
+            conditions.join(" AND "),
+            order,
+            order
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("address", address.to_lowercase()))
+            .bind(("from_block", from_block))
+            .bind(("to_block", to_block))
+            .bind(("limit", limit))
+            .bind(("offset", offset))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Transactions by blocks query failed: {}", e))
+            })?;
+
+        let transactions: Vec<EvmTransaction> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Transactions by blocks extraction failed: {}", e))
+        })?;
+
+        Ok(transactions)
+    }
+
+    /// Transactions sent to an address without decoded input, in hash order after `after`
+    pub async fn get_undecoded_to(
+        &self,
+        address: &str,
+        after: Option<&str>,
+        limit: u32,
+    ) -> Result<Vec<EvmTransaction>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE to = $address AND decoded_input = NONE AND ($after = NONE OR hash > $after) ORDER BY hash ASC LIMIT $limit",
+            EVM_TXS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("address", address.to_lowercase()))
+            .bind(("after", after.map(str::to_string)))
+            .bind(("limit", limit))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Undecoded transactions query failed: {}", e))
+            })?;
+
+        let transactions: Vec<EvmTransaction> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Undecoded transactions extraction failed: {}", e))
+        })?;
+
+        Ok(transactions)
+    }
+
+    pub async fn set_decoded_input(
+        &self,
+        hash: &str,
+        decoded_input: &DecodedCall,
+    ) -> Result<(), ServiceError> {
+        let query = format!(
+            "UPDATE {} SET decoded_input = $decoded_input WHERE hash = $hash",
+            EVM_TXS_TABLE
+        );
+        self.db
+            .query(query)
+            .bind(("hash", hash.to_string()))
+            .bind(("decoded_input", decoded_input.clone()))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Decoded input update failed: {}", e))
+            })?
+            .check()
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Decoded input update failed: {}", e))
+            })?;
+
+        Ok(())
+    }
+
     /// Check if a transaction exists by hash
     pub async fn is_exist_by_hash(&self, hash: &str) -> Result<bool, ServiceError> {
         let query = format!(
@@ -119,4 +298,115 @@ impl<'a> TransactionService<'a> {
 
         Ok(count.unwrap_or(0) > 0)
     }
+
+    /// Delete all transactions included in a specific block number
+    pub async fn delete_by_block_number(&self, block_number: u32) -> Result<(), ServiceError> {
+        let query = format!(
+            "DELETE FROM {} WHERE block_number = $block_number",
+            EVM_TXS_TABLE
+        );
+        self.db
+            .query(query)
+            .bind(("block_number", block_number))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Transaction delete failed: {}", e)))?
+            .check()
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Transaction delete failed: {}", e))
+            })?;
+
+        Ok(())
+    }
+
+    pub async fn get_by_hashes(
+        &self,
+        hashes: &[String],
+    ) -> Result<Vec<EvmTransaction>, ServiceError> {
+        if hashes.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let query = format!("SELECT * FROM {} WHERE hash IN $hashes", EVM_TXS_TABLE);
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("hashes", hashes.to_vec()))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Transactions query failed: {}", e))
+            })?;
+
+        let transactions: Vec<EvmTransaction> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Transactions extraction failed: {}", e))
+        })?;
+
+        Ok(transactions)
+    }
+
+    /// Transactions of a block ordered by hash, those after the `after` hash
+    pub async fn get_by_block_page(
+        &self,
+        block_number: u32,
+        after: Option<&str>,
+        limit: u32,
+    ) -> Result<Vec<EvmTransaction>, ServiceError> {
+        let after_condition = if after.is_some() {
+            " AND hash > $after"
+        } else {
+            ""
+        };
+        let query = format!(
+            "SELECT * FROM {} WHERE block_number = $block_number{} ORDER BY hash ASC LIMIT $limit",
+            EVM_TXS_TABLE, after_condition
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("block_number", block_number))
+            .bind(("after", after.map(str::to_string)))
+            .bind(("limit", limit))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Block transactions query failed: {}", e))
+            })?;
+
+        let transactions: Vec<EvmTransaction> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Block transactions extraction failed: {}", e))
+        })?;
+
+        Ok(transactions)
+    }
+
+    /// Transactions of the blocks `from..=to`, in block order and by hash inside a block
+    pub async fn get_by_block_range(
+        &self,
+        from: u64,
+        to: u64,
+    ) -> Result<Vec<EvmTransaction>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE block_number >= $from AND block_number <= $to ORDER BY block_number ASC, hash ASC",
+            EVM_TXS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("from", from))
+            .bind(("to", to))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!(
+                    "Transactions by block range query failed: {}",
+                    e
+                ))
+            })?;
+
+        let transactions: Vec<EvmTransaction> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!(
+                "Transactions by block range extraction failed: {}",
+                e
+            ))
+        })?;
+
+        Ok(transactions)
+    }
 }
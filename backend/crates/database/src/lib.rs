@@ -1,11 +1,31 @@
+pub mod account_link;
+pub mod activity;
 pub mod evm;
+pub mod live;
+pub mod schema;
 pub mod substrate;
+pub mod sync_state;
+pub mod webhook;
 
 use custom_error::ServiceError;
-use evm::{AccountService, ContractService, EvmBlockService, TransactionService};
+use evm::{
+    AccountService, ContractService, ContractSourceService, EvmBlockService,
+    InternalTransactionService, LogService, OrphanedBlockService, ProxyImplementationService,
+    TokenBalanceService, TokenTransferService, TransactionService,
+};
 use surrealdb::{Surreal, engine::any, opt::auth::Root};
 
-use crate::substrate::{SubstrateBlockService, SubstrateEventService, SubstrateExtrinsicService};
+use crate::{
+    account_link::AccountLinkService,
+    activity::ActivityService,
+    live::LiveService,
+    substrate::{
+        IdentityService, StakingService, SubstrateBlockService, SubstrateEventService,
+        SubstrateExtrinsicService, SubstrateTransferService, ValidatorSnapshotService,
+    },
+    sync_state::SyncStateService,
+    webhook::{WebhookDeliveryService, WebhookService},
+};
 
 #[derive(Clone)]
 pub struct DatabaseService {
@@ -34,6 +54,9 @@ impl DatabaseService {
             .await
             .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
 
+        schema::define_lowercase_fields(&db).await?;
+        schema::define_indexes(&db).await?;
+
         Ok(Self { db })
     }
 
@@ -49,10 +72,42 @@ impl DatabaseService {
         AccountService { db: &self.db }
     }
 
+    pub fn account_links(&self) -> AccountLinkService {
+        AccountLinkService { db: &self.db }
+    }
+
     pub fn contracts(&self) -> ContractService {
         ContractService { db: &self.db }
     }
 
+    pub fn contract_sources(&self) -> ContractSourceService {
+        ContractSourceService { db: &self.db }
+    }
+
+    pub fn orphaned_blocks(&self) -> OrphanedBlockService {
+        OrphanedBlockService { db: &self.db }
+    }
+
+    pub fn proxy_implementations(&self) -> ProxyImplementationService {
+        ProxyImplementationService { db: &self.db }
+    }
+
+    pub fn internal_transactions(&self) -> InternalTransactionService {
+        InternalTransactionService { db: &self.db }
+    }
+
+    pub fn evm_logs(&self) -> LogService {
+        LogService { db: &self.db }
+    }
+
+    pub fn token_balances(&self) -> TokenBalanceService {
+        TokenBalanceService { db: &self.db }
+    }
+
+    pub fn token_transfers(&self) -> TokenTransferService {
+        TokenTransferService { db: &self.db }
+    }
+
     pub fn substrate_blocks(&self) -> SubstrateBlockService {
         SubstrateBlockService { db: &self.db }
     }
@@ -64,4 +119,40 @@ impl DatabaseService {
     pub fn substrate_events(&self) -> SubstrateEventService {
         SubstrateEventService { db: &self.db }
     }
+
+    pub fn substrate_transfers(&self) -> SubstrateTransferService {
+        SubstrateTransferService { db: &self.db }
+    }
+
+    pub fn staking(&self) -> StakingService {
+        StakingService { db: &self.db }
+    }
+
+    pub fn validator_snapshots(&self) -> ValidatorSnapshotService {
+        ValidatorSnapshotService { db: &self.db }
+    }
+
+    pub fn identities(&self) -> IdentityService {
+        IdentityService { db: &self.db }
+    }
+
+    pub fn activity(&self) -> ActivityService {
+        ActivityService { db: &self.db }
+    }
+
+    pub fn live(&self) -> LiveService {
+        LiveService { db: &self.db }
+    }
+
+    pub fn sync_state(&self) -> SyncStateService {
+        SyncStateService { db: &self.db }
+    }
+
+    pub fn webhooks(&self) -> WebhookService {
+        WebhookService { db: &self.db }
+    }
+
+    pub fn webhook_deliveries(&self) -> WebhookDeliveryService {
+        WebhookDeliveryService { db: &self.db }
+    }
 }
@@ -0,0 +1,115 @@
+use config::SYNC_STATE_TABLE;
+use custom_error::ServiceError;
+use futures::{StreamExt, future::ready, stream::BoxStream};
+use models::{
+    SyncState,
+    live::{ChainEvent, Feed, PushedEvent, sequence},
+};
+use serde::de::DeserializeOwned;
+use surrealdb::{Action, Surreal, engine::any::Any};
+
+use crate::{
+    evm::{EvmBlockService, TokenTransferService, TransactionService},
+    substrate::{SubstrateBlockService, SubstrateEventService, SubstrateExtrinsicService},
+};
+
+pub struct LiveService<'a> {
+    pub db: &'a Surreal<Any>,
+}
+
+type LiveStream<T> = BoxStream<'static, Result<T, ServiceError>>;
+
+// Ordered reads of the indexed records and live queries over the indexer checkpoints
+impl<'a> LiveService<'a> {
+    /// Records of a feed in the blocks `from..=to`, in the order they are pushed: each
+    /// block before its transactions and token transfers, or its extrinsics and events.
+    /// Reading the same blocks again yields the same records at the same cursors
+    pub async fn records(
+        &self,
+        feed: Feed,
+        from: u64,
+        to: u64,
+    ) -> Result<Vec<PushedEvent>, ServiceError> {
+        let (from_block, to_block) = (from as u32, to.min(u32::MAX as u64) as u32);
+        let mut records = Vec::new();
+
+        match feed {
+            Feed::Evm => {
+                let blocks = EvmBlockService { db: self.db }
+                    .get_range(from_block, to_block)
+                    .await?;
+                let transactions = TransactionService { db: self.db }
+                    .get_by_block_range(from, to)
+                    .await?;
+                let transfers = TokenTransferService { db: self.db }
+                    .get_by_block_range(from, to)
+                    .await?;
+                records.extend(blocks.into_iter().map(ChainEvent::EvmBlock));
+                records.extend(transactions.into_iter().map(ChainEvent::EvmTransaction));
+                records.extend(transfers.into_iter().map(ChainEvent::TokenTransfer));
+            }
+            Feed::Substrate => {
+                let blocks = SubstrateBlockService { db: self.db }
+                    .get_range(from_block, to_block)
+                    .await?;
+                let extrinsics = SubstrateExtrinsicService { db: self.db }
+                    .get_by_block_range(from_block, to_block)
+                    .await?;
+                let events = SubstrateEventService { db: self.db }
+                    .get_by_block_range(from_block, to_block, &[])
+                    .await?;
+                records.extend(blocks.into_iter().map(ChainEvent::SubstrateBlock));
+                records.extend(extrinsics.into_iter().map(ChainEvent::SubstrateExtrinsic));
+                records.extend(events.into_iter().map(ChainEvent::SubstrateEvent));
+            }
+            Feed::FinalizedEvm => {
+                let blocks = EvmBlockService { db: self.db }
+                    .get_range(from_block, to_block)
+                    .await?;
+                records.extend(blocks.into_iter().map(ChainEvent::FinalizedEvmBlock));
+            }
+            Feed::FinalizedSubstrate => {
+                let blocks = SubstrateBlockService { db: self.db }
+                    .get_range(from_block, to_block)
+                    .await?;
+                records.extend(blocks.into_iter().map(ChainEvent::FinalizedSubstrateBlock));
+            }
+        }
+
+        Ok(sequence(feed, records))
+    }
+
+    /// Indexer checkpoints each time one is created or moved
+    pub async fn sync_states(&self) -> Result<LiveStream<SyncState>, ServiceError> {
+        self.notifications(SYNC_STATE_TABLE, |action| {
+            matches!(action, Action::Create | Action::Update)
+        })
+        .await
+    }
+
+    async fn notifications<T>(
+        &self,
+        table: &'static str,
+        keep: fn(&Action) -> bool,
+    ) -> Result<LiveStream<T>, ServiceError>
+    where
+        T: DeserializeOwned + Unpin + Send + 'static,
+    {
+        let notifications = self.db.select::<Vec<T>>(table).live().await.map_err(|e| {
+            ServiceError::DatabaseError(format!("Live query on {} failed: {}", table, e))
+        })?;
+
+        Ok(notifications
+            .filter_map(move |notification| {
+                ready(match notification {
+                    Ok(notification) if keep(&notification.action) => Some(Ok(notification.data)),
+                    Ok(_) => None,
+                    Err(e) => Some(Err(ServiceError::DatabaseError(format!(
+                        "Live notification on {} failed: {}",
+                        table, e
+                    )))),
+                })
+            })
+            .boxed())
+    }
+}
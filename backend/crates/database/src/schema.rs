@@ -0,0 +1,186 @@
+use config::{
+    ACCOUNT_LINKS_TABLE, ERA_VALIDATOR_POINTS_TABLE, EVM_BLOCK_TABLE, EVM_CONTRACT_SOURCES_TABLE,
+    EVM_CONTRACTS_TABLE, EVM_TXS_TABLE, IDENTITIES_TABLE, INDEXED_BLOCKS_TABLE,
+    STAKING_LEDGERS_TABLE, STAKING_REWARDS_TABLE, STAKING_SLASHES_TABLE, SUBSTRATE_EVENTS_TABLE,
+    SUBSTRATE_EXTRINSICS_TABLE, SUBSTRATE_TRANSFERS_TABLE, VALIDATOR_SNAPSHOTS_TABLE,
+    WEBHOOK_DEAD_LETTERS_TABLE, WEBHOOK_DELIVERIES_TABLE, WEBHOOKS_TABLE,
+};
+use custom_error::ServiceError;
+use surrealdb::{Surreal, engine::any::Any};
+
+/// Indexes behind the per-address lookups as (name, table, fields)
+const INDEXES: [(&str, &str, &str); 25] = [
+    ("evm_transaction_from", EVM_TXS_TABLE, "from, timestamp"),
+    ("evm_transaction_to", EVM_TXS_TABLE, "to, timestamp"),
+    ("evm_transaction_timestamp", EVM_TXS_TABLE, "timestamp"),
+    ("evm_block_timestamp", EVM_BLOCK_TABLE, "timestamp"),
+    (
+        "indexed_block_number",
+        INDEXED_BLOCKS_TABLE,
+        "indexer, number",
+    ),
+    (
+        "substrate_extrinsic_signer",
+        SUBSTRATE_EXTRINSICS_TABLE,
+        "signer, timestamp",
+    ),
+    ("account_link_evm", ACCOUNT_LINKS_TABLE, "evm_address"),
+    (
+        "account_link_substrate",
+        ACCOUNT_LINKS_TABLE,
+        "substrate_address",
+    ),
+    (
+        "substrate_event_accounts",
+        SUBSTRATE_EVENTS_TABLE,
+        "accounts",
+    ),
+    (
+        "substrate_event_module",
+        SUBSTRATE_EVENTS_TABLE,
+        "module, event, timestamp",
+    ),
+    ("staking_reward_stash", STAKING_REWARDS_TABLE, "stash, era"),
+    ("staking_slash_staker", STAKING_SLASHES_TABLE, "staker"),
+    (
+        "era_validator_points_validator",
+        ERA_VALIDATOR_POINTS_TABLE,
+        "validator, era",
+    ),
+    (
+        "validator_snapshot_account",
+        VALIDATOR_SNAPSHOTS_TABLE,
+        "account_id, era",
+    ),
+    (
+        "staking_ledger_nominations",
+        STAKING_LEDGERS_TABLE,
+        "nominations",
+    ),
+    (
+        "substrate_transfer_from",
+        SUBSTRATE_TRANSFERS_TABLE,
+        "from, block_number",
+    ),
+    (
+        "substrate_transfer_to",
+        SUBSTRATE_TRANSFERS_TABLE,
+        "to, block_number",
+    ),
+    ("webhook_owner", WEBHOOKS_TABLE, "owner, created_at"),
+    (
+        "webhook_delivery_webhook",
+        WEBHOOK_DELIVERIES_TABLE,
+        "webhook_id, created_at",
+    ),
+    (
+        "webhook_delivery_due",
+        WEBHOOK_DELIVERIES_TABLE,
+        "status, next_attempt_at",
+    ),
+    (
+        "webhook_dead_letter_webhook",
+        WEBHOOK_DEAD_LETTERS_TABLE,
+        "webhook_id, created_at",
+    ),
+    ("evm_contract_name_lower", EVM_CONTRACTS_TABLE, "name_lower"),
+    (
+        "evm_contract_symbol_lower",
+        EVM_CONTRACTS_TABLE,
+        "symbol_lower",
+    ),
+    (
+        "evm_contract_source_name_lower",
+        EVM_CONTRACT_SOURCES_TABLE,
+        "contract_name_lower",
+    ),
+    (
+        "identity_display_name_lower",
+        IDENTITIES_TABLE,
+        "display_name_lower",
+    ),
+];
+
+/// Lowercase copies of the searchable names as (table, field, source field), kept up to date
+/// on every write so name search is a range over their index
+const LOWERCASE_FIELDS: [(&str, &str, &str); 4] = [
+    (EVM_CONTRACTS_TABLE, "name_lower", "name"),
+    (EVM_CONTRACTS_TABLE, "symbol_lower", "symbol"),
+    (
+        EVM_CONTRACT_SOURCES_TABLE,
+        "contract_name_lower",
+        "contract_name",
+    ),
+    (IDENTITIES_TABLE, "display_name_lower", "display_name"),
+];
+
+/// Defines the lowercase name fields and fills them on records written before they existed
+pub async fn define_lowercase_fields(db: &Surreal<Any>) -> Result<(), ServiceError> {
+    let statements: Vec<String> = LOWERCASE_FIELDS
+        .iter()
+        .map(|(table, field, source)| {
+            format!(
+                "DEFINE FIELD IF NOT EXISTS {1} ON TABLE {0} VALUE string::lowercase({2} ?? '');\n\
+                 UPDATE {0} SET {1} = string::lowercase({2} ?? '') WHERE {1} = NONE;",
+                table, field, source
+            )
+        })
+        .collect();
+
+    db.query(statements.join("\n"))
+        .await
+        .map_err(|e| {
+            ServiceError::DatabaseError(format!("Lowercase field definition failed: {}", e))
+        })?
+        .check()
+        .map_err(|e| {
+            ServiceError::DatabaseError(format!("Lowercase field definition failed: {}", e))
+        })?;
+
+    Ok(())
+}
+
+/// Lower bound and exclusive upper bound of the lowercase values starting with `prefix`
+pub(crate) fn prefix_range(prefix: &str) -> (String, String) {
+    let prefix = prefix.to_lowercase();
+    let end = format!("{}{}", prefix, char::MAX);
+    (prefix, end)
+}
+
+/// Defines the indexes that are missing, existing ones are left untouched
+pub async fn define_indexes(db: &Surreal<Any>) -> Result<(), ServiceError> {
+    let statements: Vec<String> = INDEXES
+        .iter()
+        .map(|(name, table, fields)| {
+            format!(
+                "DEFINE INDEX IF NOT EXISTS {} ON TABLE {} FIELDS {};",
+                name, table, fields
+            )
+        })
+        .collect();
+
+    db.query(statements.join("\n"))
+        .await
+        .map_err(|e| ServiceError::DatabaseError(format!("Index definition failed: {}", e)))?
+        .check()
+        .map_err(|e| ServiceError::DatabaseError(format!("Index definition failed: {}", e)))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefix_range_bounds_every_completion() {
+        let (start, end) = prefix_range("DOT");
+        assert_eq!(start, "dot");
+        for value in ["dot", "dot2", "dotsama", "dot\u{ffff}"] {
+            assert!(start.as_str() <= value && value < end.as_str());
+        }
+        for value in ["do", "dos", "dou", "eth"] {
+            assert!(!(start.as_str() <= value && value < end.as_str()));
+        }
+    }
+}
@@ -129,4 +129,86 @@ impl<'a> SubstrateBlockService<'a> {
 
         Ok(block)
     }
+
+    /// Up to `limit` blocks below `before`, or the latest ones, highest first
+    pub async fn get_before(
+        &self,
+        before: Option<u32>,
+        limit: u32,
+    ) -> Result<Vec<SubstrateBlock>, ServiceError> {
+        let condition = if before.is_some() {
+            "WHERE number < $before"
+        } else {
+            ""
+        };
+        let query = format!(
+            "SELECT * FROM {} {} ORDER BY number DESC LIMIT $limit",
+            SUBSTRATE_BLOCKS_TABLE, condition
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("before", before))
+            .bind(("limit", limit))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Block page query failed: {}", e)))?;
+
+        let blocks: Vec<SubstrateBlock> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Block page extraction failed: {}", e))
+        })?;
+
+        Ok(blocks)
+    }
+
+    /// Stored blocks with numbers in `from..=to`, lowest first
+    pub async fn get_range(&self, from: u32, to: u32) -> Result<Vec<SubstrateBlock>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE number >= $from AND number <= $to ORDER BY number ASC",
+            SUBSTRATE_BLOCKS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("from", from))
+            .bind(("to", to))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Substrate block range query failed: {}", e))
+            })?;
+
+        let blocks: Vec<SubstrateBlock> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Substrate block range extraction failed: {}", e))
+        })?;
+
+        Ok(blocks)
+    }
+
+    /// Stored blocks among `numbers`, in no particular order
+    pub async fn get_by_numbers(
+        &self,
+        numbers: &[u32],
+    ) -> Result<Vec<SubstrateBlock>, ServiceError> {
+        if numbers.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let query = format!(
+            "SELECT * FROM {} WHERE number IN $numbers",
+            SUBSTRATE_BLOCKS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("numbers", numbers.to_vec()))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Substrate blocks query failed: {}", e))
+            })?;
+
+        let blocks: Vec<SubstrateBlock> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Substrate blocks extraction failed: {}", e))
+        })?;
+
+        Ok(blocks)
+    }
 }
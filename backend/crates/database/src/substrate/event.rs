@@ -1,6 +1,9 @@
 use config::SUBSTRATE_EVENTS_TABLE;
 use custom_error::ServiceError;
-use models::substrate::SubstrateEvent;
+use models::{
+    activity::{Cursor, SubstrateKey},
+    substrate::{EventFieldFilter, SubstrateAccountFilter, SubstrateEvent},
+};
 
 use super::SubstrateEventService;
 
@@ -21,6 +24,7 @@ impl<'a> SubstrateEventService<'a> {
         Ok(created)
     }
 
+    /// Saves the events of a block, stopping at the first one that fails
     pub async fn save_batch(
         &self,
         events: &[SubstrateEvent],
@@ -28,21 +32,80 @@ impl<'a> SubstrateEventService<'a> {
         let mut saved_events = Vec::new();
 
         for event in events {
-            match self.save(event).await {
-                Ok(saved) => saved_events.push(saved),
-                Err(e) => {
-                    println!(
-                        "❌ Failed to save event {} in block {}: {}",
-                        event.event_index, event.block_number, e
-                    );
-                    // Continue with other events
-                }
-            }
+            saved_events.push(self.save(event).await?);
         }
 
         Ok(saved_events)
     }
 
+    pub async fn delete_by_block_number(&self, block_number: u32) -> Result<(), ServiceError> {
+        let query = format!(
+            "DELETE FROM {} WHERE block_number = $block_number",
+            SUBSTRATE_EVENTS_TABLE
+        );
+        self.db
+            .query(query)
+            .bind(("block_number", block_number))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Event delete failed: {}", e)))?
+            .check()
+            .map_err(|e| ServiceError::DatabaseError(format!("Event delete failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Events indexed before their mentioned accounts were recorded
+    pub async fn get_without_accounts(
+        &self,
+        limit: u32,
+    ) -> Result<Vec<SubstrateEvent>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE accounts = NONE LIMIT $limit",
+            SUBSTRATE_EVENTS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("limit", limit))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Events without accounts query failed: {}", e))
+            })?;
+
+        let events: Vec<SubstrateEvent> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Events without accounts extraction failed: {}", e))
+        })?;
+
+        Ok(events)
+    }
+
+    pub async fn set_accounts(
+        &self,
+        block_number: u32,
+        event_index: u32,
+        accounts: &[String],
+    ) -> Result<(), ServiceError> {
+        let query = format!(
+            "UPDATE {} SET accounts = $accounts WHERE block_number = $block_number AND event_index = $event_index",
+            SUBSTRATE_EVENTS_TABLE
+        );
+        self.db
+            .query(query)
+            .bind(("block_number", block_number))
+            .bind(("event_index", event_index))
+            .bind(("accounts", accounts.to_vec()))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Event accounts update failed: {}", e))
+            })?
+            .check()
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Event accounts update failed: {}", e))
+            })?;
+
+        Ok(())
+    }
+
     pub async fn get_by_block_number(
         &self,
         block_number: u32,
@@ -67,6 +130,128 @@ impl<'a> SubstrateEventService<'a> {
         Ok(events)
     }
 
+    /// Events of the blocks `from..=to` from the given modules, or from every module when
+    /// `modules` is empty, in block order
+    pub async fn get_by_block_range(
+        &self,
+        from: u32,
+        to: u32,
+        modules: &[&str],
+    ) -> Result<Vec<SubstrateEvent>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE block_number >= $from AND block_number <= $to AND ($modules = [] OR module IN $modules) ORDER BY block_number ASC, event_index ASC",
+            SUBSTRATE_EVENTS_TABLE
+        );
+        let modules: Vec<String> = modules.iter().map(|module| module.to_string()).collect();
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("from", from))
+            .bind(("to", to))
+            .bind(("modules", modules))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Events by block range query failed: {}", e))
+            })?;
+
+        let events: Vec<SubstrateEvent> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Events by block range extraction failed: {}", e))
+        })?;
+
+        Ok(events)
+    }
+
+    /// Events emitted while applying a given extrinsic
+    pub async fn get_by_extrinsic(
+        &self,
+        block_number: u32,
+        extrinsic_index: u32,
+    ) -> Result<Vec<SubstrateEvent>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE block_number = $block_number AND extrinsic_index = $extrinsic_index ORDER BY event_index ASC",
+            SUBSTRATE_EVENTS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("block_number", block_number))
+            .bind(("extrinsic_index", extrinsic_index))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Events by extrinsic query failed: {}", e))
+            })?;
+
+        let events: Vec<SubstrateEvent> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Events by extrinsic extraction failed: {}", e))
+        })?;
+
+        Ok(events)
+    }
+
+    /// Events mentioning an account, e.g. transfers, rewards and slashes, newest first
+    pub async fn get_by_account(
+        &self,
+        account: &str,
+        filter: &SubstrateAccountFilter,
+        cursor: Option<&Cursor<SubstrateKey>>,
+        limit: u32,
+    ) -> Result<Vec<SubstrateEvent>, ServiceError> {
+        let mut conditions = vec!["accounts CONTAINS $account".to_string()];
+
+        if filter.module.is_some() {
+            conditions.push("module = $module".to_string());
+        }
+        if filter.name.is_some() {
+            conditions.push("event = $name".to_string());
+        }
+        if filter.from_timestamp.is_some() {
+            conditions.push("timestamp >= $from_timestamp".to_string());
+        }
+        if filter.to_timestamp.is_some() {
+            conditions.push("timestamp <= $to_timestamp".to_string());
+        }
+        if let Some(cursor) = cursor {
+            conditions.push(match cursor.key {
+                Some(_) => "(timestamp < $cursor_timestamp OR (timestamp = $cursor_timestamp AND (block_number < $cursor_block OR (block_number = $cursor_block AND event_index < $cursor_index))))".to_string(),
+                None => "timestamp < $cursor_timestamp".to_string(),
+            });
+        }
+
+        let query = format!(
+            "SELECT * FROM {} WHERE {} ORDER BY timestamp DESC, block_number DESC, event_index DESC LIMIT $limit",
+            SUBSTRATE_EVENTS_TABLE,
+            conditions.join(" AND ")
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("account", account.to_string()))
+            .bind(("module", filter.module.clone()))
+            .bind(("name", filter.name.clone()))
+            .bind(("from_timestamp", filter.from_timestamp))
+            .bind(("to_timestamp", filter.to_timestamp))
+            .bind(("cursor_timestamp", cursor.map(|cursor| cursor.timestamp)))
+            .bind((
+                "cursor_block",
+                cursor.and_then(|cursor| cursor.key).map(|key| key.0),
+            ))
+            .bind((
+                "cursor_index",
+                cursor.and_then(|cursor| cursor.key).map(|key| key.1),
+            ))
+            .bind(("limit", limit))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Events by account query failed: {}", e))
+            })?;
+
+        let events: Vec<SubstrateEvent> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Events by account extraction failed: {}", e))
+        })?;
+
+        Ok(events)
+    }
+
     pub async fn get_by_module(
         &self,
         module: &str,
@@ -223,4 +408,90 @@ impl<'a> SubstrateEventService<'a> {
 
         Ok(events)
     }
+
+    /// Filters events of a pallet/variant by one of their decoded fields, e.g.
+    /// `Balances.Transfer` events where `from` equals an address
+    pub async fn get_by_field(
+        &self,
+        module: &str,
+        event_name: &str,
+        filter: &EventFieldFilter,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<SubstrateEvent>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE module = $module AND event = $event AND data.{} IN $values ORDER BY timestamp DESC LIMIT $limit START $offset",
+            SUBSTRATE_EVENTS_TABLE,
+            filter.field()
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("module", module.to_string()))
+            .bind(("event", event_name.to_string()))
+            .bind(("values", filter.values()))
+            .bind(("limit", limit))
+            .bind(("offset", offset))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Events by field query failed: {}", e))
+            })?;
+
+        let events: Vec<SubstrateEvent> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Events by field extraction failed: {}", e))
+        })?;
+
+        Ok(events)
+    }
+
+    /// Events of a block in order, or of one of its extrinsics, those after the `after` index,
+    /// pallet and event names ignore case
+    pub async fn get_block_page(
+        &self,
+        block_number: u32,
+        extrinsic_index: Option<u32>,
+        filter: &SubstrateAccountFilter,
+        after: Option<u32>,
+        limit: u32,
+    ) -> Result<Vec<SubstrateEvent>, ServiceError> {
+        let mut conditions = vec!["block_number = $block_number".to_string()];
+
+        if extrinsic_index.is_some() {
+            conditions.push("extrinsic_index = $extrinsic_index".to_string());
+        }
+        if filter.module.is_some() {
+            conditions.push("string::lowercase(module) = $module".to_string());
+        }
+        if filter.name.is_some() {
+            conditions.push("string::lowercase(event) = $name".to_string());
+        }
+        if after.is_some() {
+            conditions.push("event_index > $after".to_string());
+        }
+
+        let query = format!(
+            "SELECT * FROM {} WHERE {} ORDER BY event_index ASC LIMIT $limit",
+            SUBSTRATE_EVENTS_TABLE,
+            conditions.join(" AND ")
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("block_number", block_number))
+            .bind(("extrinsic_index", extrinsic_index))
+            .bind(("module", filter.module.as_deref().map(str::to_lowercase)))
+            .bind(("name", filter.name.as_deref().map(str::to_lowercase)))
+            .bind(("after", after))
+            .bind(("limit", limit))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Events by block query failed: {}", e))
+            })?;
+
+        let events: Vec<SubstrateEvent> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Events by block extraction failed: {}", e))
+        })?;
+
+        Ok(events)
+    }
 }
@@ -1,6 +1,9 @@
 use config::SUBSTRATE_EXTRINSICS_TABLE;
 use custom_error::ServiceError;
-use models::substrate::SubstrateExtrinsic;
+use models::{
+    activity::{Cursor, SubstrateKey},
+    substrate::{SubstrateAccountFilter, SubstrateExtrinsic},
+};
 
 use super::SubstrateExtrinsicService;
 
@@ -26,6 +29,7 @@ impl<'a> SubstrateExtrinsicService<'a> {
         Ok(created)
     }
 
+    /// Saves the extrinsics of a block, stopping at the first one that fails
     pub async fn save_batch(
         &self,
         extrinsics: &[SubstrateExtrinsic],
@@ -33,21 +37,28 @@ impl<'a> SubstrateExtrinsicService<'a> {
         let mut saved_extrinsics = Vec::new();
 
         for extrinsic in extrinsics {
-            match self.save(extrinsic).await {
-                Ok(saved) => saved_extrinsics.push(saved),
-                Err(e) => {
-                    println!(
-                        "❌ Failed to save extrinsic {} in block {}: {}",
-                        extrinsic.extrinsic_index, extrinsic.block_number, e
-                    );
-                    // Continue with other extrinsics
-                }
-            }
+            saved_extrinsics.push(self.save(extrinsic).await?);
         }
 
         Ok(saved_extrinsics)
     }
 
+    pub async fn delete_by_block_number(&self, block_number: u32) -> Result<(), ServiceError> {
+        let query = format!(
+            "DELETE FROM {} WHERE block_number = $block_number",
+            SUBSTRATE_EXTRINSICS_TABLE
+        );
+        self.db
+            .query(query)
+            .bind(("block_number", block_number))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Extrinsic delete failed: {}", e)))?
+            .check()
+            .map_err(|e| ServiceError::DatabaseError(format!("Extrinsic delete failed: {}", e)))?;
+
+        Ok(())
+    }
+
     pub async fn get_all(
         &self,
         limit: u32,
@@ -98,6 +109,63 @@ impl<'a> SubstrateExtrinsicService<'a> {
         Ok(extrinsics)
     }
 
+    /// Extrinsics of the blocks `from..=to`, in block order
+    pub async fn get_by_block_range(
+        &self,
+        from: u32,
+        to: u32,
+    ) -> Result<Vec<SubstrateExtrinsic>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE block_number >= $from AND block_number <= $to ORDER BY block_number ASC, extrinsic_index ASC",
+            SUBSTRATE_EXTRINSICS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("from", from))
+            .bind(("to", to))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!(
+                    "Extrinsics by block range query failed: {}",
+                    e
+                ))
+            })?;
+
+        let extrinsics: Vec<SubstrateExtrinsic> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!(
+                "Extrinsics by block range extraction failed: {}",
+                e
+            ))
+        })?;
+
+        Ok(extrinsics)
+    }
+
+    pub async fn get_by_block_and_index(
+        &self,
+        block_number: u32,
+        extrinsic_index: u32,
+    ) -> Result<Option<SubstrateExtrinsic>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE block_number = $block_number AND extrinsic_index = $extrinsic_index LIMIT 1",
+            SUBSTRATE_EXTRINSICS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("block_number", block_number))
+            .bind(("extrinsic_index", extrinsic_index))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Extrinsic query failed: {}", e)))?;
+
+        let extrinsic: Option<SubstrateExtrinsic> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Extrinsic extraction failed: {}", e))
+        })?;
+
+        Ok(extrinsic)
+    }
+
     pub async fn get_by_signer(
         &self,
         signer: &str,
@@ -124,6 +192,70 @@ impl<'a> SubstrateExtrinsicService<'a> {
         Ok(extrinsics)
     }
 
+    /// Extrinsics signed by an account, newest first
+    pub async fn get_by_account(
+        &self,
+        account: &str,
+        filter: &SubstrateAccountFilter,
+        cursor: Option<&Cursor<SubstrateKey>>,
+        limit: u32,
+    ) -> Result<Vec<SubstrateExtrinsic>, ServiceError> {
+        let mut conditions = vec!["signer = $account".to_string()];
+
+        if filter.module.is_some() {
+            conditions.push("call_module = $module".to_string());
+        }
+        if filter.name.is_some() {
+            conditions.push("call_function = $name".to_string());
+        }
+        if filter.from_timestamp.is_some() {
+            conditions.push("timestamp >= $from_timestamp".to_string());
+        }
+        if filter.to_timestamp.is_some() {
+            conditions.push("timestamp <= $to_timestamp".to_string());
+        }
+        if let Some(cursor) = cursor {
+            conditions.push(match cursor.key {
+                Some(_) => "(timestamp < $cursor_timestamp OR (timestamp = $cursor_timestamp AND (block_number < $cursor_block OR (block_number = $cursor_block AND extrinsic_index < $cursor_index))))".to_string(),
+                None => "timestamp < $cursor_timestamp".to_string(),
+            });
+        }
+
+        let query = format!(
+            "SELECT * FROM {} WHERE {} ORDER BY timestamp DESC, block_number DESC, extrinsic_index DESC LIMIT $limit",
+            SUBSTRATE_EXTRINSICS_TABLE,
+            conditions.join(" AND ")
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("account", account.to_string()))
+            .bind(("module", filter.module.clone()))
+            .bind(("name", filter.name.clone()))
+            .bind(("from_timestamp", filter.from_timestamp))
+            .bind(("to_timestamp", filter.to_timestamp))
+            .bind(("cursor_timestamp", cursor.map(|cursor| cursor.timestamp)))
+            .bind((
+                "cursor_block",
+                cursor.and_then(|cursor| cursor.key).map(|key| key.0),
+            ))
+            .bind((
+                "cursor_index",
+                cursor.and_then(|cursor| cursor.key).map(|key| key.1),
+            ))
+            .bind(("limit", limit))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Extrinsics by account query failed: {}", e))
+            })?;
+
+        let extrinsics: Vec<SubstrateExtrinsic> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Extrinsics by account extraction failed: {}", e))
+        })?;
+
+        Ok(extrinsics)
+    }
+
     pub async fn get_by_module(
         &self,
         module: &str,
@@ -246,4 +378,84 @@ impl<'a> SubstrateExtrinsicService<'a> {
 
         Ok(extrinsic)
     }
+
+    /// Extrinsics by `(block_number, extrinsic_index)`, in no particular order
+    pub async fn get_by_keys(
+        &self,
+        keys: &[SubstrateKey],
+    ) -> Result<Vec<SubstrateExtrinsic>, ServiceError> {
+        if keys.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let blocks: Vec<u32> = keys.iter().map(|key| key.0).collect();
+        let indexes: Vec<u32> = keys.iter().map(|key| key.1).collect();
+        let query = format!(
+            "SELECT * FROM {} WHERE block_number IN $blocks AND extrinsic_index IN $indexes",
+            SUBSTRATE_EXTRINSICS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("blocks", blocks))
+            .bind(("indexes", indexes))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Extrinsics query failed: {}", e)))?;
+
+        let extrinsics: Vec<SubstrateExtrinsic> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Extrinsics extraction failed: {}", e))
+        })?;
+
+        // Both lists match every pairing of their values, keep only the requested pairs
+        Ok(extrinsics
+            .into_iter()
+            .filter(|extrinsic| keys.contains(&(extrinsic.block_number, extrinsic.extrinsic_index)))
+            .collect())
+    }
+
+    /// Extrinsics of a block in order, those after the `after` index, pallet and call names
+    /// ignore case
+    pub async fn get_block_page(
+        &self,
+        block_number: u32,
+        filter: &SubstrateAccountFilter,
+        after: Option<u32>,
+        limit: u32,
+    ) -> Result<Vec<SubstrateExtrinsic>, ServiceError> {
+        let mut conditions = vec!["block_number = $block_number".to_string()];
+
+        if filter.module.is_some() {
+            conditions.push("string::lowercase(call_module) = $module".to_string());
+        }
+        if filter.name.is_some() {
+            conditions.push("string::lowercase(call_function) = $name".to_string());
+        }
+        if after.is_some() {
+            conditions.push("extrinsic_index > $after".to_string());
+        }
+
+        let query = format!(
+            "SELECT * FROM {} WHERE {} ORDER BY extrinsic_index ASC LIMIT $limit",
+            SUBSTRATE_EXTRINSICS_TABLE,
+            conditions.join(" AND ")
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("block_number", block_number))
+            .bind(("module", filter.module.as_deref().map(str::to_lowercase)))
+            .bind(("name", filter.name.as_deref().map(str::to_lowercase)))
+            .bind(("after", after))
+            .bind(("limit", limit))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Extrinsics by block query failed: {}", e))
+            })?;
+
+        let extrinsics: Vec<SubstrateExtrinsic> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Extrinsics by block extraction failed: {}", e))
+        })?;
+
+        Ok(extrinsics)
+    }
 }
@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+
+use config::IDENTITIES_TABLE;
+use custom_error::ServiceError;
+use models::substrate::Identity;
+
+use super::IdentityService;
+use crate::schema::prefix_range;
+
+// On-chain identities, one record per account
+impl<'a> IdentityService<'a> {
+    pub async fn save(&self, identity: &Identity) -> Result<(), ServiceError> {
+        self.db
+            .query("UPSERT type::thing($table, [$account]) CONTENT $identity")
+            .bind(("table", IDENTITIES_TABLE))
+            .bind(("account", identity.account.clone()))
+            .bind(("identity", identity.clone()))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Identity save failed: {}", e)))?
+            .check()
+            .map_err(|e| ServiceError::DatabaseError(format!("Identity save failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Removes the identity of an account that cleared it or had it killed
+    pub async fn delete(&self, account: &str) -> Result<(), ServiceError> {
+        self.db
+            .query("DELETE type::thing($table, [$account])")
+            .bind(("table", IDENTITIES_TABLE))
+            .bind(("account", account.to_string()))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Identity delete failed: {}", e)))?
+            .check()
+            .map_err(|e| ServiceError::DatabaseError(format!("Identity delete failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Removes the identities a full scan at `block_number` did not refresh
+    pub async fn delete_stale(&self, block_number: u32) -> Result<(), ServiceError> {
+        let query = format!(
+            "DELETE {} WHERE block_number < $block_number",
+            IDENTITIES_TABLE
+        );
+        self.db
+            .query(query)
+            .bind(("block_number", block_number))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Stale identity delete failed: {}", e))
+            })?
+            .check()
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Stale identity delete failed: {}", e))
+            })?;
+
+        Ok(())
+    }
+
+    pub async fn get_by_account(&self, account: &str) -> Result<Option<Identity>, ServiceError> {
+        let mut result = self
+            .db
+            .query("SELECT * FROM type::thing($table, [$account])")
+            .bind(("table", IDENTITIES_TABLE))
+            .bind(("account", account.to_string()))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Identity query failed: {}", e)))?;
+
+        let identity: Option<Identity> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Identity extraction failed: {}", e))
+        })?;
+
+        Ok(identity)
+    }
+
+    /// Display names of the accounts that have one, keyed by account
+    pub async fn display_names(
+        &self,
+        accounts: &[String],
+    ) -> Result<HashMap<String, String>, ServiceError> {
+        if accounts.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let query = format!(
+            "SELECT * FROM {} WHERE account IN $accounts AND display_name != NONE",
+            IDENTITIES_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("accounts", accounts.to_vec()))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Display names query failed: {}", e))
+            })?;
+
+        let identities: Vec<Identity> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Display names extraction failed: {}", e))
+        })?;
+
+        Ok(identities
+            .into_iter()
+            .filter_map(|identity| Some((identity.account, identity.display_name?)))
+            .collect())
+    }
+
+    /// Identities whose display name, legal name or username contains `query`, ignoring case
+    pub async fn search(
+        &self,
+        query: &str,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<Identity>, ServiceError> {
+        let statement = format!(
+            "SELECT * FROM {} WHERE string::lowercase(display_name ?? '') CONTAINS $query OR string::lowercase(legal ?? '') CONTAINS $query OR string::lowercase(username ?? '') CONTAINS $query ORDER BY display_name ASC LIMIT $limit START $offset",
+            IDENTITIES_TABLE
+        );
+        let mut result = self
+            .db
+            .query(statement)
+            .bind(("query", query.to_lowercase()))
+            .bind(("limit", limit))
+            .bind(("offset", offset))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Identity search query failed: {}", e))
+            })?;
+
+        let identities: Vec<Identity> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Identity search extraction failed: {}", e))
+        })?;
+
+        Ok(identities)
+    }
+
+    /// Identities whose display name starts with `prefix`, ignoring case, shortest completions
+    /// first
+    pub async fn search_by_display_prefix(
+        &self,
+        prefix: &str,
+        limit: u32,
+    ) -> Result<Vec<Identity>, ServiceError> {
+        let (start, end) = prefix_range(prefix);
+        let statement = format!(
+            "SELECT *, string::len(display_name_lower) AS match_length FROM {} WHERE display_name_lower >= $start AND display_name_lower < $end ORDER BY match_length ASC, display_name_lower ASC LIMIT $limit",
+            IDENTITIES_TABLE
+        );
+        let mut result = self
+            .db
+            .query(statement)
+            .bind(("start", start))
+            .bind(("end", end))
+            .bind(("limit", limit))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Identity prefix query failed: {}", e))
+            })?;
+
+        let identities: Vec<Identity> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Identity prefix extraction failed: {}", e))
+        })?;
+
+        Ok(identities)
+    }
+}
@@ -1,6 +1,10 @@
 pub mod block;
 pub mod event;
 pub mod extrinsic;
+pub mod identity;
+pub mod staking;
+pub mod transfer;
+pub mod validator;
 
 use surrealdb::{Surreal, engine::any::Any};
 
@@ -15,3 +19,19 @@ pub struct SubstrateExtrinsicService<'a> {
 pub struct SubstrateEventService<'a> {
     pub db: &'a Surreal<Any>,
 }
+
+pub struct StakingService<'a> {
+    pub db: &'a Surreal<Any>,
+}
+
+pub struct ValidatorSnapshotService<'a> {
+    pub db: &'a Surreal<Any>,
+}
+
+pub struct IdentityService<'a> {
+    pub db: &'a Surreal<Any>,
+}
+
+pub struct SubstrateTransferService<'a> {
+    pub db: &'a Surreal<Any>,
+}
@@ -0,0 +1,329 @@
+use config::{
+    ERA_PAYOUTS_TABLE, ERA_VALIDATOR_POINTS_TABLE, STAKING_LEDGERS_TABLE, STAKING_REWARDS_TABLE,
+    STAKING_SLASHES_TABLE,
+};
+use custom_error::ServiceError;
+use models::substrate::{
+    EraPayout, EraValidatorPoints, StakingLedger, StakingReward, StakingSlash,
+};
+use serde::Serialize;
+
+use super::StakingService;
+
+// Staking history, records are keyed so re-indexing a block overwrites them
+impl<'a> StakingService<'a> {
+    async fn upsert<T: Serialize + 'static>(
+        &self,
+        table: &'static str,
+        key: Vec<serde_json::Value>,
+        record: T,
+        label: &str,
+    ) -> Result<(), ServiceError> {
+        self.db
+            .query("UPSERT type::thing($table, $key) CONTENT $record")
+            .bind(("table", table))
+            .bind(("key", key))
+            .bind(("record", record))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("{} save failed: {}", label, e)))?
+            .check()
+            .map_err(|e| ServiceError::DatabaseError(format!("{} save failed: {}", label, e)))?;
+
+        Ok(())
+    }
+
+    pub async fn save_reward(&self, reward: &StakingReward) -> Result<(), ServiceError> {
+        self.upsert(
+            STAKING_REWARDS_TABLE,
+            vec![reward.block_number.into(), reward.event_index.into()],
+            reward.clone(),
+            "Staking reward",
+        )
+        .await
+    }
+
+    pub async fn save_slash(&self, slash: &StakingSlash) -> Result<(), ServiceError> {
+        self.upsert(
+            STAKING_SLASHES_TABLE,
+            vec![slash.block_number.into(), slash.event_index.into()],
+            slash.clone(),
+            "Staking slash",
+        )
+        .await
+    }
+
+    pub async fn save_era_payout(&self, payout: &EraPayout) -> Result<(), ServiceError> {
+        self.upsert(
+            ERA_PAYOUTS_TABLE,
+            vec![payout.era.into()],
+            payout.clone(),
+            "Era payout",
+        )
+        .await
+    }
+
+    pub async fn save_validator_points(
+        &self,
+        points: &EraValidatorPoints,
+    ) -> Result<(), ServiceError> {
+        self.upsert(
+            ERA_VALIDATOR_POINTS_TABLE,
+            vec![points.era.into(), points.validator.clone().into()],
+            points.clone(),
+            "Era validator points",
+        )
+        .await
+    }
+
+    /// Rewards received by a stash, latest era first
+    pub async fn get_rewards_by_stash(
+        &self,
+        stash: &str,
+        from_era: Option<u32>,
+        to_era: Option<u32>,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<StakingReward>, ServiceError> {
+        let mut conditions = vec!["stash = $stash"];
+        if from_era.is_some() {
+            conditions.push("era >= $from_era");
+        }
+        if to_era.is_some() {
+            conditions.push("era <= $to_era");
+        }
+
+        let query = format!(
+            "SELECT * FROM {} WHERE {} ORDER BY era DESC, block_number DESC, event_index DESC LIMIT $limit START $offset",
+            STAKING_REWARDS_TABLE,
+            conditions.join(" AND ")
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("stash", stash.to_string()))
+            .bind(("from_era", from_era))
+            .bind(("to_era", to_era))
+            .bind(("limit", limit))
+            .bind(("offset", offset))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Staking rewards query failed: {}", e))
+            })?;
+
+        let rewards: Vec<StakingReward> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Staking rewards extraction failed: {}", e))
+        })?;
+
+        Ok(rewards)
+    }
+
+    pub async fn get_slashes_by_staker(
+        &self,
+        staker: &str,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<StakingSlash>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE staker = $staker ORDER BY block_number DESC, event_index DESC LIMIT $limit START $offset",
+            STAKING_SLASHES_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("staker", staker.to_string()))
+            .bind(("limit", limit))
+            .bind(("offset", offset))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Staking slashes query failed: {}", e))
+            })?;
+
+        let slashes: Vec<StakingSlash> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Staking slashes extraction failed: {}", e))
+        })?;
+
+        Ok(slashes)
+    }
+
+    /// Era-by-era points, reward share and commission of a validator, latest era first
+    pub async fn get_validator_eras(
+        &self,
+        validator: &str,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<EraValidatorPoints>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE validator = $validator ORDER BY era DESC LIMIT $limit START $offset",
+            ERA_VALIDATOR_POINTS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("validator", validator.to_string()))
+            .bind(("limit", limit))
+            .bind(("offset", offset))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Validator eras query failed: {}", e))
+            })?;
+
+        let eras: Vec<EraValidatorPoints> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Validator eras extraction failed: {}", e))
+        })?;
+
+        Ok(eras)
+    }
+
+    /// Validators of an era, most points first
+    pub async fn get_era_validators(
+        &self,
+        era: u32,
+    ) -> Result<Vec<EraValidatorPoints>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE era = $era ORDER BY points DESC",
+            ERA_VALIDATOR_POINTS_TABLE
+        );
+        let mut result = self.db.query(query).bind(("era", era)).await.map_err(|e| {
+            ServiceError::DatabaseError(format!("Era validators query failed: {}", e))
+        })?;
+
+        let validators: Vec<EraValidatorPoints> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Era validators extraction failed: {}", e))
+        })?;
+
+        Ok(validators)
+    }
+
+    pub async fn get_era_payouts(
+        &self,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<EraPayout>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} ORDER BY era DESC LIMIT $limit START $offset",
+            ERA_PAYOUTS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("limit", limit))
+            .bind(("offset", offset))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Era payouts query failed: {}", e)))?;
+
+        let payouts: Vec<EraPayout> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Era payouts extraction failed: {}", e))
+        })?;
+
+        Ok(payouts)
+    }
+
+    pub async fn get_era_payout(&self, era: u32) -> Result<Option<EraPayout>, ServiceError> {
+        let mut result = self
+            .db
+            .query("SELECT * FROM type::thing($table, [$era])")
+            .bind(("table", ERA_PAYOUTS_TABLE))
+            .bind(("era", era))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Era payout query failed: {}", e)))?;
+
+        let payout: Option<EraPayout> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Era payout extraction failed: {}", e))
+        })?;
+
+        Ok(payout)
+    }
+
+    /// Saves the ledger of a stash, one record per stash
+    pub async fn save_ledger(&self, ledger: &StakingLedger) -> Result<(), ServiceError> {
+        self.upsert(
+            STAKING_LEDGERS_TABLE,
+            vec![ledger.stash.clone().into()],
+            ledger.clone(),
+            "Staking ledger",
+        )
+        .await
+    }
+
+    /// Removes the ledger of a stash that is no longer bonded
+    pub async fn delete_ledger(&self, stash: &str) -> Result<(), ServiceError> {
+        self.db
+            .query("DELETE type::thing($table, [$stash])")
+            .bind(("table", STAKING_LEDGERS_TABLE))
+            .bind(("stash", stash.to_string()))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Staking ledger delete failed: {}", e))
+            })?
+            .check()
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Staking ledger delete failed: {}", e))
+            })?;
+
+        Ok(())
+    }
+
+    /// Removes the ledgers a full snapshot at `block_number` did not refresh
+    pub async fn delete_stale_ledgers(&self, block_number: u32) -> Result<(), ServiceError> {
+        let query = format!(
+            "DELETE {} WHERE block_number < $block_number",
+            STAKING_LEDGERS_TABLE
+        );
+        self.db
+            .query(query)
+            .bind(("block_number", block_number))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Stale ledger delete failed: {}", e)))?
+            .check()
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Stale ledger delete failed: {}", e))
+            })?;
+
+        Ok(())
+    }
+
+    pub async fn get_ledger(&self, stash: &str) -> Result<Option<StakingLedger>, ServiceError> {
+        let mut result = self
+            .db
+            .query("SELECT * FROM type::thing($table, [$stash])")
+            .bind(("table", STAKING_LEDGERS_TABLE))
+            .bind(("stash", stash.to_string()))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Staking ledger query failed: {}", e))
+            })?;
+
+        let ledger: Option<StakingLedger> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Staking ledger extraction failed: {}", e))
+        })?;
+
+        Ok(ledger)
+    }
+
+    /// Ledgers of the stashes nominating a validator, largest active bond first
+    pub async fn get_nominators(
+        &self,
+        validator: &str,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<StakingLedger>, ServiceError> {
+        let query = format!(
+            "SELECT *, <decimal> active AS active_order FROM {} WHERE nominations CONTAINS $validator ORDER BY active_order DESC LIMIT $limit START $offset",
+            STAKING_LEDGERS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("validator", validator.to_string()))
+            .bind(("limit", limit))
+            .bind(("offset", offset))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Nominators query failed: {}", e)))?;
+
+        let nominators: Vec<StakingLedger> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Nominators extraction failed: {}", e))
+        })?;
+
+        Ok(nominators)
+    }
+}
@@ -0,0 +1,105 @@
+use config::SUBSTRATE_TRANSFERS_TABLE;
+use custom_error::ServiceError;
+use models::substrate::SubstrateTransfer;
+
+use super::SubstrateTransferService;
+
+// Native transfers, one record per (block_number, transfer_index)
+impl<'a> SubstrateTransferService<'a> {
+    pub async fn save(&self, transfer: &SubstrateTransfer) -> Result<(), ServiceError> {
+        self.db
+            .query("UPSERT type::thing($table, [$block_number, $transfer_index]) CONTENT $transfer")
+            .bind(("table", SUBSTRATE_TRANSFERS_TABLE))
+            .bind(("block_number", transfer.block_number))
+            .bind(("transfer_index", transfer.transfer_index))
+            .bind(("transfer", transfer.clone()))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Substrate transfer save failed: {}", e))
+            })?
+            .check()
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Substrate transfer save failed: {}", e))
+            })?;
+
+        Ok(())
+    }
+
+    pub async fn delete_by_block_number(&self, block_number: u32) -> Result<(), ServiceError> {
+        let query = format!(
+            "DELETE FROM {} WHERE block_number = $block_number",
+            SUBSTRATE_TRANSFERS_TABLE
+        );
+        self.db
+            .query(query)
+            .bind(("block_number", block_number))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Transfer delete failed: {}", e)))?
+            .check()
+            .map_err(|e| ServiceError::DatabaseError(format!("Transfer delete failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Saves every transfer, stopping at the first that fails so the block can be retried
+    pub async fn save_batch(&self, transfers: &[SubstrateTransfer]) -> Result<usize, ServiceError> {
+        for transfer in transfers {
+            self.save(transfer).await?;
+        }
+
+        Ok(transfers.len())
+    }
+
+    /// Transfers sent or received by an account, newest first
+    pub async fn get_by_account(
+        &self,
+        account: &str,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<SubstrateTransfer>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE from = $account OR to = $account ORDER BY block_number DESC, transfer_index DESC LIMIT $limit START $offset",
+            SUBSTRATE_TRANSFERS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("account", account.to_string()))
+            .bind(("limit", limit))
+            .bind(("offset", offset))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Account transfers query failed: {}", e))
+            })?;
+
+        let transfers: Vec<SubstrateTransfer> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Account transfers extraction failed: {}", e))
+        })?;
+
+        Ok(transfers)
+    }
+
+    pub async fn get_by_block_number(
+        &self,
+        block_number: u32,
+    ) -> Result<Vec<SubstrateTransfer>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE block_number = $block_number ORDER BY transfer_index ASC",
+            SUBSTRATE_TRANSFERS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("block_number", block_number))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Block transfers query failed: {}", e))
+            })?;
+
+        let transfers: Vec<SubstrateTransfer> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Block transfers extraction failed: {}", e))
+        })?;
+
+        Ok(transfers)
+    }
+}
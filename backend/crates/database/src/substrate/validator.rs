@@ -0,0 +1,128 @@
+use config::VALIDATOR_SNAPSHOTS_TABLE;
+use custom_error::ServiceError;
+use models::substrate::ValidatorSnapshot;
+use serde::Deserialize;
+
+use super::ValidatorSnapshotService;
+
+#[derive(Debug, Deserialize)]
+struct EraRecord {
+    era: u32,
+}
+
+// Validator history, one record per (era, account_id)
+impl<'a> ValidatorSnapshotService<'a> {
+    /// Saves a snapshot, its session block counts replace those recorded for the same sessions
+    /// and are kept alongside the other sessions of the era
+    pub async fn save(&self, snapshot: &ValidatorSnapshot) -> Result<(), ServiceError> {
+        self.db
+            .query(
+                "UPSERT type::thing($table, [$era, $account_id]) SET era = $era, account_id = $account_id, validator_type = $validator_type, commission = $commission, blocked = $blocked, total_stake = $total_stake, own_stake = $own_stake, nominator_count = $nominator_count, session_blocks = array::concat((session_blocks ?? [])[WHERE session NOTINSIDE $sessions], $session_blocks), blocks_produced = math::sum(session_blocks.blocks), block_number = $block_number, timestamp = $timestamp",
+            )
+            .bind(("table", VALIDATOR_SNAPSHOTS_TABLE))
+            .bind(("era", snapshot.era))
+            .bind(("account_id", snapshot.account_id.clone()))
+            .bind(("validator_type", snapshot.validator_type.clone()))
+            .bind(("commission", snapshot.commission))
+            .bind(("blocked", snapshot.blocked))
+            .bind(("total_stake", snapshot.total_stake.to_string()))
+            .bind(("own_stake", snapshot.own_stake.to_string()))
+            .bind(("nominator_count", snapshot.nominator_count))
+            .bind(("session_blocks", snapshot.session_blocks.clone()))
+            .bind((
+                "sessions",
+                snapshot
+                    .session_blocks
+                    .iter()
+                    .map(|session| session.session)
+                    .collect::<Vec<u32>>(),
+            ))
+            .bind(("block_number", snapshot.block_number))
+            .bind(("timestamp", snapshot.timestamp))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Validator snapshot save failed: {}", e))
+            })?
+            .check()
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Validator snapshot save failed: {}", e))
+            })?;
+
+        Ok(())
+    }
+
+    /// Latest era with a snapshot
+    pub async fn get_latest_era(&self) -> Result<Option<u32>, ServiceError> {
+        let query = format!(
+            "SELECT era FROM {} ORDER BY era DESC LIMIT 1",
+            VALIDATOR_SNAPSHOTS_TABLE
+        );
+        let mut result = self.db.query(query).await.map_err(|e| {
+            ServiceError::DatabaseError(format!("Latest validator era query failed: {}", e))
+        })?;
+
+        let record: Option<EraRecord> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Latest validator era extraction failed: {}", e))
+        })?;
+
+        Ok(record.map(|record| record.era))
+    }
+
+    /// Validators of an era, highest total stake first
+    pub async fn get_by_era(
+        &self,
+        era: u32,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<ValidatorSnapshot>, ServiceError> {
+        let query = format!(
+            "SELECT *, <decimal> total_stake AS stake FROM {} WHERE era = $era ORDER BY stake DESC LIMIT $limit START $offset",
+            VALIDATOR_SNAPSHOTS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("era", era))
+            .bind(("limit", limit))
+            .bind(("offset", offset))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Validator snapshots query failed: {}", e))
+            })?;
+
+        let snapshots: Vec<ValidatorSnapshot> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Validator snapshots extraction failed: {}", e))
+        })?;
+
+        Ok(snapshots)
+    }
+
+    /// Era history of a validator, latest era first
+    pub async fn get_by_account(
+        &self,
+        account_id: &str,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<ValidatorSnapshot>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE account_id = $account_id ORDER BY era DESC LIMIT $limit START $offset",
+            VALIDATOR_SNAPSHOTS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("account_id", account_id.to_string()))
+            .bind(("limit", limit))
+            .bind(("offset", offset))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Validator history query failed: {}", e))
+            })?;
+
+        let snapshots: Vec<ValidatorSnapshot> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Validator history extraction failed: {}", e))
+        })?;
+
+        Ok(snapshots)
+    }
+}
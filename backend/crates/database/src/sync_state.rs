@@ -0,0 +1,239 @@
+use std::{
+    collections::HashSet,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use config::{INDEXED_BLOCKS_TABLE, SYNC_STATE_TABLE};
+use custom_error::ServiceError;
+use models::SyncState;
+use surrealdb::{Surreal, engine::any::Any};
+
+pub struct SyncStateService<'a> {
+    pub db: &'a Surreal<Any>,
+}
+
+/// Most blocks one gap scan looks at above the checkpoint, a block that keeps failing holds
+/// the checkpoint without making every later scan longer
+pub const MAX_GAP_SCAN_BLOCKS: u64 = 1_000;
+
+/// Last block of the gap scan that starts at `from` and would end at `up_to`
+pub fn gap_scan_end(from: u64, up_to: u64) -> u64 {
+    up_to.min(from + MAX_GAP_SCAN_BLOCKS - 1)
+}
+
+/// Checkpoint a gap scan of `from..=to` can advance to: the block before the lowest one still
+/// not fully indexed, or `to` when every block is. None when `from` itself is not indexed.
+pub fn contiguous_end(from: u64, to: u64, still_missing: &[u64]) -> Option<u64> {
+    match still_missing.iter().min() {
+        Some(&first) if first <= from => None,
+        Some(&first) => Some(first - 1),
+        None => Some(to),
+    }
+}
+
+fn now_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0)
+}
+
+// Sync state service implementation, one record per indexer
+impl<'a> SyncStateService<'a> {
+    pub async fn get(&self, indexer: &str) -> Result<Option<SyncState>, ServiceError> {
+        let mut result = self
+            .db
+            .query("SELECT * FROM type::thing($table, $indexer)")
+            .bind(("table", SYNC_STATE_TABLE))
+            .bind(("indexer", indexer.to_string()))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Sync state query failed: {}", e)))?;
+
+        let state: Option<SyncState> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Sync state extraction failed: {}", e))
+        })?;
+
+        Ok(state)
+    }
+
+    pub async fn get_all(&self) -> Result<Vec<SyncState>, ServiceError> {
+        let query = format!("SELECT * FROM {} ORDER BY indexer ASC", SYNC_STATE_TABLE);
+        let mut result = self
+            .db
+            .query(query)
+            .await
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        let states: Vec<SyncState> = result
+            .take(0)
+            .map_err(|e| ServiceError::DatabaseError(e.to_string()))?;
+
+        Ok(states)
+    }
+
+    /// Returns the block the indexer should resume from, if it has a checkpoint
+    pub async fn resume_block(&self, indexer: &str) -> Result<Option<u64>, ServiceError> {
+        let state = self.get(indexer).await?;
+
+        Ok(state
+            .and_then(|s| s.highest_contiguous_block)
+            .map(|block| block + 1))
+    }
+
+    pub async fn set_highest_contiguous_block(
+        &self,
+        indexer: &str,
+        block_number: u64,
+    ) -> Result<(), ServiceError> {
+        self.upsert(indexer, "highest_contiguous_block", block_number)
+            .await
+    }
+
+    /// Moves the checkpoint back so the indexer resumes from `block_number`
+    pub async fn rewind_to(&self, indexer: &str, block_number: u64) -> Result<(), ServiceError> {
+        self.upsert(
+            indexer,
+            "highest_contiguous_block",
+            block_number.checked_sub(1),
+        )
+        .await
+    }
+
+    /// Records that a block and every row indexed from it are stored. Written only after all
+    /// of them committed, so a block whose transactions or events failed is scanned again.
+    pub async fn mark_indexed(&self, indexer: &str, block_number: u64) -> Result<(), ServiceError> {
+        self.db
+            .query("UPSERT type::thing($table, [$indexer, $number]) SET indexer = $indexer, number = $number, indexed_at = $indexed_at")
+            .bind(("table", INDEXED_BLOCKS_TABLE))
+            .bind(("indexer", indexer.to_string()))
+            .bind(("number", block_number))
+            .bind(("indexed_at", now_millis()))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Indexed block update failed: {}", e)))?
+            .check()
+            .map_err(|e| ServiceError::DatabaseError(format!("Indexed block update failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Forgets that a block was indexed, for blocks rolled back by a reorganization
+    pub async fn unmark_indexed(
+        &self,
+        indexer: &str,
+        block_number: u64,
+    ) -> Result<(), ServiceError> {
+        self.db
+            .query("DELETE type::thing($table, [$indexer, $number])")
+            .bind(("table", INDEXED_BLOCKS_TABLE))
+            .bind(("indexer", indexer.to_string()))
+            .bind(("number", block_number))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Indexed block delete failed: {}", e))
+            })?
+            .check()
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Indexed block delete failed: {}", e))
+            })?;
+
+        Ok(())
+    }
+
+    /// Returns the block numbers in `from..=to` that are not fully indexed yet
+    pub async fn find_unindexed(
+        &self,
+        indexer: &str,
+        from: u64,
+        to: u64,
+    ) -> Result<Vec<u64>, ServiceError> {
+        let query = format!(
+            "SELECT VALUE number FROM {} WHERE indexer = $indexer AND number >= $from AND number <= $to",
+            INDEXED_BLOCKS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("indexer", indexer.to_string()))
+            .bind(("from", from))
+            .bind(("to", to))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Block gap query failed: {}", e)))?;
+
+        let indexed: Vec<u64> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Block gap extraction failed: {}", e))
+        })?;
+
+        let indexed: HashSet<u64> = indexed.into_iter().collect();
+        let missing = (from..=to)
+            .filter(|number| !indexed.contains(number))
+            .collect();
+
+        Ok(missing)
+    }
+
+    pub async fn set_finalized_block(
+        &self,
+        indexer: &str,
+        block_number: u64,
+    ) -> Result<(), ServiceError> {
+        self.upsert(indexer, "finalized_block", block_number).await
+    }
+
+    /// Remembers how far a paged backfill got, so a restart continues after `address`
+    pub async fn set_link_cursor(&self, indexer: &str, address: &str) -> Result<(), ServiceError> {
+        self.upsert(indexer, "link_cursor", address.to_string())
+            .await
+    }
+
+    pub async fn set_last_error(&self, indexer: &str, error: &str) -> Result<(), ServiceError> {
+        self.upsert(indexer, "last_error", error.to_string()).await
+    }
+
+    async fn upsert<T>(&self, indexer: &str, field: &str, value: T) -> Result<(), ServiceError>
+    where
+        T: serde::Serialize + 'static,
+    {
+        let query = format!(
+            "UPSERT type::thing($table, $indexer) SET indexer = $indexer, {} = $value, updated_at = $updated_at",
+            field
+        );
+        self.db
+            .query(query)
+            .bind(("table", SYNC_STATE_TABLE))
+            .bind(("indexer", indexer.to_string()))
+            .bind(("value", value))
+            .bind(("updated_at", now_millis()))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Sync state update failed: {}", e)))?
+            .check()
+            .map_err(|e| ServiceError::DatabaseError(format!("Sync state update failed: {}", e)))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_GAP_SCAN_BLOCKS, contiguous_end, gap_scan_end};
+
+    #[test]
+    fn checkpoint_moves_to_the_end_of_a_complete_scan() {
+        assert_eq!(contiguous_end(100, 150, &[]), Some(150));
+    }
+
+    #[test]
+    fn checkpoint_stops_below_the_lowest_unindexed_block() {
+        assert_eq!(contiguous_end(100, 150, &[130, 120]), Some(119));
+    }
+
+    #[test]
+    fn checkpoint_stays_when_the_first_block_is_unindexed() {
+        assert_eq!(contiguous_end(100, 150, &[100, 120]), None);
+    }
+
+    #[test]
+    fn gap_scan_is_capped_above_the_checkpoint() {
+        assert_eq!(gap_scan_end(100, 150), 150);
+        assert_eq!(gap_scan_end(100, 1_000_000), 100 + MAX_GAP_SCAN_BLOCKS - 1);
+    }
+}
@@ -0,0 +1,239 @@
+use config::{WEBHOOK_DEAD_LETTERS_TABLE, WEBHOOK_DELIVERIES_TABLE, WEBHOOKS_TABLE};
+use custom_error::ServiceError;
+use models::webhook::{Webhook, WebhookDelivery};
+use surrealdb::{Surreal, engine::any::Any};
+
+pub struct WebhookService<'a> {
+    pub db: &'a Surreal<Any>,
+}
+
+pub struct WebhookDeliveryService<'a> {
+    pub db: &'a Surreal<Any>,
+}
+
+// Registered webhooks, one record per id
+impl<'a> WebhookService<'a> {
+    pub async fn save(&self, webhook: &Webhook) -> Result<(), ServiceError> {
+        self.db
+            .query("UPSERT type::thing($table, [$id]) CONTENT $webhook")
+            .bind(("table", WEBHOOKS_TABLE))
+            .bind(("id", webhook.id.clone()))
+            .bind(("webhook", webhook.clone()))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Webhook save failed: {}", e)))?
+            .check()
+            .map_err(|e| ServiceError::DatabaseError(format!("Webhook save failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Removes a webhook, returns whether it existed
+    pub async fn delete(&self, id: &str) -> Result<bool, ServiceError> {
+        let mut result = self
+            .db
+            .query("DELETE type::thing($table, [$id]) RETURN BEFORE")
+            .bind(("table", WEBHOOKS_TABLE))
+            .bind(("id", id.to_string()))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Webhook delete failed: {}", e)))?;
+
+        let deleted: Vec<Webhook> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Webhook delete extraction failed: {}", e))
+        })?;
+
+        Ok(!deleted.is_empty())
+    }
+
+    pub async fn get(&self, id: &str) -> Result<Option<Webhook>, ServiceError> {
+        let mut result = self
+            .db
+            .query("SELECT * FROM type::thing($table, [$id])")
+            .bind(("table", WEBHOOKS_TABLE))
+            .bind(("id", id.to_string()))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Webhook query failed: {}", e)))?;
+
+        let webhook: Option<Webhook> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Webhook extraction failed: {}", e))
+        })?;
+
+        Ok(webhook)
+    }
+
+    /// Webhooks registered with the owner token of hash `owner`, oldest first
+    pub async fn get_by_owner(&self, owner: &str) -> Result<Vec<Webhook>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE owner = $owner ORDER BY created_at ASC",
+            WEBHOOKS_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("owner", owner.to_string()))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("Webhooks query failed: {}", e)))?;
+
+        let webhooks: Vec<Webhook> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Webhooks extraction failed: {}", e))
+        })?;
+
+        Ok(webhooks)
+    }
+
+    pub async fn get_all(&self) -> Result<Vec<Webhook>, ServiceError> {
+        let query = format!("SELECT * FROM {} ORDER BY created_at ASC", WEBHOOKS_TABLE);
+        let mut result =
+            self.db.query(query).await.map_err(|e| {
+                ServiceError::DatabaseError(format!("Webhooks query failed: {}", e))
+            })?;
+
+        let webhooks: Vec<Webhook> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Webhooks extraction failed: {}", e))
+        })?;
+
+        Ok(webhooks)
+    }
+}
+
+// Delivery log, one record per (webhook, payload), and the dead letters of failed ones
+impl<'a> WebhookDeliveryService<'a> {
+    pub async fn save(&self, delivery: &WebhookDelivery) -> Result<(), ServiceError> {
+        self.upsert(WEBHOOK_DELIVERIES_TABLE, delivery, "Webhook delivery")
+            .await
+    }
+
+    /// Stores a new delivery unless one with its id exists, so matching a record again
+    /// neither queues it twice nor resets the status of the first delivery
+    pub async fn create(&self, delivery: &WebhookDelivery) -> Result<(), ServiceError> {
+        self.db
+            .query("LET $record = type::thing($table, [$id])")
+            .query("IF !record::exists($record) { CREATE $record CONTENT $delivery }")
+            .bind(("table", WEBHOOK_DELIVERIES_TABLE))
+            .bind(("id", delivery.id.clone()))
+            .bind(("delivery", delivery.clone()))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Webhook delivery create failed: {}", e))
+            })?
+            .check()
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Webhook delivery create failed: {}", e))
+            })?;
+
+        Ok(())
+    }
+
+    /// Keeps a copy of a delivery that ran out of retries
+    pub async fn save_dead_letter(&self, delivery: &WebhookDelivery) -> Result<(), ServiceError> {
+        self.upsert(WEBHOOK_DEAD_LETTERS_TABLE, delivery, "Webhook dead letter")
+            .await
+    }
+
+    async fn upsert(
+        &self,
+        table: &'static str,
+        delivery: &WebhookDelivery,
+        label: &str,
+    ) -> Result<(), ServiceError> {
+        self.db
+            .query("UPSERT type::thing($table, [$id]) CONTENT $delivery")
+            .bind(("table", table))
+            .bind(("id", delivery.id.clone()))
+            .bind(("delivery", delivery.clone()))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("{} save failed: {}", label, e)))?
+            .check()
+            .map_err(|e| ServiceError::DatabaseError(format!("{} save failed: {}", label, e)))?;
+
+        Ok(())
+    }
+
+    /// Pending deliveries whose next attempt is due at `now`, oldest first
+    pub async fn get_due(
+        &self,
+        now: u128,
+        limit: u32,
+    ) -> Result<Vec<WebhookDelivery>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE status = 'pending' AND next_attempt_at <= $now ORDER BY next_attempt_at ASC LIMIT $limit",
+            WEBHOOK_DELIVERIES_TABLE
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("now", now))
+            .bind(("limit", limit))
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Due deliveries query failed: {}", e))
+            })?;
+
+        let deliveries: Vec<WebhookDelivery> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("Due deliveries extraction failed: {}", e))
+        })?;
+
+        Ok(deliveries)
+    }
+
+    /// Deliveries of a webhook, newest first
+    pub async fn get_by_webhook(
+        &self,
+        webhook_id: &str,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<WebhookDelivery>, ServiceError> {
+        self.list(
+            WEBHOOK_DELIVERIES_TABLE,
+            webhook_id,
+            limit,
+            offset,
+            "Webhook deliveries",
+        )
+        .await
+    }
+
+    /// Dead letters of a webhook, newest first
+    pub async fn get_dead_letters(
+        &self,
+        webhook_id: &str,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<WebhookDelivery>, ServiceError> {
+        self.list(
+            WEBHOOK_DEAD_LETTERS_TABLE,
+            webhook_id,
+            limit,
+            offset,
+            "Webhook dead letters",
+        )
+        .await
+    }
+
+    async fn list(
+        &self,
+        table: &'static str,
+        webhook_id: &str,
+        limit: u32,
+        offset: u32,
+        label: &str,
+    ) -> Result<Vec<WebhookDelivery>, ServiceError> {
+        let query = format!(
+            "SELECT * FROM {} WHERE webhook_id = $webhook_id ORDER BY created_at DESC LIMIT $limit START $offset",
+            table
+        );
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("webhook_id", webhook_id.to_string()))
+            .bind(("limit", limit))
+            .bind(("offset", offset))
+            .await
+            .map_err(|e| ServiceError::DatabaseError(format!("{} query failed: {}", label, e)))?;
+
+        let deliveries: Vec<WebhookDelivery> = result.take(0).map_err(|e| {
+            ServiceError::DatabaseError(format!("{} extraction failed: {}", label, e))
+        })?;
+
+        Ok(deliveries)
+    }
+}
@@ -6,6 +6,8 @@ pub enum ServiceError {
     BlockNotFound,
     #[error("Invalid block data: {0}")]
     InvalidBlockData(String),
+    #[error("Reorganization at block {0} is deeper than {1} blocks")]
+    ReorgTooDeep(u32, u32),
     #[error("Transaction not found: {0}")]
     TransactionNotFound(String),
     #[error("Invalid transaction data: {0}")]
@@ -18,4 +20,8 @@ pub enum ServiceError {
     SubstrateError(String),
     #[error("Surrealdb error: {0}")]
     DatabaseError(String),
+    #[error("Verification failed: {0}")]
+    VerificationError(String),
+    #[error("Webhook error: {0}")]
+    WebhookError(String),
 }
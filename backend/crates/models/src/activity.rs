@@ -0,0 +1,252 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    evm::EvmTransaction,
+    substrate::{SubstrateEvent, SubstrateExtrinsic},
+};
+
+/// Block number and position in the block of an extrinsic or event
+pub type SubstrateKey = (u32, u32);
+
+/// Position in a newest-first listing. Items older than `timestamp` come after it, and so
+/// do items at `timestamp` whose key sorts below `key`; without a key every item at
+/// `timestamp` is skipped
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cursor<K> {
+    pub timestamp: u128,
+    pub key: Option<K>,
+}
+
+impl<K> Cursor<K> {
+    pub fn new(timestamp: u128, key: K) -> Self {
+        Self {
+            timestamp,
+            key: Some(key),
+        }
+    }
+
+    /// Skips everything at or after `timestamp`
+    pub fn before(timestamp: u128) -> Self {
+        Self {
+            timestamp,
+            key: None,
+        }
+    }
+}
+
+impl Cursor<String> {
+    /// `timestamp:key`, e.g. a transaction hash
+    pub fn encode(&self) -> String {
+        format!(
+            "{}:{}",
+            self.timestamp,
+            self.key.as_deref().unwrap_or_default()
+        )
+    }
+
+    pub fn parse(cursor: &str) -> Option<Self> {
+        let (timestamp, key) = cursor.split_once(':')?;
+        if key.is_empty() {
+            return None;
+        }
+        Some(Self::new(timestamp.parse().ok()?, key.to_lowercase()))
+    }
+}
+
+impl Cursor<SubstrateKey> {
+    /// `timestamp:block-index`
+    pub fn encode(&self) -> String {
+        let (block_number, index) = self.key.unwrap_or_default();
+        format!("{}:{}-{}", self.timestamp, block_number, index)
+    }
+
+    pub fn parse(cursor: &str) -> Option<Self> {
+        let (timestamp, key) = cursor.split_once(':')?;
+        let (block_number, index) = key.split_once('-')?;
+        Some(Self::new(
+            timestamp.parse().ok()?,
+            (block_number.parse().ok()?, index.parse().ok()?),
+        ))
+    }
+}
+
+/// One page of a newest-first listing, `next_cursor` continues after its last item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CursorPage<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+}
+
+/// Time range of an activity timeline in milliseconds
+#[derive(Debug, Clone, Default)]
+pub struct ActivityFilter {
+    pub from_timestamp: Option<u128>,
+    pub to_timestamp: Option<u128>,
+}
+
+/// Kinds of activity, in the order they are listed within the same timestamp (last first)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityKind {
+    EvmTransaction,
+    Extrinsic,
+    Event,
+}
+
+impl ActivityKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ActivityKind::EvmTransaction => "evm_transaction",
+            ActivityKind::Extrinsic => "extrinsic",
+            ActivityKind::Event => "event",
+        }
+    }
+
+    pub fn parse(kind: &str) -> Option<Self> {
+        match kind {
+            "evm_transaction" => Some(ActivityKind::EvmTransaction),
+            "extrinsic" => Some(ActivityKind::Extrinsic),
+            "event" => Some(ActivityKind::Event),
+            _ => None,
+        }
+    }
+}
+
+/// Tie-breaker of items sharing a timestamp and kind
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ActivityKey {
+    Hash(String),
+    Index(SubstrateKey),
+}
+
+/// Entry of an account's activity timeline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "data", rename_all = "snake_case")]
+pub enum AccountActivity {
+    EvmTransaction(EvmTransaction),
+    Extrinsic(SubstrateExtrinsic),
+    Event(SubstrateEvent),
+}
+
+impl AccountActivity {
+    pub fn kind(&self) -> ActivityKind {
+        match self {
+            AccountActivity::EvmTransaction(_) => ActivityKind::EvmTransaction,
+            AccountActivity::Extrinsic(_) => ActivityKind::Extrinsic,
+            AccountActivity::Event(_) => ActivityKind::Event,
+        }
+    }
+
+    /// Milliseconds; EVM blocks carry their timestamp in seconds
+    pub fn timestamp(&self) -> u128 {
+        match self {
+            AccountActivity::EvmTransaction(tx) => tx.timestamp * 1000,
+            AccountActivity::Extrinsic(extrinsic) => extrinsic.timestamp as u128,
+            AccountActivity::Event(event) => event.timestamp as u128,
+        }
+    }
+
+    pub fn key(&self) -> ActivityKey {
+        match self {
+            AccountActivity::EvmTransaction(tx) => ActivityKey::Hash(tx.hash.clone()),
+            AccountActivity::Extrinsic(extrinsic) => {
+                ActivityKey::Index((extrinsic.block_number, extrinsic.extrinsic_index))
+            }
+            AccountActivity::Event(event) => {
+                ActivityKey::Index((event.block_number, event.event_index))
+            }
+        }
+    }
+
+    /// Timeline order is descending on this key
+    pub fn sort_key(&self) -> (u128, ActivityKind, ActivityKey) {
+        (self.timestamp(), self.kind(), self.key())
+    }
+
+    pub fn cursor(&self) -> ActivityCursor {
+        ActivityCursor {
+            timestamp: self.timestamp(),
+            kind: self.kind(),
+            key: self.key(),
+        }
+    }
+}
+
+/// Position in an activity timeline, `timestamp:kind:key` for clients
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActivityCursor {
+    pub timestamp: u128,
+    pub kind: ActivityKind,
+    pub key: ActivityKey,
+}
+
+impl ActivityCursor {
+    pub fn encode(&self) -> String {
+        let key = match &self.key {
+            ActivityKey::Hash(hash) => hash.clone(),
+            ActivityKey::Index((block_number, index)) => format!("{}-{}", block_number, index),
+        };
+        format!("{}:{}:{}", self.timestamp, self.kind.as_str(), key)
+    }
+
+    pub fn parse(cursor: &str) -> Option<Self> {
+        let (timestamp, rest) = cursor.split_once(':')?;
+        let (kind, key) = rest.split_once(':')?;
+        let timestamp = timestamp.parse().ok()?;
+        let kind = ActivityKind::parse(kind)?;
+
+        let key = match kind {
+            ActivityKind::EvmTransaction if !key.is_empty() => {
+                ActivityKey::Hash(key.to_lowercase())
+            }
+            ActivityKind::EvmTransaction => return None,
+            ActivityKind::Extrinsic | ActivityKind::Event => {
+                let (block_number, index) = key.split_once('-')?;
+                ActivityKey::Index((block_number.parse().ok()?, index.parse().ok()?))
+            }
+        };
+
+        Some(Self {
+            timestamp,
+            kind,
+            key,
+        })
+    }
+
+    /// Millisecond cursor for one source of the timeline. Kinds listed before the
+    /// cursor's kind at its timestamp were already returned, later ones were not
+    fn bound(&self, kind: ActivityKind) -> (u128, Option<&ActivityKey>) {
+        if kind == self.kind {
+            (self.timestamp, Some(&self.key))
+        } else if kind > self.kind {
+            (self.timestamp, None)
+        } else {
+            (self.timestamp + 1, None)
+        }
+    }
+
+    /// Cursor over EVM transactions, whose timestamps are in seconds
+    pub fn evm_transactions(&self) -> Cursor<String> {
+        match self.bound(ActivityKind::EvmTransaction) {
+            (timestamp, Some(ActivityKey::Hash(hash))) => {
+                Cursor::new(timestamp / 1000, hash.clone())
+            }
+            (timestamp, _) => Cursor::before(timestamp.div_ceil(1000)),
+        }
+    }
+
+    pub fn extrinsics(&self) -> Cursor<SubstrateKey> {
+        Self::substrate_cursor(self.bound(ActivityKind::Extrinsic))
+    }
+
+    pub fn events(&self) -> Cursor<SubstrateKey> {
+        Self::substrate_cursor(self.bound(ActivityKind::Event))
+    }
+
+    fn substrate_cursor((timestamp, key): (u128, Option<&ActivityKey>)) -> Cursor<SubstrateKey> {
+        match key {
+            Some(ActivityKey::Index(key)) => Cursor::new(timestamp, *key),
+            _ => Cursor::before(timestamp),
+        }
+    }
+}
@@ -1,7 +1,11 @@
+use blockscan_model::amount::decimal_string;
 pub use blockscan_model::{
-    contract::{ContractCreationInfo, ContractType},
-    method::TransactionMethod,
+    contract::{ContractCreationInfo, ContractType, ProxyInfo, ProxyKind},
+    log::DecodedLog,
+    method::{DecodedCall, TransactionMethod},
+    token_transfer::TokenStandard,
     transaction::TransactionStatus,
+    verification::{SourceFile, VerificationRequest},
 };
 use serde::{Deserialize, Serialize};
 
@@ -18,7 +22,8 @@ pub struct EvmBlock {
     pub gas_used: u64,
     pub gas_limit: u64,
     pub base_fee: u64,
-    pub burn_fee: f64,
+    #[serde(deserialize_with = "decimal_string")]
+    pub burn_fee: String, // Exact amount in native token units
     pub validator: String,
     pub extra_data: String,
     pub nonce: Option<u32>,
@@ -26,6 +31,18 @@ pub struct EvmBlock {
     pub era: u32,
 }
 
+/// A block that was replaced by the canonical chain during a reorganization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvmOrphanedBlock {
+    pub number: u32,
+    pub hash: String,
+    pub parent_hash: String,
+    pub canonical_hash: Option<String>,
+    pub timestamp: u128,
+    pub transaction_hashes: Vec<String>,
+    pub orphaned_at: u128,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum TransactionType {
     Legacy = 0,
@@ -40,15 +57,144 @@ pub struct EvmTransaction {
     pub timestamp: u128,
     pub from: String,
     pub to: Option<String>,
-    pub value: u128,
+    #[serde(deserialize_with = "decimal_string")]
+    pub value: String, // Wei, decimal string
     pub gas_price: u64,
     pub gas_limit: u64,
     pub gas_used: u64,
     pub nonce: u64,
     pub status: TransactionStatus,
     pub transaction_type: TransactionType,
-    pub fee: u64,
+    #[serde(deserialize_with = "decimal_string")]
+    pub fee: String, // Wei, decimal string
     pub transaction_method: Option<TransactionMethod>,
+    #[serde(default)]
+    pub decoded_input: Option<DecodedCall>, // Only for calls to verified contracts
+}
+
+/// A single ERC20/ERC721/ERC1155 token movement taken from a transaction receipt log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenTransfer {
+    pub transaction_hash: String,
+    pub block_number: u64,
+    pub log_index: u64,
+    pub batch_index: u32,
+    pub token_address: String,
+    pub standard: TokenStandard,
+    pub operator: Option<String>,
+    pub from: String,
+    pub to: String,
+    pub value: Option<String>,
+    pub token_id: Option<String>,
+    pub timestamp: u128,
+}
+
+/// A call made by a contract during a transaction, taken from the transaction's call trace
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvmInternalTransaction {
+    pub transaction_hash: String,
+    pub block_number: u64,
+    pub trace_address: Vec<u32>,
+    pub call_type: String,
+    pub from: String,
+    pub to: Option<String>,
+    pub value: String,
+    pub gas: u64,
+    pub gas_used: u64,
+    pub input: String,
+    pub output: Option<String>,
+    pub error: Option<String>,
+    pub timestamp: u128,
+}
+
+/// A receipt log, topics are split out so they can be filtered like `eth_getLogs`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvmLog {
+    pub address: String,
+    pub topic0: Option<String>,
+    pub topic1: Option<String>,
+    pub topic2: Option<String>,
+    pub topic3: Option<String>,
+    pub data: String,
+    pub block_number: u64,
+    pub transaction_hash: String,
+    pub log_index: u64,
+    pub removed: bool, // Set when the block was orphaned by a reorganization
+    pub decoded: Option<DecodedLog>,
+    pub timestamp: u128,
+}
+
+/// Filter over indexed logs, every list is an OR of its values
+#[derive(Debug, Clone, Default)]
+pub struct EvmLogFilter {
+    pub addresses: Vec<String>,
+    pub topics: [Vec<String>; 4],
+    pub from_block: Option<u64>,
+    pub to_block: Option<u64>,
+}
+
+/// Side of a transaction an address is on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransactionDirection {
+    #[default]
+    All,
+    Sent,
+    Received,
+}
+
+/// Filter of the transactions sent or received by an address, `method` is a function
+/// name or a 4-byte selector
+#[derive(Debug, Clone, Default)]
+pub struct EvmAddressTxFilter {
+    pub direction: TransactionDirection,
+    pub method: Option<String>,
+    pub from_timestamp: Option<u128>,
+    pub to_timestamp: Option<u128>,
+}
+
+/// Filter of indexed token transfers, `address` matches either side of a transfer
+#[derive(Debug, Clone, Default)]
+pub struct TokenTransferFilter {
+    pub address: Option<String>,
+    pub token: Option<String>,
+    pub standards: Vec<TokenStandard>,
+    pub from_block: Option<u64>,
+    pub to_block: Option<u64>,
+}
+
+/// Balance of a token holder, ERC1155 balances are tracked per token id
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenBalance {
+    pub token_address: String,
+    pub holder: String,
+    pub token_id: Option<String>,
+    pub standard: TokenStandard,
+    pub balance: String, // Decimal string in the token's smallest unit
+    pub last_block: u64,
+    pub reconciled_at: Option<u128>, // Last time the balance was checked with `balanceOf`
+    pub updated_at: u128,
+}
+
+/// Holder of a token with its share of the total supply
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenHolder {
+    pub holder: String,
+    pub token_id: Option<String>,
+    pub balance: String,
+    pub percentage: Option<String>, // Decimal string with up to 6 decimal places
+}
+
+/// Token held by an account, with the token contract metadata
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenHolding {
+    pub token_address: String,
+    pub standard: TokenStandard,
+    pub token_id: Option<String>,
+    pub balance: String,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub decimals: Option<u8>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -61,4 +207,32 @@ pub struct EvmContract {
     pub total_supply: Option<String>,
     pub is_verified: bool,
     pub creator_info: Option<ContractCreationInfo>,
+    #[serde(default)]
+    pub proxy: Option<ProxyInfo>,
+}
+
+/// An implementation a proxy delegated to, starting at `block_number`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvmProxyImplementation {
+    pub proxy_address: String,
+    pub implementation: String,
+    pub kind: ProxyKind,
+    pub beacon: Option<String>,
+    pub block_number: u64,
+    pub transaction_hash: Option<String>, // None when found by inspecting the proxy storage
+    pub timestamp: u128,
+}
+
+/// Verified source code of a contract with the compiler output used to decode its calls and logs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvmContractSource {
+    pub address: String,
+    pub contract_name: String,
+    pub source_path: String,
+    pub compiler_version: String,
+    pub abi: serde_json::Value,
+    pub sources: Vec<SourceFile>,
+    pub settings: serde_json::Value, // Compiler settings without the output selection
+    pub metadata: Option<String>,    // Raw solc metadata JSON
+    pub verified_at: u128,
 }
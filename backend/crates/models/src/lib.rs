@@ -1,7 +1,14 @@
 use serde::{Deserialize, Serialize};
 
+pub mod activity;
 pub mod evm;
+pub mod live;
+pub mod search;
 pub mod substrate;
+pub mod webhook;
+
+use blockscan_model::amount::decimal_string;
+pub use blockscan_model::amount::{NATIVE_DECIMALS, format_units, parse_units};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AddressType {
@@ -12,11 +19,56 @@ pub enum AddressType {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccountInfo {
     pub address: String,
-    pub balance_token: f64,
-    pub free_balance: f64,
+    #[serde(deserialize_with = "decimal_string")]
+    pub balance_token: String, // Exact decimal amount in token units
+    #[serde(deserialize_with = "decimal_string")]
+    pub free_balance: String,
     pub nonce: u64,
     pub is_contract: bool,
     pub address_type: AddressType,
     pub created_at: u128,
     pub last_activity: u128,
 }
+
+/// How the two addresses of a link map onto each other
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AccountLinkKind {
+    /// `substrate_address` is `blake2("evm:" ++ evm_address)` and holds the EVM balance
+    EvmDerived,
+    /// `evm_address` is the first 20 bytes of `substrate_address`
+    SubstrateDerived,
+}
+
+/// Connection between the H160 and SS58 forms of one user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountLink {
+    pub evm_address: String,
+    pub substrate_address: String,
+    pub kind: AccountLinkKind,
+    pub created_at: u128,
+}
+
+/// Account seen through all of its linked addresses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkedAccount {
+    pub address: String,
+    pub display_name: Option<String>, // Identity of the first SS58 form that has one
+    pub evm_addresses: Vec<String>,
+    pub substrate_addresses: Vec<String>,
+    pub links: Vec<AccountLink>,
+    pub accounts: Vec<AccountInfo>,
+    pub balance_token: String, // Combined, funds shared by two forms are counted once
+    pub free_balance: String,
+}
+
+/// Progress checkpoint of a block indexer
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyncState {
+    pub indexer: String,
+    pub highest_contiguous_block: Option<u64>,
+    pub finalized_block: Option<u64>,
+    pub last_error: Option<String>,
+    pub link_cursor: Option<String>, // Last address linked by the account indexer's backfill
+    pub updated_at: u128,
+}
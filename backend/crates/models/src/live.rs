@@ -0,0 +1,344 @@
+use crate::{
+    SyncState,
+    evm::{EvmBlock, EvmTransaction, TokenTransfer},
+    substrate::{SubstrateBlock, SubstrateEvent, SubstrateExtrinsic},
+};
+use config::{EVM_INDEXER, SUBSTRATE_INDEXER};
+use std::fmt;
+
+/// A record an indexer has just created, pushed to API clients as it lands
+#[derive(Debug, Clone)]
+pub enum ChainEvent {
+    EvmBlock(EvmBlock),
+    EvmTransaction(EvmTransaction),
+    TokenTransfer(TokenTransfer),
+    SubstrateBlock(SubstrateBlock),
+    SubstrateExtrinsic(SubstrateExtrinsic),
+    SubstrateEvent(SubstrateEvent),
+    /// An indexed block that is now at or below the finalized height of its chain
+    FinalizedEvmBlock(EvmBlock),
+    FinalizedSubstrateBlock(SubstrateBlock),
+}
+
+impl ChainEvent {
+    pub fn block_number(&self) -> u64 {
+        match self {
+            ChainEvent::EvmBlock(block) | ChainEvent::FinalizedEvmBlock(block) => {
+                block.number as u64
+            }
+            ChainEvent::EvmTransaction(transaction) => transaction.block_number,
+            ChainEvent::TokenTransfer(transfer) => transfer.block_number,
+            ChainEvent::SubstrateBlock(block) | ChainEvent::FinalizedSubstrateBlock(block) => {
+                block.number as u64
+            }
+            ChainEvent::SubstrateExtrinsic(extrinsic) => extrinsic.block_number as u64,
+            ChainEvent::SubstrateEvent(event) => event.block_number as u64,
+        }
+    }
+
+    /// Identifies the underlying record, the same for every time it is read or re-indexed
+    pub fn record_id(&self) -> String {
+        match self {
+            ChainEvent::EvmBlock(block) => format!("evm_block:{}", block.hash),
+            ChainEvent::FinalizedEvmBlock(block) => format!("finalized_evm_block:{}", block.hash),
+            ChainEvent::EvmTransaction(transaction) => {
+                format!("evm_transaction:{}", transaction.hash)
+            }
+            ChainEvent::TokenTransfer(transfer) => format!(
+                "token_transfer:{}:{}:{}",
+                transfer.transaction_hash, transfer.log_index, transfer.batch_index
+            ),
+            ChainEvent::SubstrateBlock(block) => format!("substrate_block:{}", block.hash),
+            ChainEvent::FinalizedSubstrateBlock(block) => {
+                format!("finalized_substrate_block:{}", block.hash)
+            }
+            ChainEvent::SubstrateExtrinsic(extrinsic) => format!(
+                "substrate_extrinsic:{}:{}",
+                extrinsic.block_number, extrinsic.extrinsic_index
+            ),
+            ChainEvent::SubstrateEvent(event) => format!(
+                "substrate_event:{}:{}",
+                event.block_number, event.event_index
+            ),
+        }
+    }
+}
+
+/// Ordered sequence of records pushed to clients, each follows one indexer checkpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Feed {
+    /// Blocks, transactions and token transfers up to the contiguous EVM checkpoint
+    Evm,
+    /// Blocks, extrinsics and events up to the contiguous Substrate checkpoint
+    Substrate,
+    /// EVM blocks that are both indexed and finalized
+    FinalizedEvm,
+    FinalizedSubstrate,
+}
+
+impl Feed {
+    pub const ALL: [Feed; 4] = [
+        Feed::Evm,
+        Feed::Substrate,
+        Feed::FinalizedEvm,
+        Feed::FinalizedSubstrate,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Feed::Evm => "evm",
+            Feed::Substrate => "substrate",
+            Feed::FinalizedEvm => "finalized_evm",
+            Feed::FinalizedSubstrate => "finalized_substrate",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Feed> {
+        Self::ALL.into_iter().find(|feed| feed.name() == name)
+    }
+
+    /// Indexer whose checkpoint the feed follows
+    pub fn indexer(self) -> &'static str {
+        match self {
+            Feed::Evm | Feed::FinalizedEvm => EVM_INDEXER,
+            Feed::Substrate | Feed::FinalizedSubstrate => SUBSTRATE_INDEXER,
+        }
+    }
+
+    /// Highest block whose records may be pushed, every block up to it is fully indexed
+    pub fn height(self, state: &SyncState) -> Option<u64> {
+        let contiguous = state.highest_contiguous_block?;
+
+        match self {
+            Feed::Evm | Feed::Substrate => Some(contiguous),
+            Feed::FinalizedEvm | Feed::FinalizedSubstrate => {
+                Some(state.finalized_block?.min(contiguous))
+            }
+        }
+    }
+}
+
+/// Place of a record in its feed, handed to clients so they can resume after it.
+/// Written as `feed:block:position`, the position counting the records of the block
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EventCursor {
+    pub feed: Feed,
+    pub block_number: u64,
+    pub position: u32,
+}
+
+impl EventCursor {
+    /// Cursor after every record of `block_number`
+    pub fn end_of_block(feed: Feed, block_number: u64) -> Self {
+        Self {
+            feed,
+            block_number,
+            position: u32::MAX,
+        }
+    }
+
+    pub fn parse(cursor: &str) -> Option<EventCursor> {
+        let mut parts = cursor.split(':');
+        let feed = Feed::parse(parts.next()?)?;
+        let block_number = parts.next()?.parse().ok()?;
+        let position = parts.next()?.parse().ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+
+        Some(EventCursor {
+            feed,
+            block_number,
+            position,
+        })
+    }
+}
+
+impl fmt::Display for EventCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}",
+            self.feed.name(),
+            self.block_number,
+            self.position
+        )
+    }
+}
+
+/// A record with its place in the feed it was read for
+#[derive(Debug, Clone)]
+pub struct PushedEvent {
+    pub cursor: EventCursor,
+    pub event: ChainEvent,
+}
+
+/// Numbers the records of a feed block by block. `records` lists every kind of record
+/// in its own order inside a block, a stable sort by block keeps that order
+pub fn sequence(feed: Feed, mut records: Vec<ChainEvent>) -> Vec<PushedEvent> {
+    records.sort_by_key(ChainEvent::block_number);
+
+    let mut position = 0;
+    let mut previous = None;
+    records
+        .into_iter()
+        .map(|event| {
+            let block_number = event.block_number();
+            if previous != Some(block_number) {
+                previous = Some(block_number);
+                position = 0;
+            }
+            let cursor = EventCursor {
+                feed,
+                block_number,
+                position,
+            };
+            position += 1;
+
+            PushedEvent { cursor, event }
+        })
+        .collect()
+}
+
+/// EVM addresses are compared ignoring case, SS58 addresses are case sensitive
+pub fn same_address(filter: &str, address: &str) -> bool {
+    if filter.starts_with("0x") {
+        filter.eq_ignore_ascii_case(address)
+    } else {
+        filter == address
+    }
+}
+
+/// Pallet, call or event name filter, matching any name when unset and ignoring case
+pub fn name_matches(filter: &Option<String>, name: &str) -> bool {
+    filter
+        .as_ref()
+        .is_none_or(|filter| filter.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transfer(block_number: u64, log_index: u64) -> ChainEvent {
+        ChainEvent::TokenTransfer(TokenTransfer {
+            transaction_hash: "0xabc".to_string(),
+            block_number,
+            log_index,
+            batch_index: 0,
+            token_address: "0xtoken".to_string(),
+            standard: crate::evm::TokenStandard::ERC20,
+            operator: None,
+            from: "0xfrom".to_string(),
+            to: "0xto".to_string(),
+            value: Some("1".to_string()),
+            token_id: None,
+            timestamp: 0,
+        })
+    }
+
+    #[test]
+    fn cursors_round_trip() {
+        let cursor = EventCursor {
+            feed: Feed::FinalizedSubstrate,
+            block_number: 1234,
+            position: 7,
+        };
+
+        assert_eq!(cursor.to_string(), "finalized_substrate:1234:7");
+        assert_eq!(EventCursor::parse(&cursor.to_string()), Some(cursor));
+        assert_eq!(EventCursor::parse("evm:12"), None);
+        assert_eq!(EventCursor::parse("evm:12:3:4"), None);
+        assert_eq!(EventCursor::parse("bitcoin:12:3"), None);
+    }
+
+    #[test]
+    fn cursors_order_by_block_then_position() {
+        let at = |block_number, position| EventCursor {
+            feed: Feed::Evm,
+            block_number,
+            position,
+        };
+
+        assert!(at(10, 5) < at(11, 0));
+        assert!(at(10, 0) < at(10, 1));
+        assert!(at(10, 5) < EventCursor::end_of_block(Feed::Evm, 10));
+    }
+
+    #[test]
+    fn records_are_numbered_per_block_keeping_their_order() {
+        let records = vec![
+            transfer(11, 0),
+            transfer(10, 4),
+            transfer(10, 2),
+            transfer(11, 1),
+        ];
+
+        let sequenced: Vec<_> = sequence(Feed::Evm, records)
+            .into_iter()
+            .map(|pushed| match pushed.event {
+                ChainEvent::TokenTransfer(transfer) => (
+                    pushed.cursor.block_number,
+                    pushed.cursor.position,
+                    transfer.log_index,
+                ),
+                _ => unreachable!(),
+            })
+            .collect();
+
+        assert_eq!(
+            sequenced,
+            vec![(10, 0, 4), (10, 1, 2), (11, 0, 0), (11, 1, 1)]
+        );
+    }
+
+    #[test]
+    fn record_ids_do_not_depend_on_the_read() {
+        assert_eq!(transfer(10, 4).record_id(), "token_transfer:0xabc:4:0");
+        assert_eq!(transfer(10, 4).record_id(), transfer(10, 4).record_id());
+        assert_ne!(transfer(10, 4).record_id(), transfer(10, 5).record_id());
+    }
+
+    #[test]
+    fn finalized_feeds_wait_for_both_checkpoints() {
+        let state = SyncState {
+            highest_contiguous_block: Some(100),
+            finalized_block: Some(120),
+            ..Default::default()
+        };
+
+        assert_eq!(Feed::Evm.height(&state), Some(100));
+        assert_eq!(Feed::FinalizedEvm.height(&state), Some(100));
+        assert_eq!(
+            Feed::FinalizedEvm.height(&SyncState {
+                finalized_block: Some(90),
+                ..state.clone()
+            }),
+            Some(90)
+        );
+        assert_eq!(Feed::FinalizedEvm.height(&SyncState::default()), None);
+    }
+
+    #[test]
+    fn evm_addresses_match_ignoring_case() {
+        assert!(same_address(
+            "0x742D35CC6634C0532925A3B844BC454E4438F44E",
+            "0x742d35cc6634c0532925a3b844bc454e4438f44e"
+        ));
+        assert!(!same_address("0x742d", "0x742e"));
+    }
+
+    #[test]
+    fn ss58_addresses_are_case_sensitive() {
+        let alice = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+        assert!(same_address(alice, alice));
+        assert!(!same_address(&alice.to_lowercase(), alice));
+    }
+
+    #[test]
+    fn unset_name_filter_matches_everything() {
+        assert!(name_matches(&None, "Transfer"));
+        assert!(name_matches(&Some("transfer".to_string()), "Transfer"));
+        assert!(!name_matches(&Some("Transfer".to_string()), "Deposit"));
+    }
+}
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AccountInfo,
+    evm::{EvmBlock, EvmContract, EvmTransaction},
+    substrate::{Identity, SubstrateBlock},
+};
+
+/// What a search input was recognised as
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchQueryKind {
+    BlockNumber,
+    Hash,
+    EvmAddress,
+    Ss58Address,
+    Text,
+}
+
+/// An address found by search, with its form on the other chain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressMatch {
+    pub address: String,
+    pub display_name: Option<String>,
+    pub linked_address: Option<String>, // EVM form of an SS58 address or SS58 form of an EVM one
+    pub account: Option<AccountInfo>,   // None when the address has no indexed activity
+}
+
+/// A record matching a search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "data", rename_all = "snake_case")]
+pub enum SearchMatch {
+    EvmBlock(EvmBlock),
+    SubstrateBlock(SubstrateBlock),
+    EvmTransaction(EvmTransaction),
+    Token(EvmContract),
+    Contract(EvmContract),
+    EvmAccount(AddressMatch),
+    SubstrateAccount(AddressMatch),
+    Identity(Identity),
+}
+
+/// A search match, results are sorted by descending `score`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResult {
+    pub score: u32,
+    #[serde(flatten)]
+    pub item: SearchMatch,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResults {
+    pub query: String,
+    pub query_kind: SearchQueryKind,
+    pub results: Vec<SearchResult>,
+}
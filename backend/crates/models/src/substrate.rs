@@ -1,3 +1,7 @@
+use blockscan_model::{
+    amount::{option_u128_string, u128_string},
+    validator::ValidatorType,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,8 +33,17 @@ pub struct SubstrateExtrinsic {
     pub signer: Option<String>,
     pub call_module: String,
     pub call_function: String,
-    pub args: String, // JSON serialized arguments
+    pub args: serde_json::Value,
+    pub success: bool,
+    pub dispatch_error: Option<String>,
+    #[serde(default, with = "option_u128_string")]
+    pub actual_fee: Option<u128>,
+    #[serde(default, with = "option_u128_string")]
+    pub tip: Option<u128>,
+    pub weight: Option<u64>,
     pub timestamp: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signer_display: Option<String>, // Identity of the signer, filled in by the API
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,8 +51,245 @@ pub struct SubstrateEvent {
     pub block_number: u32,
     pub event_index: u32,
     pub phase: String,
+    pub extrinsic_index: Option<u32>,
     pub module: String,
     pub event: String,
-    pub data: String, // JSON serialized event data
+    pub data: serde_json::Value,
     pub timestamp: u64,
+    #[serde(default)]
+    pub accounts: Vec<String>, // SS58 accounts mentioned in `data`
+}
+
+/// Filter of the extrinsics or events of an account, `name` is the call or event name
+#[derive(Debug, Clone, Default)]
+pub struct SubstrateAccountFilter {
+    pub module: Option<String>,
+    pub name: Option<String>,
+    pub from_timestamp: Option<u64>,
+    pub to_timestamp: Option<u64>,
+}
+
+/// Filter of events by one of their decoded fields. The field name is checked once here,
+/// since it ends up in the query text
+#[derive(Debug, Clone)]
+pub struct EventFieldFilter {
+    field: String,
+    value: String,
+}
+
+impl EventFieldFilter {
+    /// `None` unless the field is a plain identifier such as `from` or `amount`
+    pub fn new(field: &str, value: &str) -> Option<Self> {
+        let valid = !field.is_empty()
+            && !field.starts_with(|c: char| c.is_ascii_digit())
+            && field.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+        valid.then(|| Self {
+            field: field.to_string(),
+            value: value.to_string(),
+        })
+    }
+
+    pub fn field(&self) -> &str {
+        &self.field
+    }
+
+    /// Values the field may be stored as: always the text, plus the number for integers
+    /// narrower than 128 bits, which decode as JSON numbers
+    pub fn values(&self) -> Vec<serde_json::Value> {
+        let mut values = vec![serde_json::Value::String(self.value.clone())];
+        if let Ok(n) = self.value.parse::<u64>() {
+            values.push(n.into());
+        } else if let Ok(n) = self.value.parse::<i64>() {
+            values.push(n.into());
+        }
+        values
+    }
+}
+
+/// Native balance transfer, from a `Balances.Transfer` event or a transfer call of a
+/// failed extrinsic
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubstrateTransfer {
+    pub block_number: u32,
+    pub transfer_index: u32, // Position of the transfer within the block
+    pub extrinsic_index: Option<u32>,
+    pub event_index: Option<u32>, // None for a transfer that failed
+    pub from: String,
+    pub to: String,
+    #[serde(with = "u128_string")]
+    pub amount: u128,
+    pub success: bool,
+    #[serde(default, with = "option_u128_string")]
+    pub fee: Option<u128>, // Fee of the whole extrinsic
+    pub timestamp: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_display: Option<String>, // Identities, filled in by the API
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_display: Option<String>,
+}
+
+/// Reward paid out to a validator or nominator stash for an era
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StakingReward {
+    pub era: u32,
+    pub validator: String,
+    pub stash: String,
+    pub is_validator: bool, // The validator's own reward rather than a nominator's
+    #[serde(with = "u128_string")]
+    pub amount: u128,
+    pub block_number: u32,
+    pub event_index: u32,
+    pub extrinsic_index: Option<u32>,
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StakingSlash {
+    pub era: u32, // Active era when the slash was applied
+    pub staker: String,
+    #[serde(with = "u128_string")]
+    pub amount: u128,
+    pub block_number: u32,
+    pub event_index: u32,
+    pub timestamp: u64,
+}
+
+/// Network-wide payout of an era, from `Staking.EraPaid`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EraPayout {
+    pub era: u32,
+    #[serde(with = "u128_string")]
+    pub validator_payout: u128,
+    #[serde(with = "u128_string")]
+    pub remainder: u128,
+    pub total_points: u32,
+    pub block_number: u32,
+    pub timestamp: u64,
+}
+
+/// Reward points of a validator in an era and its share of the era's validator payout
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EraValidatorPoints {
+    pub era: u32,
+    pub validator: String,
+    pub points: u32,
+    pub commission: f64, // Percent
+    #[serde(with = "u128_string")]
+    pub reward: u128, // validator_payout * points / total_points, before commission
+    pub block_number: u32,
+    pub timestamp: u64,
+}
+
+/// Judgement of a registrar on an identity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdentityJudgement {
+    pub registrar_index: u32,
+    pub judgement: String, // `Reasonable`, `KnownGood`, `FeePaid`, ...
+}
+
+/// On-chain identity of an account, from `Identity.IdentityOf` and `Identity.SuperOf`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Identity {
+    pub account: String,
+    pub display_name: Option<String>, // `display`, or `parent display/sub name` for a sub-identity
+    pub display: Option<String>,
+    pub legal: Option<String>,
+    pub web: Option<String>,
+    pub email: Option<String>,
+    pub twitter: Option<String>,
+    pub riot: Option<String>,
+    pub username: Option<String>, // Primary username
+    pub judgements: Vec<IdentityJudgement>,
+    pub parent: Option<String>, // Super-identity of a sub-identity
+    pub sub_name: Option<String>,
+    pub block_number: u32, // Block the identity was read at
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StakingRole {
+    Validator,
+    Nominator,
+    Idle, // Bonded without validating or nominating
+}
+
+/// Funds leaving the bond, withdrawable from `era`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnlockingChunk {
+    #[serde(with = "u128_string")]
+    pub value: u128,
+    pub era: u32,
+}
+
+/// Bond and nominations of a stash, from `Staking.Bonded`, `Staking.Ledger` and `Staking.Nominators`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StakingLedger {
+    pub stash: String,
+    pub controller: String,
+    pub role: StakingRole,
+    #[serde(with = "u128_string")]
+    pub bonded: u128, // Active plus unlocking
+    #[serde(with = "u128_string")]
+    pub active: u128,
+    pub unlocking: Vec<UnlockingChunk>,
+    pub nominations: Vec<String>, // Validator stashes nominated
+    pub nominations_submitted_in: Option<u32>,
+    pub block_number: u32, // Block the ledger was read at
+    pub timestamp: u64,
+}
+
+/// Blocks a validator produced in one session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionBlocks {
+    pub session: u32,
+    pub blocks: u32,
+}
+
+/// A validator in an era, refreshed at the end of each session of the era
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidatorSnapshot {
+    pub era: u32,
+    pub account_id: String,
+    pub validator_type: ValidatorType,
+    pub commission: f64, // Percent
+    pub blocked: bool,
+    #[serde(with = "u128_string")]
+    pub total_stake: u128,
+    #[serde(with = "u128_string")]
+    pub own_stake: u128,
+    pub nominator_count: u32,
+    pub blocks_produced: u32, // Sum of `session_blocks`
+    pub session_blocks: Vec<SessionBlocks>,
+    pub block_number: u32, // Last block the snapshot was taken at
+    pub timestamp: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>, // Identity, filled in by the API
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn event_field_names() {
+        assert!(EventFieldFilter::new("from", "x").is_some());
+        assert!(EventFieldFilter::new("who_2", "x").is_some());
+        assert!(EventFieldFilter::new("", "x").is_none());
+        assert!(EventFieldFilter::new("2nd", "x").is_none());
+        assert!(EventFieldFilter::new("from.to", "x").is_none());
+        assert!(EventFieldFilter::new("a = 1 OR 1", "x").is_none());
+    }
+
+    #[test]
+    fn event_field_values() {
+        let filter = EventFieldFilter::new("amount", "42").unwrap();
+        assert_eq!(filter.values(), vec![json!("42"), json!(42)]);
+
+        let filter = EventFieldFilter::new("delta", "-3").unwrap();
+        assert_eq!(filter.values(), vec![json!("-3"), json!(-3)]);
+
+        let filter = EventFieldFilter::new("who", "5Grw").unwrap();
+        assert_eq!(filter.values(), vec![json!("5Grw")]);
+    }
 }
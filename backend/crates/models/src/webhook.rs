@@ -0,0 +1,159 @@
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+
+/// What a webhook is notified about
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WebhookFilter {
+    /// Transactions, token transfers and extrinsics sent, received or signed by
+    /// the address, and Substrate events that mention it
+    AddressActivity { address: String },
+    /// Token transfers of at least `min_value` smallest units, of one token when set
+    TokenTransfer {
+        #[serde(default)]
+        token: Option<String>,
+        #[serde(default)]
+        min_value: Option<String>,
+    },
+    /// Substrate events of a pallet, of one event name when set
+    PalletEvent {
+        pallet: String,
+        #[serde(default)]
+        event: Option<String>,
+    },
+    /// `Staking.Slashed` for the validator, or for any staker when unset
+    ValidatorSlashed {
+        #[serde(default)]
+        validator: Option<String>,
+    },
+    /// `CommitteeManagement.BanValidators` for underperformance, which removes the
+    /// validator from the committee
+    ValidatorOffline {
+        #[serde(default)]
+        validator: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Webhook {
+    pub id: String,
+    pub url: String,
+    /// HMAC-SHA256 key of the `X-Webhook-Signature` header, only returned on registration
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub secret: String,
+    pub filter: WebhookFilter,
+    pub description: Option<String>,
+    pub created_at: u128,
+    /// Hex SHA-256 of the token the webhook was registered with, never returned
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub owner: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    /// Waiting for its first attempt or a retry at `next_attempt_at`
+    Pending,
+    Delivered,
+    /// Out of retries, a copy is kept in the dead-letter table
+    DeadLetter,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeliveryAttempt {
+    pub attempt: u32,
+    pub attempted_at: u128,
+    pub response_status: Option<u16>,
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+/// One payload sent to one webhook, with every attempt made to deliver it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub id: String,
+    pub webhook_id: String,
+    pub url: String,
+    pub event: String, // evm_transaction, token_transfer, substrate_extrinsic or substrate_event
+    pub payload: serde_json::Value,
+    pub status: DeliveryStatus,
+    pub attempts: Vec<DeliveryAttempt>,
+    pub next_attempt_at: u128,
+    pub created_at: u128,
+    pub delivered_at: Option<u128>,
+}
+
+/// Whether a webhook may be delivered to the address: loopback, private, link-local,
+/// shared, multicast and reserved ranges are refused so a webhook cannot reach internal services
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || ip.is_documentation()
+                || a == 0
+                || a >= 240
+                || (a == 100 && (64..128).contains(&b)) // Carrier-grade NAT
+                || (a == 198 && (b == 18 || b == 19))) // Benchmarking
+        }
+        IpAddr::V6(ip) => {
+            if let Some(ip) = ip.to_ipv4_mapped() {
+                return is_public_ip(IpAddr::V4(ip));
+            }
+
+            let first = ip.segments()[0];
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                || (first & 0xfe00) == 0xfc00 // Unique local
+                || (first & 0xffc0) == 0xfe80) // Link-local
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn public(ip: &str) -> bool {
+        is_public_ip(ip.parse().unwrap())
+    }
+
+    #[test]
+    fn internal_ipv4_ranges_are_refused() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "255.255.255.255",
+        ] {
+            assert!(!public(ip), "{}", ip);
+        }
+        assert!(public("93.184.216.34"));
+        assert!(public("100.128.0.1"));
+    }
+
+    #[test]
+    fn internal_ipv6_ranges_are_refused() {
+        for ip in [
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "ff02::1",
+        ] {
+            assert!(!public(ip), "{}", ip);
+        }
+        assert!(public("2606:2800:220:1:248:1893:25c8:1946"));
+    }
+}
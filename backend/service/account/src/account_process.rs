@@ -1,9 +1,25 @@
-use config::{GENESIS_TIMESTAMP, selendra};
+use blockscan::{evm_to_ss58, ss58_to_evm_address};
+use config::{ACCOUNT_INDEXER, GENESIS_TIMESTAMP, SS58_PREFIX, selendra};
 use custom_error::ServiceError;
 use database::DatabaseService;
-use models::{AccountInfo, AddressType};
+use models::{AccountInfo, AccountLinkKind, AddressType, NATIVE_DECIMALS, format_units};
 use sp_core::crypto::{AccountId32, Ss58Codec};
-use subxt::{OnlineClient, SubstrateConfig};
+use subxt::{OnlineClient, SubstrateConfig, blocks::Block, storage::Storage};
+
+pub(crate) type FinalizedBlock = Block<SubstrateConfig, OnlineClient<SubstrateConfig>>;
+pub(crate) type StorageAt = Storage<SubstrateConfig, OnlineClient<SubstrateConfig>>;
+
+pub(crate) fn substrate_error(context: &str, e: subxt::Error) -> ServiceError {
+    ServiceError::SubstrateError(format!("{}: {:?}", context, e))
+}
+
+/// Account id at the end of a `Twox64Concat` storage key
+pub(crate) fn key_account(key_bytes: &[u8]) -> Option<[u8; 32]> {
+    key_bytes
+        .len()
+        .checked_sub(32)
+        .and_then(|start| <[u8; 32]>::try_from(&key_bytes[start..]).ok())
+}
 
 #[derive(Clone)]
 pub struct AccountProcessingService {
@@ -72,8 +88,8 @@ impl AccountProcessingService {
                             .update_account(
                                 &account_address,
                                 None,
-                                Some(balance_token),
-                                Some(free_balance),
+                                Some(&balance_token),
+                                Some(&free_balance),
                             )
                             .await?;
                     } else {
@@ -91,21 +107,154 @@ impl AccountProcessingService {
 
                         self.db_service.accounts().save(&account).await?;
                     }
+
+                    self.db_service
+                        .account_links()
+                        .link(
+                            &ss58_to_evm_address(&account_address)?,
+                            &account_address,
+                            AccountLinkKind::SubstrateDerived,
+                        )
+                        .await?;
                 }
             }
         }
 
         println!("✅ Saved all account success");
 
+        self.link_evm_accounts().await?;
+
+        Ok(())
+    }
+
+    /// Latest finalized block, the scans read their state at it and following starts after it
+    pub async fn finalized_block(&self) -> Result<FinalizedBlock, ServiceError> {
+        let block_ref = self
+            .api
+            .backend()
+            .latest_finalized_block_ref()
+            .await
+            .map_err(|e| substrate_error("Error getting finalized head", e))?;
+
+        self.api
+            .blocks()
+            .at(block_ref)
+            .await
+            .map_err(|e| substrate_error("Error getting finalized block", e))
+    }
+
+    /// Follows the finalized blocks after `from_block`, keeping staking ledgers and
+    /// identities up to date. Every block is applied in order; one that fails is replayed
+    /// when the next finalized block arrives.
+    pub async fn follow_finalized(&self, from_block: u32) -> Result<(), ServiceError> {
+        let mut blocks = self
+            .api
+            .blocks()
+            .subscribe_finalized()
+            .await
+            .map_err(|e| substrate_error("Error subscribing to finalized blocks", e))?;
+
+        let mut processed = from_block;
+        while let Some(block) = blocks.next().await {
+            let block = block.map_err(|e| substrate_error("Error getting finalized block", e))?;
+            if block.number() <= processed {
+                continue;
+            }
+
+            // Notifications can skip blocks, walk back through the parents to the last one applied
+            let mut pending = vec![block];
+            while let Some(oldest) = pending
+                .last()
+                .filter(|block| block.number() > processed + 1)
+            {
+                let parent = self
+                    .api
+                    .blocks()
+                    .at(oldest.header().parent_hash)
+                    .await
+                    .map_err(|e| substrate_error("Error getting parent block", e))?;
+                pending.push(parent);
+            }
+
+            for block in pending.iter().rev() {
+                if let Err(e) = self.process_finalized_block(block).await {
+                    println!(
+                        "❌ Failed to update accounts at block {}, retrying with the next block: {}",
+                        block.number(),
+                        e
+                    );
+                    break;
+                }
+                processed = block.number();
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn process_finalized_block(&self, block: &FinalizedBlock) -> Result<(), ServiceError> {
+        let events = block
+            .events()
+            .await
+            .map_err(|e| substrate_error("Error getting block events", e))?;
+
+        self.process_staking_block(block, &events).await?;
+        self.process_identity_block(block, &events).await
+    }
+
+    /// Links every indexed EVM address to the SS58 account holding its native balance,
+    /// covering addresses indexed before links existed. New addresses are linked by the EVM
+    /// indexer, so the persisted cursor lets a restart skip what was already linked.
+    async fn link_evm_accounts(&self) -> Result<(), ServiceError> {
+        const PAGE_SIZE: u32 = 1_000;
+        let sync_state = self.db_service.sync_state();
+        let mut cursor = sync_state
+            .get(ACCOUNT_INDEXER)
+            .await?
+            .and_then(|state| state.link_cursor);
+        let mut linked = 0;
+
+        loop {
+            let evm_addresses = self
+                .db_service
+                .accounts()
+                .get_addresses_by_type(&AddressType::H160, cursor.as_deref(), PAGE_SIZE)
+                .await?;
+
+            for evm_address in &evm_addresses {
+                self.db_service
+                    .account_links()
+                    .link(
+                        evm_address,
+                        &evm_to_ss58(evm_address)?,
+                        AccountLinkKind::EvmDerived,
+                    )
+                    .await?;
+            }
+            linked += evm_addresses.len();
+
+            let Some(last) = evm_addresses.last() else {
+                break;
+            };
+            sync_state.set_link_cursor(ACCOUNT_INDEXER, last).await?;
+            cursor = Some(last.clone());
+
+            if evm_addresses.len() < PAGE_SIZE as usize {
+                break;
+            }
+        }
+
+        println!("🔗 Linked {} EVM accounts", linked);
+
         Ok(())
     }
 
-    fn account_id_to_ss58(account_id: &[u8; 32]) -> String {
+    pub(crate) fn account_id_to_ss58(account_id: &[u8; 32]) -> String {
         let account = AccountId32::from(*account_id);
-        account.to_ss58check_with_version(sp_core::crypto::Ss58AddressFormat::custom(42))
+        account.to_ss58check_with_version(sp_core::crypto::Ss58AddressFormat::custom(SS58_PREFIX))
     }
 
-    fn format_balance(balance: u128) -> f64 {
-        balance as f64 / 1e18
+    fn format_balance(balance: u128) -> String {
+        format_units(balance, NATIVE_DECIMALS)
     }
 }
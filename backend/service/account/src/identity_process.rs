@@ -0,0 +1,320 @@
+use std::collections::{HashMap, HashSet};
+
+use config::selendra::{
+    self,
+    identity::events::{
+        IdentityCleared, IdentityKilled, IdentitySet, JudgementGiven, JudgementUnrequested,
+        PrimaryUsernameSet, SubIdentityAdded, SubIdentityRemoved, SubIdentityRevoked, UsernameSet,
+    },
+    runtime_types::pallet_identity::{
+        legacy::IdentityInfo,
+        types::{Data, Judgement},
+    },
+};
+use custom_error::ServiceError;
+use models::substrate::{Identity, IdentityJudgement};
+use subxt::{SubstrateConfig, events::Events, ext::codec::Encode, utils::AccountId32};
+
+use crate::account_process::{
+    AccountProcessingService, FinalizedBlock, StorageAt, key_account, substrate_error,
+};
+
+/// Text of a raw `Data` field, hashed and empty fields have none
+fn data_text(data: &Data) -> Option<String> {
+    // `Raw` variants are encoded as `1 + len` followed by the bytes
+    match data.encode().split_first() {
+        Some((1..=33, raw)) if !raw.is_empty() => Some(String::from_utf8_lossy(raw).into_owned()),
+        _ => None,
+    }
+}
+
+fn judgement_name(judgement: &Judgement<u128>) -> &'static str {
+    match judgement {
+        Judgement::Unknown => "Unknown",
+        Judgement::FeePaid(_) => "FeePaid",
+        Judgement::Reasonable => "Reasonable",
+        Judgement::KnownGood => "KnownGood",
+        Judgement::OutOfDate => "OutOfDate",
+        Judgement::LowQuality => "LowQuality",
+        Judgement::Erroneous => "Erroneous",
+    }
+}
+
+/// Identity record made of an account's own `IdentityOf` entry
+fn own_identity(
+    account: String,
+    info: &IdentityInfo,
+    judgements: &[(u32, Judgement<u128>)],
+    username: Option<&[u8]>,
+    block_number: u32,
+) -> Identity {
+    let display = data_text(&info.display);
+
+    Identity {
+        account,
+        display_name: display.clone(),
+        display,
+        legal: data_text(&info.legal),
+        web: data_text(&info.web),
+        email: data_text(&info.email),
+        twitter: data_text(&info.twitter),
+        riot: data_text(&info.riot),
+        username: username.map(|username| String::from_utf8_lossy(username).into_owned()),
+        judgements: judgements
+            .iter()
+            .map(|(registrar_index, judgement)| IdentityJudgement {
+                registrar_index: *registrar_index,
+                judgement: judgement_name(judgement).to_string(),
+            })
+            .collect(),
+        parent: None,
+        sub_name: None,
+        block_number,
+    }
+}
+
+/// Adds the super-identity of a sub account, shown as `parent display/sub name`
+fn with_parent(
+    identity: Option<Identity>,
+    account: String,
+    parent: String,
+    parent_display: Option<&str>,
+    sub_name: Option<String>,
+    block_number: u32,
+) -> Identity {
+    let mut identity = identity.unwrap_or(Identity {
+        account,
+        display_name: None,
+        display: None,
+        legal: None,
+        web: None,
+        email: None,
+        twitter: None,
+        riot: None,
+        username: None,
+        judgements: Vec::new(),
+        parent: None,
+        sub_name: None,
+        block_number,
+    });
+
+    if let Some(parent_display) = parent_display {
+        identity.display_name = Some(match &sub_name {
+            Some(sub_name) => format!("{}/{}", parent_display, sub_name),
+            None => parent_display.to_string(),
+        });
+    }
+    identity.parent = Some(parent);
+    identity.sub_name = sub_name;
+    identity
+}
+
+// On-chain identities, scanned once and refreshed from identity events
+impl AccountProcessingService {
+    /// Iterates `Identity.IdentityOf` and `Identity.SuperOf` at a finalized block, stale
+    /// identities are only deleted after both complete
+    pub async fn process_identities(&self, block: &FinalizedBlock) -> Result<(), ServiceError> {
+        let storage = block.storage();
+        let block_number = block.number();
+
+        let mut identities: HashMap<[u8; 32], Identity> = HashMap::new();
+
+        let mut iter = storage
+            .iter(selendra::storage().identity().identity_of_iter())
+            .await
+            .map_err(|e| substrate_error("Error getting identities", e))?;
+        while let Some(kv) = iter.next().await {
+            let kv = kv.map_err(|e| substrate_error("Error reading identities", e))?;
+            let Some(account) = key_account(&kv.key_bytes) else {
+                continue;
+            };
+
+            let (registration, username) = kv.value;
+            identities.insert(
+                account,
+                own_identity(
+                    Self::account_id_to_ss58(&account),
+                    &registration.info,
+                    &registration.judgements.0,
+                    username.as_ref().map(|username| username.0.as_slice()),
+                    block_number,
+                ),
+            );
+        }
+
+        let mut subs = Vec::new();
+        let mut iter = storage
+            .iter(selendra::storage().identity().super_of_iter())
+            .await
+            .map_err(|e| substrate_error("Error getting sub-identities", e))?;
+        while let Some(kv) = iter.next().await {
+            let kv = kv.map_err(|e| substrate_error("Error reading sub-identities", e))?;
+            if let Some(account) = key_account(&kv.key_bytes) {
+                subs.push((account, kv.value));
+            }
+        }
+
+        for (account, (parent, sub_name)) in subs {
+            let parent_display = identities
+                .get(&parent.0)
+                .and_then(|identity| identity.display.clone());
+            let identity = with_parent(
+                identities.remove(&account),
+                Self::account_id_to_ss58(&account),
+                Self::account_id_to_ss58(&parent.0),
+                parent_display.as_deref(),
+                data_text(&sub_name),
+                block_number,
+            );
+            identities.insert(account, identity);
+        }
+
+        for identity in identities.values() {
+            self.db_service.identities().save(identity).await?;
+        }
+        self.db_service
+            .identities()
+            .delete_stale(block_number)
+            .await?;
+
+        println!(
+            "✅ Saved {} identities at block {}",
+            identities.len(),
+            block_number
+        );
+
+        Ok(())
+    }
+
+    /// Refreshes the accounts whose identity, judgements, username or sub-identities changed
+    pub(crate) async fn process_identity_block(
+        &self,
+        block: &FinalizedBlock,
+        events: &Events<SubstrateConfig>,
+    ) -> Result<(), ServiceError> {
+        let mut accounts = HashSet::new();
+        // Accounts whose display name is shown by their sub-identities
+        let mut parents = HashSet::new();
+
+        for event in events.iter() {
+            let event = event.map_err(|e| substrate_error("Error decoding event", e))?;
+            if event.pallet_name() != "Identity" {
+                continue;
+            }
+
+            if let Ok(Some(set)) = event.as_event::<IdentitySet>() {
+                parents.insert(set.who.0);
+            } else if let Ok(Some(cleared)) = event.as_event::<IdentityCleared>() {
+                parents.insert(cleared.who.0);
+            } else if let Ok(Some(killed)) = event.as_event::<IdentityKilled>() {
+                parents.insert(killed.who.0);
+            } else if let Ok(Some(given)) = event.as_event::<JudgementGiven>() {
+                accounts.insert(given.target.0);
+            } else if let Ok(Some(unrequested)) = event.as_event::<JudgementUnrequested>() {
+                accounts.insert(unrequested.who.0);
+            } else if let Ok(Some(username)) = event.as_event::<UsernameSet>() {
+                accounts.insert(username.who.0);
+            } else if let Ok(Some(username)) = event.as_event::<PrimaryUsernameSet>() {
+                accounts.insert(username.who.0);
+            } else if let Ok(Some(added)) = event.as_event::<SubIdentityAdded>() {
+                accounts.insert(added.sub.0);
+            } else if let Ok(Some(removed)) = event.as_event::<SubIdentityRemoved>() {
+                accounts.insert(removed.sub.0);
+            } else if let Ok(Some(revoked)) = event.as_event::<SubIdentityRevoked>() {
+                accounts.insert(revoked.sub.0);
+            }
+        }
+
+        if accounts.is_empty() && parents.is_empty() {
+            return Ok(());
+        }
+
+        let storage = block.storage();
+        for parent in parents {
+            let subs = storage
+                .fetch(&selendra::storage().identity().subs_of(AccountId32(parent)))
+                .await
+                .map_err(|e| substrate_error("Error getting sub-identities", e))?;
+            if let Some((_, subs)) = subs {
+                accounts.extend(subs.0.into_iter().map(|sub| sub.0));
+            }
+            accounts.insert(parent);
+        }
+
+        for account in accounts {
+            self.refresh_identity(&storage, account, block.number())
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn refresh_identity(
+        &self,
+        storage: &StorageAt,
+        account: [u8; 32],
+        block_number: u32,
+    ) -> Result<(), ServiceError> {
+        let address = Self::account_id_to_ss58(&account);
+
+        let (own, super_of) =
+            tokio::try_join!(self.fetch_identity(storage, account, block_number), async {
+                storage
+                    .fetch(
+                        &selendra::storage()
+                            .identity()
+                            .super_of(AccountId32(account)),
+                    )
+                    .await
+                    .map_err(|e| substrate_error("Error getting super-identity", e))
+            })?;
+
+        let identity = match super_of {
+            Some((parent, sub_name)) => {
+                let parent_identity = self.fetch_identity(storage, parent.0, block_number).await?;
+                Some(with_parent(
+                    own,
+                    address.clone(),
+                    Self::account_id_to_ss58(&parent.0),
+                    parent_identity
+                        .as_ref()
+                        .and_then(|identity| identity.display.as_deref()),
+                    data_text(&sub_name),
+                    block_number,
+                ))
+            }
+            None => own,
+        };
+
+        match identity {
+            Some(identity) => self.db_service.identities().save(&identity).await,
+            None => self.db_service.identities().delete(&address).await,
+        }
+    }
+
+    async fn fetch_identity(
+        &self,
+        storage: &StorageAt,
+        account: [u8; 32],
+        block_number: u32,
+    ) -> Result<Option<Identity>, ServiceError> {
+        let identity = storage
+            .fetch(
+                &selendra::storage()
+                    .identity()
+                    .identity_of(AccountId32(account)),
+            )
+            .await
+            .map_err(|e| substrate_error("Error getting identity", e))?;
+
+        Ok(identity.map(|(registration, username)| {
+            own_identity(
+                Self::account_id_to_ss58(&account),
+                &registration.info,
+                &registration.judgements.0,
+                username.as_ref().map(|username| username.0.as_slice()),
+                block_number,
+            )
+        }))
+    }
+}
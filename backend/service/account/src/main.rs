@@ -1,4 +1,6 @@
 pub mod account_process;
+pub mod identity_process;
+pub mod staking_process;
 
 use config::{
     DATABASE_NAMESPACE, DATABASE_PASSWORD, DATABASE_TABLE, DATABASE_URL, DATABASE_USERNAME,
@@ -30,5 +32,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let processor = AccountProcessingService::new(api, database);
     processor.process_account().await?;
 
+    // Snapshots and following share one finalized block, so no block is missed in between
+    let block = processor.finalized_block().await?;
+    processor.process_staking(&block).await?;
+    processor.process_identities(&block).await?;
+    processor.follow_finalized(block.number()).await?;
+
     Ok(())
 }
@@ -0,0 +1,305 @@
+use std::collections::HashSet;
+
+use config::selendra::{
+    self,
+    runtime_types::pallet_staking::{Nominations, StakingLedger as LedgerInfo},
+    staking::{
+        calls::types::{Nominate, Validate},
+        events::{Bonded, Chilled, EraPaid, Unbonded, Withdrawn},
+    },
+};
+use custom_error::ServiceError;
+use models::substrate::{StakingLedger, StakingRole, UnlockingChunk};
+use subxt::{SubstrateConfig, events::Events, utils::AccountId32};
+
+use crate::account_process::{
+    AccountProcessingService, FinalizedBlock, StorageAt, key_account, substrate_error,
+};
+
+// Bonds and nominations, snapshotted at era boundaries and refreshed from staking activity
+impl AccountProcessingService {
+    /// Snapshots every stash at a finalized block
+    pub async fn process_staking(&self, block: &FinalizedBlock) -> Result<(), ServiceError> {
+        self.snapshot_ledgers(block).await
+    }
+
+    /// Takes a full snapshot on `EraPaid`, otherwise refreshes the stashes touched by the block
+    pub(crate) async fn process_staking_block(
+        &self,
+        block: &FinalizedBlock,
+        events: &Events<SubstrateConfig>,
+    ) -> Result<(), ServiceError> {
+        let mut stashes = HashSet::new();
+        for event in events.iter() {
+            let event = event.map_err(|e| substrate_error("Error decoding event", e))?;
+
+            if event
+                .as_event::<EraPaid>()
+                .map_err(|e| substrate_error("Error decoding EraPaid", e))?
+                .is_some()
+            {
+                return self.snapshot_ledgers(block).await;
+            }
+
+            let stash = if let Ok(Some(bonded)) = event.as_event::<Bonded>() {
+                Some(bonded.stash)
+            } else if let Ok(Some(unbonded)) = event.as_event::<Unbonded>() {
+                Some(unbonded.stash)
+            } else if let Ok(Some(withdrawn)) = event.as_event::<Withdrawn>() {
+                Some(withdrawn.stash)
+            } else if let Ok(Some(chilled)) = event.as_event::<Chilled>() {
+                Some(chilled.stash)
+            } else {
+                None
+            };
+            stashes.extend(stash.map(|stash| stash.0));
+        }
+
+        // Nominating and validating emit no event, so the signer of those calls is refreshed
+        let extrinsics = block
+            .extrinsics()
+            .await
+            .map_err(|e| substrate_error("Error getting extrinsics", e))?;
+        for extrinsic in extrinsics.iter() {
+            let changes_role = extrinsic.is::<Nominate>().unwrap_or(false)
+                || extrinsic.is::<Validate>().unwrap_or(false);
+            if !changes_role {
+                continue;
+            }
+
+            // A signed address is `MultiAddress::Id`, a zero variant byte then the account id
+            if let Some([0, account @ ..]) = extrinsic.address_bytes() {
+                stashes.extend(<[u8; 32]>::try_from(account).ok());
+            }
+        }
+
+        if stashes.is_empty() {
+            return Ok(());
+        }
+
+        let storage = block.storage();
+        let timestamp = self.block_timestamp(&storage).await?;
+        for stash in stashes {
+            self.refresh_ledger(&storage, stash, block.number(), timestamp)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Iterates `Staking.Bonded` and saves the ledger of every stash, dropping the ones
+    /// that were not bonded anymore
+    async fn snapshot_ledgers(&self, block: &FinalizedBlock) -> Result<(), ServiceError> {
+        let storage = block.storage();
+        let block_number = block.number();
+        let timestamp = self.block_timestamp(&storage).await?;
+        let validators = self.validator_stashes(&storage).await?;
+
+        let mut iter = storage
+            .iter(selendra::storage().staking().bonded_iter())
+            .await
+            .map_err(|e| substrate_error("Error getting bonded stashes", e))?;
+
+        // Stale ledgers are only deleted after a complete scan, a failed read ends the snapshot
+        let mut saved = 0;
+        while let Some(kv) = iter.next().await {
+            let kv = kv.map_err(|e| substrate_error("Error reading bonded stashes", e))?;
+            let Some(stash) = key_account(&kv.key_bytes) else {
+                continue;
+            };
+
+            let controller = kv.value;
+            let (ledger, nominations) = tokio::try_join!(
+                self.fetch_ledger(&storage, &controller),
+                self.fetch_nominations(&storage, stash)
+            )?;
+            let Some(ledger) = ledger else {
+                continue;
+            };
+
+            self.db_service
+                .staking()
+                .save_ledger(&Self::staking_ledger(
+                    stash,
+                    &controller,
+                    validators.contains(&stash),
+                    ledger,
+                    nominations,
+                    block_number,
+                    timestamp,
+                ))
+                .await?;
+            saved += 1;
+        }
+
+        self.db_service
+            .staking()
+            .delete_stale_ledgers(block_number)
+            .await?;
+
+        println!(
+            "✅ Saved {} staking ledgers at block {}",
+            saved, block_number
+        );
+
+        Ok(())
+    }
+
+    async fn refresh_ledger(
+        &self,
+        storage: &StorageAt,
+        stash: [u8; 32],
+        block_number: u32,
+        timestamp: u64,
+    ) -> Result<(), ServiceError> {
+        let stash_address = Self::account_id_to_ss58(&stash);
+
+        let controller = storage
+            .fetch(&selendra::storage().staking().bonded(AccountId32(stash)))
+            .await
+            .map_err(|e| substrate_error("Error getting bonded controller", e))?;
+        let Some(controller) = controller else {
+            return self
+                .db_service
+                .staking()
+                .delete_ledger(&stash_address)
+                .await;
+        };
+
+        let (ledger, nominations, is_validator) = tokio::try_join!(
+            self.fetch_ledger(storage, &controller),
+            self.fetch_nominations(storage, stash),
+            self.is_validator(storage, stash)
+        )?;
+        let Some(ledger) = ledger else {
+            return self
+                .db_service
+                .staking()
+                .delete_ledger(&stash_address)
+                .await;
+        };
+
+        self.db_service
+            .staking()
+            .save_ledger(&Self::staking_ledger(
+                stash,
+                &controller,
+                is_validator,
+                ledger,
+                nominations,
+                block_number,
+                timestamp,
+            ))
+            .await
+    }
+
+    async fn fetch_ledger(
+        &self,
+        storage: &StorageAt,
+        controller: &AccountId32,
+    ) -> Result<Option<LedgerInfo>, ServiceError> {
+        storage
+            .fetch(&selendra::storage().staking().ledger(controller.clone()))
+            .await
+            .map_err(|e| substrate_error("Error getting staking ledger", e))
+    }
+
+    async fn fetch_nominations(
+        &self,
+        storage: &StorageAt,
+        stash: [u8; 32],
+    ) -> Result<Option<Nominations>, ServiceError> {
+        storage
+            .fetch(&selendra::storage().staking().nominators(AccountId32(stash)))
+            .await
+            .map_err(|e| substrate_error("Error getting nominations", e))
+    }
+
+    async fn is_validator(
+        &self,
+        storage: &StorageAt,
+        stash: [u8; 32],
+    ) -> Result<bool, ServiceError> {
+        let prefs = storage
+            .fetch(&selendra::storage().staking().validators(AccountId32(stash)))
+            .await
+            .map_err(|e| substrate_error("Error getting validator prefs", e))?;
+        Ok(prefs.is_some())
+    }
+
+    /// Stashes with an entry in `Staking.Validators`
+    async fn validator_stashes(
+        &self,
+        storage: &StorageAt,
+    ) -> Result<HashSet<[u8; 32]>, ServiceError> {
+        let mut iter = storage
+            .iter(selendra::storage().staking().validators_iter())
+            .await
+            .map_err(|e| substrate_error("Error getting validators", e))?;
+
+        let mut stashes = HashSet::new();
+        while let Some(kv) = iter.next().await {
+            let kv = kv.map_err(|e| substrate_error("Error reading validators", e))?;
+            stashes.extend(key_account(&kv.key_bytes));
+        }
+
+        Ok(stashes)
+    }
+
+    async fn block_timestamp(&self, storage: &StorageAt) -> Result<u64, ServiceError> {
+        let timestamp = storage
+            .fetch(&selendra::storage().timestamp().now())
+            .await
+            .map_err(|e| substrate_error("Error getting timestamp", e))?;
+        Ok(timestamp.unwrap_or_default())
+    }
+
+    fn staking_ledger(
+        stash: [u8; 32],
+        controller: &AccountId32,
+        is_validator: bool,
+        ledger: LedgerInfo,
+        nominations: Option<Nominations>,
+        block_number: u32,
+        timestamp: u64,
+    ) -> StakingLedger {
+        let role = if is_validator {
+            StakingRole::Validator
+        } else if nominations.is_some() {
+            StakingRole::Nominator
+        } else {
+            StakingRole::Idle
+        };
+
+        StakingLedger {
+            stash: Self::account_id_to_ss58(&stash),
+            controller: Self::account_id_to_ss58(&controller.0),
+            role,
+            bonded: ledger.total,
+            active: ledger.active,
+            unlocking: ledger
+                .unlocking
+                .0
+                .into_iter()
+                .map(|chunk| UnlockingChunk {
+                    value: chunk.value,
+                    era: chunk.era,
+                })
+                .collect(),
+            nominations: nominations
+                .as_ref()
+                .map(|nominations| {
+                    nominations
+                        .targets
+                        .0
+                        .iter()
+                        .map(|target| Self::account_id_to_ss58(&target.0))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            nominations_submitted_in: nominations.map(|nominations| nominations.submitted_in),
+            block_number,
+            timestamp,
+        }
+    }
+}
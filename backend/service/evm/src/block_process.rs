@@ -1,21 +1,40 @@
-use std::sync::Arc;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
-use blockscan::BlockStateQuery;
+use blockscan::{
+    BEACON_UPGRADED_TOPIC, BlockStateQuery, UPGRADED_TOPIC, decode_calldata, decode_log_with_abi,
+    evm_to_ss58,
+};
+use config::{EVM_INDEXER, EVM_TRACING_ENABLED};
 use custom_error::ServiceError;
 use database::DatabaseService;
 use ethers::{
     providers::{Http, Middleware, Provider},
-    types::BlockId,
+    types::{BlockId, BlockNumber, U256},
 };
 use models::{
-    AccountInfo, AddressType,
-    evm::{EvmBlock, EvmContract, EvmTransaction, TransactionType},
+    AccountInfo, AccountLinkKind, AddressType, NATIVE_DECIMALS,
+    evm::{
+        ContractCreationInfo, EvmBlock, EvmContract, EvmInternalTransaction, EvmLog,
+        EvmOrphanedBlock, EvmProxyImplementation, EvmTransaction, ProxyInfo, TokenTransfer,
+        TransactionType,
+    },
+    format_units,
 };
+use tokio::sync::Mutex;
+
+/// Maximum number of blocks walked back when looking for the common ancestor of a fork
+const MAX_REORG_DEPTH: u32 = 64;
 
 #[derive(Clone)]
 pub struct BlockProcessingService {
     pub provider: Arc<Provider<Http>>,
     pub db_service: DatabaseService,
+    reorg_lock: Arc<Mutex<()>>,
+    pub(crate) balance_lock: Arc<Mutex<()>>,
 }
 
 impl BlockProcessingService {
@@ -23,6 +42,8 @@ impl BlockProcessingService {
         Self {
             provider,
             db_service,
+            reorg_lock: Arc::new(Mutex::new(())),
+            balance_lock: Arc::new(Mutex::new(())),
         }
     }
 
@@ -36,6 +57,270 @@ impl BlockProcessingService {
         Ok(latest_block.as_u64())
     }
 
+    pub async fn finalized_block(&self) -> Result<u64, ServiceError> {
+        let block = self
+            .provider
+            .get_block(BlockNumber::Finalized)
+            .await?
+            .ok_or(ServiceError::BlockNotFound)?;
+
+        let number = block
+            .number
+            .ok_or_else(|| ServiceError::InvalidBlockData("Missing block number".to_string()))?;
+
+        Ok(number.as_u64())
+    }
+
+    /// Returns the canonical `(hash, parent_hash)` of a block as seen by the node
+    async fn canonical_hashes(&self, block_number: u32) -> Result<(String, String), ServiceError> {
+        let block = self
+            .provider
+            .get_block(block_number as u64)
+            .await?
+            .ok_or(ServiceError::BlockNotFound)?;
+
+        let hash = block
+            .hash
+            .map(|h| format!("{:#x}", h))
+            .ok_or_else(|| ServiceError::InvalidBlockData("Missing block hash".to_string()))?;
+
+        Ok((hash, format!("{:#x}", block.parent_hash)))
+    }
+
+    /// Whether a stored block is still on the canonical chain, a number the node no longer
+    /// has is not
+    async fn is_canonical(&self, block: &EvmBlock) -> Result<bool, ServiceError> {
+        match self.canonical_hashes(block.number).await {
+            Ok((hash, _)) => Ok(hash == block.hash),
+            Err(ServiceError::BlockNotFound) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Detects a chain reorganization at `block_number` and rolls back the orphaned branch.
+    ///
+    /// The stored block at `block_number` and the closest stored block below it are compared
+    /// with the canonical chain, so a gap under the block does not hide a fork. On a mismatch
+    /// the indexer walks back to the common ancestor, orphans the stored blocks above it that
+    /// left the canonical chain and re-indexes the canonical branch below `block_number`. The
+    /// caller indexes `block_number` itself.
+    ///
+    /// Rollbacks must not run while blocks above the fork are being indexed, callers check
+    /// blocks one at a time and in order before indexing them.
+    pub async fn handle_reorg(&self, block_number: u32) -> Result<(), ServiceError> {
+        let _guard = self.reorg_lock.lock().await;
+        let evm_service = self.db_service.evm_blocks();
+        let (hash, parent_hash) = self.canonical_hashes(block_number).await?;
+
+        let block_mismatch = evm_service
+            .get_by_number(block_number)
+            .await?
+            .is_some_and(|block| block.hash != hash);
+
+        let ancestor = evm_service
+            .get_before(Some(block_number), 1)
+            .await?
+            .into_iter()
+            .next();
+        let ancestor_mismatch = match &ancestor {
+            Some(ancestor) if ancestor.number + 1 == block_number => ancestor.hash != parent_hash,
+            Some(ancestor) => !self.is_canonical(ancestor).await?,
+            None => false,
+        };
+
+        let fork_point = match ancestor {
+            Some(ancestor) if ancestor_mismatch => self.find_fork_point(ancestor.number).await?,
+            _ if block_mismatch => block_number,
+            _ => return Ok(()),
+        };
+
+        println!(
+            "⚠️  Chain reorganization detected at block {}, rolling back from block {}",
+            block_number, fork_point
+        );
+
+        self.rollback_from(fork_point).await?;
+
+        for number in fork_point..block_number {
+            self.index_block(number).await?;
+        }
+
+        println!(
+            "✅ Re-indexed canonical blocks {} to {}",
+            fork_point, block_number
+        );
+
+        Ok(())
+    }
+
+    /// Walks back from the orphaned block `from` until a stored block matches the canonical
+    /// chain and returns the lowest block number that belongs to the orphaned branch.
+    /// Numbers without a stored block say nothing about the fork and are walked past.
+    async fn find_fork_point(&self, from: u32) -> Result<u32, ServiceError> {
+        let lowest = from.saturating_sub(MAX_REORG_DEPTH);
+        let stored = self.db_service.evm_blocks().get_range(lowest, from).await?;
+
+        let mut window = Vec::new();
+        for block in stored.iter().rev() {
+            let canonical = self.is_canonical(block).await?;
+            window.push((block.number, canonical));
+            if canonical {
+                break;
+            }
+        }
+
+        fork_point(&window, lowest).ok_or(ServiceError::ReorgTooDeep(from, MAX_REORG_DEPTH))
+    }
+
+    /// Orphans every stored block from `block_number` upwards that left the canonical chain
+    /// and moves the checkpoint back so the range is indexed again. Unlike `handle_reorg`
+    /// there is no depth limit, this is the way out of a reorganization deeper than it searches.
+    pub async fn resync_from(&self, block_number: u32) -> Result<(), ServiceError> {
+        let _guard = self.reorg_lock.lock().await;
+        println!("⚠️  Resyncing EVM blocks from block {}", block_number);
+
+        self.rollback_from(block_number).await?;
+        self.db_service
+            .sync_state()
+            .rewind_to(EVM_INDEXER, block_number as u64)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Indexes a block with its transactions and marks it fully indexed once all rows committed
+    pub async fn index_block(&self, block_number: u32) -> Result<(), ServiceError> {
+        tokio::try_join!(
+            self.process_block(block_number),
+            self.process_transactions(block_number)
+        )?;
+
+        self.db_service
+            .sync_state()
+            .mark_indexed(EVM_INDEXER, block_number as u64)
+            .await
+    }
+
+    /// Moves every stored block from `fork_point` upwards that is no longer canonical into the
+    /// orphaned table and removes the transactions and contracts indexed from them
+    async fn rollback_from(&self, fork_point: u32) -> Result<(), ServiceError> {
+        let orphaned_blocks = self.db_service.evm_blocks().get_from(fork_point).await?;
+        let orphaned_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or(0);
+        let mut touched_accounts = HashSet::new();
+        let mut touched_proxies = HashSet::new();
+
+        for block in orphaned_blocks {
+            let canonical_hash = match self.canonical_hashes(block.number).await {
+                Ok((hash, _)) => Some(hash),
+                Err(ServiceError::BlockNotFound) => None,
+                Err(e) => return Err(e),
+            };
+
+            // Blocks above the fork that the canonical chain still has stay indexed
+            if canonical_hash.as_deref() == Some(block.hash.as_str()) {
+                continue;
+            }
+
+            let transactions = self
+                .db_service
+                .transactions()
+                .get_all_with_block_number(block.number)
+                .await?;
+
+            for tx in &transactions {
+                touched_accounts.insert(tx.from.clone());
+                if let Some(to) = &tx.to {
+                    touched_accounts.insert(to.clone());
+                }
+            }
+
+            let orphaned = EvmOrphanedBlock {
+                number: block.number,
+                hash: block.hash.clone(),
+                parent_hash: block.parent_hash.clone(),
+                canonical_hash,
+                timestamp: block.timestamp,
+                transaction_hashes: transactions.into_iter().map(|tx| tx.hash).collect(),
+                orphaned_at,
+            };
+
+            self.db_service.orphaned_blocks().save(&orphaned).await?;
+            self.db_service
+                .transactions()
+                .delete_by_block_number(block.number)
+                .await?;
+            let token_transfers = self
+                .db_service
+                .token_transfers()
+                .get_by_block_number(block.number as u64)
+                .await?;
+            self.apply_token_transfers(&token_transfers, true).await?;
+            self.db_service
+                .evm_logs()
+                .mark_removed_by_block_number(block.number as u64)
+                .await?;
+            self.db_service
+                .internal_transactions()
+                .delete_by_block_number(block.number as u64)
+                .await?;
+            let proxy_implementations = self
+                .db_service
+                .proxy_implementations()
+                .get_by_block_number(block.number as u64)
+                .await?;
+            touched_proxies.extend(
+                proxy_implementations
+                    .into_iter()
+                    .map(|implementation| implementation.proxy_address),
+            );
+            self.db_service
+                .proxy_implementations()
+                .delete_by_block_number(block.number as u64)
+                .await?;
+            self.db_service
+                .token_transfers()
+                .delete_by_block_number(block.number as u64)
+                .await?;
+            self.db_service
+                .contracts()
+                .delete_by_creation_block(block.number as u64)
+                .await?;
+            self.db_service
+                .sync_state()
+                .unmark_indexed(EVM_INDEXER, block.number as u64)
+                .await?;
+            self.db_service
+                .evm_blocks()
+                .delete_by_number(block.number)
+                .await?;
+
+            println!("🗑️  Orphaned block {} ({})", block.number, block.hash);
+        }
+
+        // Balances changed by orphaned transactions are re-read from the canonical chain
+        let query = BlockStateQuery::new(Arc::clone(&self.provider), None);
+        for address in touched_proxies {
+            self.refresh_proxy(&query, &address).await?;
+        }
+        for address in touched_accounts {
+            let account_info = query.query_account(&address).await?;
+            self.db_service
+                .accounts()
+                .update_account(
+                    &address,
+                    None,
+                    Some(&account_info.balance_token),
+                    Some(&account_info.free_balance),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
     pub async fn process_block(&self, block_number: u32) -> Result<(), ServiceError> {
         let block_id = BlockId::Number(block_number.into());
         let query = BlockStateQuery::new(Arc::clone(&self.provider), Some(block_id));
@@ -54,8 +339,8 @@ impl BlockProcessingService {
             Err(e) => return Err(e),
         };
 
-        let total_burned_wei = block_info.base_fee_per_gas.unwrap_or(0) * block_info.gas_used;
-        let total_burned_eth = total_burned_wei as f64 / 1e18;
+        let total_burned_wei =
+            U256::from(block_info.base_fee_per_gas.unwrap_or(0)) * U256::from(block_info.gas_used);
         let session = block_number / 900;
         let era = session / 96;
 
@@ -71,7 +356,7 @@ impl BlockProcessingService {
             gas_used: block_info.gas_used,
             gas_limit: block_info.gas_limit,
             base_fee: block_info.base_fee_per_gas.unwrap_or(0) / 1_000_000_000u64,
-            burn_fee: total_burned_eth,
+            burn_fee: format_units(total_burned_wei, NATIVE_DECIMALS),
             validator: block_info.validate,
             extra_data: block_info.extra_data,
             nonce: block_info.nonce.map(|n| n as u32),
@@ -115,11 +400,23 @@ impl BlockProcessingService {
 
             if tx_service.is_exist_by_hash(&tx_hash).await? {
                 println!("⚠️ Transaction {} already exists in database", tx_hash);
-                return Ok(());
+                continue;
             }
 
             let mut transaction_info = query.transaction_by_hash(&tx_hash).await?;
-            let transaction_method = query.get_transaction_method(&transaction_info).await?;
+            // Contract types are recorded at creation and upgrade, not looked up per call
+            let contract_type = match &transaction_info.to {
+                Some(to) => self
+                    .db_service
+                    .contracts()
+                    .get_by_address(to)
+                    .await?
+                    .map(|contract| contract.contract_type),
+                None => None,
+            };
+            let transaction_method = query
+                .get_transaction_method(&transaction_info, contract_type)
+                .await?;
             transaction_info.trasation_method = Some(transaction_method);
 
             let timestamp = transaction_info.timestamp.unwrap_or(0);
@@ -156,6 +453,37 @@ impl BlockProcessingService {
                 }
             }
 
+            self.process_token_transfers(
+                &query,
+                &tx_hash,
+                transaction_info.block_number,
+                timestamp,
+            )
+            .await?;
+
+            self.process_logs(&query, &tx_hash, transaction_info.block_number, timestamp)
+                .await?;
+
+            if *EVM_TRACING_ENABLED {
+                self.process_internal_transactions(
+                    &query,
+                    &tx_hash,
+                    transaction_info.block_number,
+                    timestamp,
+                )
+                .await?;
+            }
+
+            let decoded_input = match &transaction_info.to {
+                Some(to) => self
+                    .db_service
+                    .contract_sources()
+                    .get_decoding_abi(to)
+                    .await?
+                    .and_then(|abi| decode_calldata(&abi, &transaction_info.input_data)),
+                None => None,
+            };
+
             let new_tx = EvmTransaction {
                 hash: transaction_info.hash,
                 block_number: transaction_info.block_number,
@@ -179,6 +507,7 @@ impl BlockProcessingService {
                     .unwrap_or(TransactionType::Legacy),
                 fee: transaction_info.transaction_fee.total_fee,
                 transaction_method: transaction_info.trasation_method,
+                decoded_input,
             };
 
             match tx_service.save(&new_tx).await {
@@ -204,6 +533,307 @@ impl BlockProcessingService {
         Ok(())
     }
 
+    /// Stores the token transfers emitted by a transaction, replacing any left by an earlier attempt
+    async fn process_token_transfers(
+        &self,
+        query: &BlockStateQuery,
+        tx_hash: &str,
+        block_number: u64,
+        timestamp: u128,
+    ) -> Result<(), ServiceError> {
+        let transfers = query.token_transfers(tx_hash).await?;
+        let transfer_service = self.db_service.token_transfers();
+
+        // Undo the balance changes of an earlier, interrupted attempt
+        let previous = transfer_service.get_by_transaction(tx_hash).await?;
+        if !previous.is_empty() {
+            self.apply_token_transfers(&previous, true).await?;
+            transfer_service.delete_by_transaction(tx_hash).await?;
+        }
+
+        let mut saved = Vec::with_capacity(transfers.len());
+        for transfer in transfers {
+            let token_transfer = TokenTransfer {
+                transaction_hash: tx_hash.to_string(),
+                block_number,
+                log_index: transfer.log_index,
+                batch_index: transfer.batch_index,
+                token_address: transfer.token_address,
+                standard: transfer.standard,
+                operator: transfer.operator,
+                from: transfer.from,
+                to: transfer.to,
+                value: transfer.value,
+                token_id: transfer.token_id,
+                timestamp,
+            };
+
+            saved.push(transfer_service.save(&token_transfer).await?);
+        }
+
+        self.apply_token_transfers(&saved, false).await?;
+
+        Ok(())
+    }
+
+    /// Stores every log emitted by a transaction, replacing any left by an earlier attempt
+    async fn process_logs(
+        &self,
+        query: &BlockStateQuery,
+        tx_hash: &str,
+        block_number: u64,
+        timestamp: u128,
+    ) -> Result<(), ServiceError> {
+        let logs = query.transaction_logs(tx_hash).await?;
+        let log_service = self.db_service.evm_logs();
+
+        log_service.delete_by_transaction(tx_hash).await?;
+
+        // Verified ABIs of the emitting contracts, looked up once per address
+        let mut abis = HashMap::new();
+
+        for log in logs {
+            let decoded = match log.decoded {
+                Some(decoded) => Some(decoded),
+                None => {
+                    if !abis.contains_key(&log.address) {
+                        let abi = self
+                            .db_service
+                            .contract_sources()
+                            .get_decoding_abi(&log.address)
+                            .await?;
+                        abis.insert(log.address.clone(), abi);
+                    }
+                    abis.get(&log.address)
+                        .and_then(Option::as_ref)
+                        .and_then(|abi| decode_log_with_abi(abi, &log.topics, &log.data))
+                }
+            };
+
+            let mut topics = log.topics.into_iter();
+            let evm_log = EvmLog {
+                address: log.address,
+                topic0: topics.next(),
+                topic1: topics.next(),
+                topic2: topics.next(),
+                topic3: topics.next(),
+                data: log.data,
+                block_number,
+                transaction_hash: tx_hash.to_string(),
+                log_index: log.log_index,
+                removed: log.removed,
+                decoded,
+                timestamp,
+            };
+
+            log_service.save(&evm_log).await?;
+
+            let topic0 = evm_log.topic0.as_deref();
+            if topic0 == Some(UPGRADED_TOPIC) || topic0 == Some(BEACON_UPGRADED_TOPIC) {
+                self.process_proxy_upgrade(query, &evm_log).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records the implementation set by an `Upgraded` or `BeaconUpgraded` event and
+    /// reclassifies the proxy through it
+    async fn process_proxy_upgrade(
+        &self,
+        query: &BlockStateQuery,
+        log: &EvmLog,
+    ) -> Result<(), ServiceError> {
+        let Some(proxy) = query.proxy_info(&log.address).await? else {
+            return Ok(());
+        };
+        let Some(topic1) = log.topic1.as_deref().filter(|topic| topic.len() >= 40) else {
+            return Ok(());
+        };
+        let event_address = format!("0x{}", &topic1[topic1.len() - 40..]);
+
+        let (implementation, beacon) = if log.topic0.as_deref() == Some(BEACON_UPGRADED_TOPIC) {
+            let implementation = query
+                .beacon_implementation(&event_address)
+                .await?
+                .unwrap_or_else(|| proxy.implementation.clone());
+            (implementation, Some(event_address))
+        } else {
+            (event_address, proxy.beacon.clone())
+        };
+
+        println!(
+            "🔀 Proxy {} upgraded to implementation {}",
+            log.address, implementation
+        );
+
+        self.db_service
+            .proxy_implementations()
+            .save(&EvmProxyImplementation {
+                proxy_address: log.address.clone(),
+                implementation,
+                kind: proxy.kind,
+                beacon,
+                block_number: log.block_number,
+                transaction_hash: Some(log.transaction_hash.clone()),
+                timestamp: log.timestamp,
+            })
+            .await?;
+
+        let latest = BlockStateQuery::new(Arc::clone(&self.provider), None);
+        self.refresh_proxy(&latest, &log.address).await
+    }
+
+    /// Re-reads the current implementation of a proxy and the contract type it implies
+    async fn refresh_proxy(
+        &self,
+        query: &BlockStateQuery,
+        address: &str,
+    ) -> Result<(), ServiceError> {
+        let account_info = query.query_account(address).await?;
+
+        if let Some(contract) = account_info.contract_type {
+            self.db_service
+                .contracts()
+                .update_proxy(address, &contract.contract_type, contract.proxy.as_ref())
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Stores the implementation a newly registered proxy delegates to
+    async fn record_detected_proxy(
+        &self,
+        query: &BlockStateQuery,
+        address: &str,
+        proxy: &ProxyInfo,
+        timestamp: u128,
+    ) -> Result<(), ServiceError> {
+        let block_number = match query.block_id {
+            BlockId::Number(BlockNumber::Number(number)) => number.as_u64(),
+            _ => self.lastest_block().await?,
+        };
+
+        self.db_service
+            .proxy_implementations()
+            .save(&EvmProxyImplementation {
+                proxy_address: address.to_string(),
+                implementation: proxy.implementation.clone(),
+                kind: proxy.kind.clone(),
+                beacon: proxy.beacon.clone(),
+                block_number,
+                transaction_hash: None,
+                timestamp,
+            })
+            .await
+    }
+
+    /// Stores the flattened call trace of a transaction and registers the contracts it deployed
+    async fn process_internal_transactions(
+        &self,
+        query: &BlockStateQuery,
+        tx_hash: &str,
+        block_number: u64,
+        timestamp: u128,
+    ) -> Result<(), ServiceError> {
+        let internal_txs = query.internal_transactions(tx_hash).await?;
+        let internal_tx_service = self.db_service.internal_transactions();
+
+        internal_tx_service.delete_by_transaction(tx_hash).await?;
+
+        for internal_tx in internal_txs {
+            let succeeded = internal_tx.error.is_none();
+
+            if succeeded {
+                if let Some(to) = &internal_tx.to {
+                    if internal_tx.is_creation() {
+                        println!("🏭 Contract {} deployed by {}", to, internal_tx.from);
+                        let creator_info = ContractCreationInfo {
+                            contract_address: to.clone(),
+                            creator_address: Some(internal_tx.from.clone()),
+                            transaction_hash: Some(tx_hash.to_string()),
+                            block_number,
+                            timestamp: timestamp.to_string(),
+                            creation_bytecode: internal_tx.input.clone(),
+                        };
+                        self.process_created_contract(query, tx_hash, creator_info, timestamp)
+                            .await?;
+                    } else if internal_tx.value != "0" {
+                        self.process_account(query, tx_hash, to, timestamp).await?;
+                    }
+                }
+            }
+
+            let new_internal_tx = EvmInternalTransaction {
+                transaction_hash: tx_hash.to_string(),
+                block_number,
+                trace_address: internal_tx.trace_address,
+                call_type: internal_tx.call_type,
+                from: internal_tx.from,
+                to: internal_tx.to,
+                value: internal_tx.value,
+                gas: internal_tx.gas,
+                gas_used: internal_tx.gas_used,
+                input: internal_tx.input,
+                output: internal_tx.output,
+                error: internal_tx.error,
+                timestamp,
+            };
+
+            internal_tx_service.save(&new_internal_tx).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Registers a contract deployed by another contract, the receipt only knows top-level deployments
+    async fn process_created_contract(
+        &self,
+        query: &BlockStateQuery,
+        tx_hash: &str,
+        creator_info: ContractCreationInfo,
+        timestamp: u128,
+    ) -> Result<(), ServiceError> {
+        let address = creator_info.contract_address.clone();
+        let account_info = query.query_account(&address).await?;
+
+        if let Some(contract) = account_info.contract_type {
+            if !self
+                .db_service
+                .contracts()
+                .is_exist_by_address(&address)
+                .await?
+            {
+                let contract_data = EvmContract {
+                    address: address.clone(),
+                    contract_type: contract.contract_type,
+                    name: contract.name,
+                    symbol: contract.symbol,
+                    decimals: contract.decimals,
+                    total_supply: contract.total_supply,
+                    is_verified: false,
+                    creator_info: Some(creator_info),
+                    proxy: contract.proxy,
+                };
+
+                let saved_contract = self.db_service.contracts().save(&contract_data).await?;
+                println!(
+                    "🔗 Saved contract: {} ({:?})",
+                    saved_contract.address, saved_contract.contract_type
+                );
+                if let Some(proxy) = &saved_contract.proxy {
+                    self.record_detected_proxy(query, &address, proxy, timestamp)
+                        .await?;
+                }
+            }
+        }
+
+        // The contract record exists now, this only stores the account
+        self.process_account(query, tx_hash, &address, timestamp)
+            .await
+    }
+
     async fn process_account(
         &self,
         query: &BlockStateQuery,
@@ -215,9 +845,9 @@ impl BlockProcessingService {
         let account_info = query.query_account(address).await?;
 
         let account = AccountInfo {
-            address: account_info.clone().address,
-            balance_token: account_info.balance_token,
-            free_balance: account_info.balance_token,
+            address: account_info.address.clone(),
+            balance_token: account_info.balance_token.clone(),
+            free_balance: account_info.free_balance.clone(),
             nonce: account_info.nonce,
             is_contract: account_info.is_contract,
             address_type: AddressType::H160,
@@ -237,14 +867,24 @@ impl BlockProcessingService {
                 .update_account(
                     &account.address,
                     Some(timestamp),
-                    Some(account.balance_token),
-                    Some(account.free_balance),
+                    Some(&account.balance_token),
+                    Some(&account.free_balance),
                 )
                 .await?;
         } else {
             // Save new account
             let saved_account = self.db_service.accounts().save(&account).await?;
             println!("✅ Saved new account: {}", saved_account.address);
+
+            // Links the SS58 account holding the native balance of the address
+            self.db_service
+                .account_links()
+                .link(
+                    &saved_account.address,
+                    &evm_to_ss58(&saved_account.address)?,
+                    AccountLinkKind::EvmDerived,
+                )
+                .await?;
         }
 
         if let Some(contract) = account_info.contract_type {
@@ -259,6 +899,7 @@ impl BlockProcessingService {
                 total_supply: contract.total_supply,
                 is_verified: false,
                 creator_info,
+                proxy: contract.proxy,
             };
 
             // Check if contract already exists
@@ -273,6 +914,10 @@ impl BlockProcessingService {
                     "🔗 Saved contract: {} ({:?})",
                     saved_contract.address, saved_contract.contract_type
                 );
+                if let Some(proxy) = &saved_contract.proxy {
+                    self.record_detected_proxy(query, &saved_contract.address, proxy, timestamp)
+                        .await?;
+                }
             } else {
                 println!("🔗 Contract already exists: {}", contract_data.address);
             }
@@ -281,3 +926,43 @@ impl BlockProcessingService {
         Ok(())
     }
 }
+
+/// Lowest block number of the orphaned branch. `window` holds the stored blocks of the search
+/// window from the highest down to the first canonical one, each paired with whether it is
+/// canonical, and `lowest` is the bottom of the window. None when the fork is below the window.
+pub(crate) fn fork_point(window: &[(u32, bool)], lowest: u32) -> Option<u32> {
+    match window.iter().find(|(_, canonical)| *canonical) {
+        Some((number, _)) => Some(number + 1),
+        None if lowest == 0 => Some(0),
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fork_point;
+
+    #[test]
+    fn fork_is_above_the_first_canonical_block() {
+        let window = [(105, false), (104, false), (101, true)];
+        assert_eq!(fork_point(&window, 41), Some(102));
+    }
+
+    #[test]
+    fn fork_at_the_orphaned_ancestor_when_the_block_below_is_canonical() {
+        let window = [(100, false), (99, true)];
+        assert_eq!(fork_point(&window, 36), Some(100));
+    }
+
+    #[test]
+    fn fork_below_the_window_is_too_deep() {
+        let window = [(100, false), (90, false), (36, false)];
+        assert_eq!(fork_point(&window, 36), None);
+    }
+
+    #[test]
+    fn window_reaching_genesis_forks_at_genesis() {
+        let window = [(10, false), (0, false)];
+        assert_eq!(fork_point(&window, 0), Some(0));
+    }
+}
@@ -1,18 +1,19 @@
 pub mod block_process;
 pub mod processing_config;
+pub mod token_balance;
 
 use block_process::BlockProcessingService;
 use config::{
     DATABASE_NAMESPACE, DATABASE_PASSWORD, DATABASE_TABLE, DATABASE_URL, DATABASE_USERNAME,
-    EVM_RPC_URL,
+    EVM_RESYNC_FROM, EVM_RPC_URL,
 };
 use dotenv::dotenv;
 use ethers::providers::{Http, Provider};
-use processing_config::{ContinuousProcessor, ProcessingConfig};
+use processing_config::{ContinuousProcessor, ProcessingConfig, is_reorg_too_deep};
 use std::{sync::Arc, time::Duration};
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
+async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     println!("🚀 Starting Evm Processing Service");
     dotenv().ok();
 
@@ -30,6 +31,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize block processing service
     let block_processor = BlockProcessingService::new(Arc::clone(&provider), database);
 
+    if let Some(block_number) = *EVM_RESYNC_FROM {
+        block_processor.resync_from(block_number as u32).await?;
+    }
+
     //1619130  869240
     let config = ProcessingConfig {
         start_block: None,
@@ -43,12 +48,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     match processor.start_processing().await {
         Ok(_) => println!("🎉 All blocks processed successfully!"),
+        Err(e) if is_reorg_too_deep(e.as_ref()) => return Err(e),
         Err(e) => println!("❌ Processing failed: {}", e),
     }
 
     // start continue block sync
     match processor.start_continuous_sync().await {
         Ok(_) => println!("✅ Continuous sync started successfully"),
+        Err(e) if is_reorg_too_deep(e.as_ref()) => return Err(e),
         Err(e) => println!("❌ Continuous sync failed: {}", e),
     }
 
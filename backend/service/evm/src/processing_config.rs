@@ -1,8 +1,15 @@
-use std::time::Duration;
+use config::EVM_INDEXER;
+use custom_error::ServiceError;
+use database::sync_state::{contiguous_end, gap_scan_end};
+use std::time::{Duration, Instant};
 use tokio::time::sleep;
 
 use crate::block_process::BlockProcessingService;
 
+/// How often token balances are checked against `balanceOf` while following the chain head
+const TOKEN_RECONCILE_INTERVAL: Duration = Duration::from_secs(60);
+const TOKEN_RECONCILE_BATCH: u32 = 100;
+
 #[derive(Debug, Clone)]
 pub struct ProcessingConfig {
     pub start_block: Option<u64>,
@@ -39,9 +46,17 @@ impl ContinuousProcessor {
 
     pub async fn start_processing(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let latest_block = self.block_processor.lastest_block().await?;
-        let start_block = self.config.start_block.unwrap_or_default();
+        let start_block = match self.config.start_block {
+            Some(start_block) => start_block,
+            None => self.resume_block().await?.unwrap_or_default(),
+        };
         let end_block = self.config.end_block.unwrap_or(latest_block);
 
+        if start_block > end_block {
+            println!("✅ Already synced up to block {}", end_block);
+            return Ok(());
+        }
+
         println!("📊 Processing Configuration:");
         println!("   Start Block: {}", start_block);
         println!("   End Block: {}", end_block);
@@ -73,14 +88,30 @@ impl ContinuousProcessor {
                         progress, processed_count, total_blocks
                     );
                 }
+                Err(e) if is_reorg_too_deep(e.as_ref()) => {
+                    self.record_error(&e.to_string()).await;
+                    println!(
+                        "🛑 {}. Retrying cannot fix this: restart the indexer with EVM_RESYNC_FROM set to a block below the fork to roll back and re-index from there.",
+                        e
+                    );
+                    return Err(e);
+                }
                 Err(e) => {
                     println!("❌ Batch failed: {}. Retrying...", e);
+                    self.record_error(&e.to_string()).await;
                     // Don't increment current_block to retry the same batch
                     sleep(Duration::from_secs(1)).await;
                     continue;
                 }
             }
 
+            if let Err(e) = self.advance_checkpoint(start_block, batch_end).await {
+                if is_reorg_too_deep(e.as_ref()) {
+                    return Err(e);
+                }
+                println!("⚠️  Failed to update sync checkpoint: {}", e);
+            }
+
             current_block = batch_end + 1;
 
             // Add delay between batches to avoid overwhelming the RPC
@@ -100,6 +131,8 @@ impl ContinuousProcessor {
         let mut processed = 0;
         let mut handles = Vec::new();
 
+        self.check_reorgs(start, end).await?;
+
         for block_num in start..=end {
             let block_processor = self.block_processor.clone();
 
@@ -118,7 +151,8 @@ impl ContinuousProcessor {
                 }
                 Err(e) => {
                     println!("❌ Failed to process block in batch: {}", e);
-                    // Continue processing other blocks in the batch
+                    self.record_error(&e.to_string()).await;
+                    // Continue processing other blocks in the batch, the gap detector retries it
                 }
             }
         }
@@ -126,6 +160,26 @@ impl ContinuousProcessor {
         Ok(processed)
     }
 
+    /// Checks the blocks of a batch for reorganizations one at a time and in order, before any
+    /// of them is indexed, so a rollback never runs next to the indexing of the same range.
+    /// The first block links the batch to the stored chain, the others only matter when an
+    /// earlier pass already stored them.
+    async fn check_reorgs(&self, start: u64, end: u64) -> Result<(), ServiceError> {
+        self.block_processor.handle_reorg(start as u32).await?;
+
+        let stored = self
+            .block_processor
+            .db_service
+            .evm_blocks()
+            .get_range(start as u32 + 1, end as u32)
+            .await?;
+        for block in stored {
+            self.block_processor.handle_reorg(block.number).await?;
+        }
+
+        Ok(())
+    }
+
     async fn process_single_block_with_retry(
         block_processor: BlockProcessingService,
         block_number: u32,
@@ -163,9 +217,23 @@ impl ContinuousProcessor {
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         println!("🔄 Starting continuous sync mode...");
 
+        let mut last_reconcile = Instant::now();
+
         loop {
             let latest_block = self.block_processor.lastest_block().await?;
-            let start_block = self.config.start_block.unwrap_or(latest_block);
+            // Without a checkpoint nothing is indexed yet, so start from genesis like the
+            // initial pass does rather than skipping everything below the head
+            let start_block = match self.resume_block().await? {
+                Some(block) => block,
+                None => {
+                    println!("📍 No sync checkpoint, syncing from block 0");
+                    0
+                }
+            };
+
+            if let Err(e) = self.update_finalized_block().await {
+                println!("⚠️  Failed to update finalized block: {}", e);
+            }
 
             if start_block <= latest_block {
                 let mut temp_config = self.config.clone();
@@ -179,28 +247,132 @@ impl ContinuousProcessor {
                     Ok(_) => {
                         println!("✅ Sync batch completed up to block {}", latest_block);
                     }
+                    Err(e) if is_reorg_too_deep(e.as_ref()) => return Err(e),
                     Err(e) => {
                         println!("❌ Sync batch failed: {}", e);
                     }
                 }
             }
 
+            // Reconcile only at the head so incremental updates and on-chain reads agree
+            if last_reconcile.elapsed() >= TOKEN_RECONCILE_INTERVAL {
+                match self
+                    .block_processor
+                    .reconcile_token_balances(TOKEN_RECONCILE_BATCH)
+                    .await
+                {
+                    Ok(count) => println!("🪙 Reconciled {} token balances", count),
+                    Err(e) => println!("⚠️  Token balance reconciliation failed: {}", e),
+                }
+                last_reconcile = Instant::now();
+            }
+
             println!("💤 Waiting for new blocks...");
             sleep(Duration::from_secs(1)).await; // Wait ~1 block time for Selendra
         }
     }
 
+    /// Block after the stored sync checkpoint, if the indexer has one
+    async fn resume_block(&self) -> Result<Option<u64>, Box<dyn std::error::Error + Send + Sync>> {
+        let resume_block = self
+            .block_processor
+            .db_service
+            .sync_state()
+            .resume_block(EVM_INDEXER)
+            .await?;
+
+        Ok(resume_block)
+    }
+
+    /// Fills holes below `up_to` and moves the contiguous checkpoint forward over blocks that
+    /// are fully indexed. At most `MAX_GAP_SCAN_BLOCKS` above the checkpoint are scanned.
+    ///
+    /// `default_start` is used as the lower bound when no checkpoint exists yet.
+    async fn advance_checkpoint(
+        &self,
+        default_start: u64,
+        up_to: u64,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let sync_state = self.block_processor.db_service.sync_state();
+        let from = sync_state
+            .resume_block(EVM_INDEXER)
+            .await?
+            .unwrap_or(default_start);
+
+        if from > up_to {
+            return Ok(());
+        }
+        let up_to = gap_scan_end(from, up_to);
+
+        let missing = sync_state.find_unindexed(EVM_INDEXER, from, up_to).await?;
+
+        let mut still_missing = Vec::new();
+        for block_number in missing {
+            println!("🕳️  Filling gap at block {}", block_number);
+            self.block_processor
+                .handle_reorg(block_number as u32)
+                .await?;
+            if let Err(e) = Self::process_single_block_with_retry(
+                self.block_processor.clone(),
+                block_number as u32,
+                self.config.max_retries,
+            )
+            .await
+            {
+                println!("❌ Failed to fill gap at block {}: {}", block_number, e);
+                self.record_error(&e.to_string()).await;
+                still_missing.push(block_number);
+            }
+        }
+
+        if let Some(highest_contiguous) = contiguous_end(from, up_to, &still_missing) {
+            sync_state
+                .set_highest_contiguous_block(EVM_INDEXER, highest_contiguous)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn update_finalized_block(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let finalized_block = self.block_processor.finalized_block().await?;
+        self.block_processor
+            .db_service
+            .sync_state()
+            .set_finalized_block(EVM_INDEXER, finalized_block)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn record_error(&self, error: &str) {
+        if let Err(e) = self
+            .block_processor
+            .db_service
+            .sync_state()
+            .set_last_error(EVM_INDEXER, error)
+            .await
+        {
+            println!("⚠️  Failed to record sync error: {}", e);
+        }
+    }
+
     async fn process_chain_info(
         block_processor: BlockProcessingService,
         block_number: u32,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        // Process block and transactions concurrently
-        let block_result = block_processor.process_block(block_number);
-        let tx_result = block_processor.process_transactions(block_number);
-
-        // Wait for both to complete
-        tokio::try_join!(block_result, tx_result)?;
+        // Reorganizations were already handled for the batch before it was spawned
+        block_processor.index_block(block_number).await?;
 
         Ok(())
     }
 }
+
+/// A reorganization deeper than the search window stops the indexer instead of failing the
+/// same batch forever
+pub fn is_reorg_too_deep(error: &(dyn std::error::Error + Send + Sync + 'static)) -> bool {
+    matches!(
+        error.downcast_ref::<ServiceError>(),
+        Some(ServiceError::ReorgTooDeep(..))
+    )
+}
@@ -0,0 +1,321 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use blockscan::BlockStateQuery;
+use custom_error::ServiceError;
+use ethers::types::U256;
+use models::evm::{TokenBalance, TokenStandard, TokenTransfer};
+
+use crate::block_process::BlockProcessingService;
+
+const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+
+// (token address, holder, token id)
+type BalanceKey = (String, String, Option<String>);
+
+fn now_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0)
+}
+
+/// Amount moved by a transfer, an ERC721 transfer always moves a single token. None when
+/// the stored value is not a `u256` decimal
+fn transfer_amount(transfer: &TokenTransfer) -> Option<U256> {
+    match transfer.standard {
+        TokenStandard::ERC721 => Some(U256::one()),
+        _ => transfer
+            .value
+            .as_deref()
+            .and_then(|value| U256::from_dec_str(value).ok()),
+    }
+}
+
+/// Credits and debits of a holder's balance in a set of transfers
+#[derive(Debug, Clone)]
+struct BalanceDelta {
+    standard: TokenStandard,
+    credit: U256,
+    debit: U256,
+    block_number: u64,
+    /// Unset once an amount could not be read or the sum overflowed
+    exact: bool,
+}
+
+impl BalanceDelta {
+    fn add(&mut self, amount: Option<U256>, credit: bool) {
+        let side = if credit {
+            &mut self.credit
+        } else {
+            &mut self.debit
+        };
+        match amount.and_then(|amount| side.checked_add(amount)) {
+            Some(sum) => *side = sum,
+            None => self.exact = false,
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.exact && self.credit == self.debit
+    }
+
+    /// New balance from the stored one, None when it cannot be computed exactly: an amount
+    /// was unreadable, the stored balance is, or out of order blocks debit before the credit
+    fn apply(&self, current: Option<&str>) -> Option<U256> {
+        if !self.exact {
+            return None;
+        }
+
+        let current = match current {
+            Some(current) => U256::from_dec_str(current).ok()?,
+            None => U256::zero(),
+        };
+
+        current.checked_add(self.credit)?.checked_sub(self.debit)
+    }
+}
+
+/// ERC20 and ERC721 balances are per holder, ERC1155 balances are per token id
+fn balance_token_id(transfer: &TokenTransfer) -> Option<String> {
+    match transfer.standard {
+        TokenStandard::ERC1155 => transfer.token_id.clone(),
+        _ => None,
+    }
+}
+
+impl BlockProcessingService {
+    /// Applies the balance changes of indexed transfers, or undoes them when `revert` is set
+    pub async fn apply_token_transfers(
+        &self,
+        transfers: &[TokenTransfer],
+        revert: bool,
+    ) -> Result<(), ServiceError> {
+        let mut deltas: HashMap<BalanceKey, BalanceDelta> = HashMap::new();
+
+        for transfer in transfers {
+            let amount = transfer_amount(transfer);
+            let token_id = balance_token_id(transfer);
+
+            // Undoing a transfer credits the sender and debits the recipient
+            let (debited, credited) = if revert {
+                (&transfer.to, &transfer.from)
+            } else {
+                (&transfer.from, &transfer.to)
+            };
+
+            for (holder, credit) in [(debited, false), (credited, true)] {
+                if holder == ZERO_ADDRESS {
+                    continue;
+                }
+
+                let key = (
+                    transfer.token_address.clone(),
+                    holder.clone(),
+                    token_id.clone(),
+                );
+                let entry = deltas.entry(key).or_insert_with(|| BalanceDelta {
+                    standard: transfer.standard.clone(),
+                    credit: U256::zero(),
+                    debit: U256::zero(),
+                    block_number: transfer.block_number,
+                    exact: true,
+                });
+                entry.add(amount, credit);
+                entry.block_number = entry.block_number.max(transfer.block_number);
+            }
+        }
+
+        // Blocks are indexed concurrently, serialize the read-modify-write of balances
+        let _guard = self.balance_lock.lock().await;
+        let balance_service = self.db_service.token_balances();
+
+        for ((token_address, holder, token_id), delta) in deltas {
+            if delta.is_zero() {
+                continue;
+            }
+
+            let existing = balance_service
+                .get(&token_address, &holder, token_id.as_deref())
+                .await?;
+
+            let last_block = existing.as_ref().map_or(delta.block_number, |balance| {
+                balance.last_block.max(delta.block_number)
+            });
+            let stored = existing.as_ref().map(|balance| balance.balance.as_str());
+
+            let balance = match delta.apply(stored) {
+                Some(balance) => TokenBalance {
+                    token_address,
+                    holder,
+                    token_id,
+                    standard: delta.standard,
+                    balance: balance.to_string(),
+                    last_block,
+                    reconciled_at: existing.and_then(|balance| balance.reconciled_at),
+                    updated_at: now_millis(),
+                },
+                None => {
+                    // Keep the stored balance and queue it first for a `balanceOf` read at
+                    // the head, which replaces it with the on-chain value
+                    println!(
+                        "⚠️  Cannot apply the {} transfers of {} exactly, queued for reconciliation",
+                        token_address, holder
+                    );
+                    TokenBalance {
+                        balance: stored.unwrap_or("0").to_string(),
+                        token_address,
+                        holder,
+                        token_id,
+                        standard: delta.standard,
+                        last_block,
+                        reconciled_at: None,
+                        updated_at: now_millis(),
+                    }
+                }
+            };
+
+            balance_service.save(&balance).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Re-reads the least recently checked balances with `balanceOf` and refreshes the
+    /// total supply of the tokens involved
+    pub async fn reconcile_token_balances(&self, limit: u32) -> Result<usize, ServiceError> {
+        let balances = self
+            .db_service
+            .token_balances()
+            .get_least_recently_reconciled(limit)
+            .await?;
+
+        self.reconcile_balances(balances).await
+    }
+
+    /// Re-reads the given balances from the chain
+    async fn reconcile_balances(&self, balances: Vec<TokenBalance>) -> Result<usize, ServiceError> {
+        let query = BlockStateQuery::new(Arc::clone(&self.provider), None);
+        let balance_service = self.db_service.token_balances();
+        let mut tokens = HashSet::new();
+        let mut reconciled = 0;
+
+        let _guard = self.balance_lock.lock().await;
+
+        for mut balance in balances {
+            let on_chain = match query
+                .token_balance_of(
+                    &balance.token_address,
+                    &balance.holder,
+                    balance.token_id.as_deref(),
+                )
+                .await
+            {
+                Ok(on_chain) => on_chain,
+                Err(e) => {
+                    println!(
+                        "⚠️  balanceOf failed for {} on {}: {}",
+                        balance.holder, balance.token_address, e
+                    );
+                    // Move it to the back of the queue so it cannot starve the other balances
+                    balance.reconciled_at = Some(now_millis());
+                    balance_service.save(&balance).await?;
+                    continue;
+                }
+            };
+
+            let on_chain = on_chain.to_string();
+            if on_chain != balance.balance {
+                println!(
+                    "🔧 Reconciled {} balance of {}: {} -> {}",
+                    balance.token_address, balance.holder, balance.balance, on_chain
+                );
+            }
+
+            tokens.insert(balance.token_address.clone());
+            balance.balance = on_chain;
+            balance.reconciled_at = Some(now_millis());
+            balance.updated_at = now_millis();
+            balance_service.save(&balance).await?;
+            reconciled += 1;
+        }
+
+        for token in tokens {
+            if let Ok(total_supply) = query.token_total_supply(&token).await {
+                self.db_service
+                    .contracts()
+                    .update_total_supply(&token, &total_supply.to_string())
+                    .await?;
+            }
+        }
+
+        Ok(reconciled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delta(credit: u64, debit: u64) -> BalanceDelta {
+        BalanceDelta {
+            standard: TokenStandard::ERC20,
+            credit: U256::from(credit),
+            debit: U256::from(debit),
+            block_number: 1,
+            exact: true,
+        }
+    }
+
+    #[test]
+    fn applies_credits_and_debits() {
+        assert_eq!(delta(5, 2).apply(Some("10")), Some(U256::from(13)));
+        assert_eq!(delta(5, 0).apply(None), Some(U256::from(5)));
+        assert!(delta(3, 3).is_zero());
+    }
+
+    #[test]
+    fn inexact_balances_are_not_computed() {
+        // A debit before its credit arrives would go below zero
+        assert_eq!(delta(0, 2).apply(Some("1")), None);
+        // Balances stored negative by earlier versions
+        assert_eq!(delta(1, 0).apply(Some("-5")), None);
+
+        let mut unreadable = delta(0, 0);
+        unreadable.add(None, true);
+        assert!(!unreadable.is_zero());
+        assert_eq!(unreadable.apply(Some("1")), None);
+
+        let mut overflow = delta(0, 0);
+        overflow.add(Some(U256::MAX), true);
+        overflow.add(Some(U256::one()), true);
+        assert_eq!(overflow.apply(None), None);
+    }
+
+    fn transfer(value: &str) -> TokenTransfer {
+        TokenTransfer {
+            transaction_hash: String::new(),
+            block_number: 1,
+            log_index: 0,
+            batch_index: 0,
+            token_address: String::new(),
+            standard: TokenStandard::ERC20,
+            operator: None,
+            from: String::new(),
+            to: String::new(),
+            value: Some(value.to_string()),
+            token_id: None,
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn amounts_above_i256_are_kept() {
+        let max = U256::MAX.to_string();
+        assert_eq!(transfer_amount(&transfer(&max)), Some(U256::MAX));
+        assert_eq!(transfer_amount(&transfer("not a number")), None);
+    }
+}
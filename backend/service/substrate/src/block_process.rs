@@ -1,4 +1,4 @@
-use blockscan::SubstrtaeBlockQuery;
+use blockscan::{SubstrtaeBlockQuery, json_accounts};
 use custom_error::ServiceError;
 use database::DatabaseService;
 use models::substrate::{SubstrateBlock, SubstrateEvent, SubstrateExtrinsic};
@@ -22,7 +22,55 @@ impl BlockProcessingService {
         Ok(latest_block)
     }
 
+    pub async fn finalized_block(&self) -> Result<u32, ServiceError> {
+        let api = SubstrtaeBlockQuery::new(self.client.clone(), None).await?;
+        let finalized_block = api.finalized_block_number().await?;
+
+        Ok(finalized_block)
+    }
+
+    /// Records the accounts mentioned by events indexed before accounts were recorded, so
+    /// they show up in account timelines
+    pub async fn backfill_event_accounts(&self) -> Result<usize, ServiceError> {
+        const PAGE_SIZE: u32 = 500;
+        let event_service = self.db_service.substrate_events();
+        let mut updated = 0;
+
+        loop {
+            // Updated events leave the filter, so every page starts over
+            let events = event_service.get_without_accounts(PAGE_SIZE).await?;
+            if events.is_empty() {
+                return Ok(updated);
+            }
+
+            for event in &events {
+                event_service
+                    .set_accounts(
+                        event.block_number,
+                        event.event_index,
+                        &json_accounts(&event.data),
+                    )
+                    .await?;
+            }
+            updated += events.len();
+        }
+    }
+
     pub async fn process_block(&self, block_number: u32) -> Result<(), ServiceError> {
+        // A retried block keeps the row saved by the earlier attempt
+        if self
+            .db_service
+            .substrate_blocks()
+            .exists_by_number(block_number)
+            .await?
+        {
+            println!(
+                "⚠️  Block {} already exists in database, skipping...",
+                block_number
+            );
+            return Ok(());
+        }
+
         let api = SubstrtaeBlockQuery::new(self.client.clone(), Some(block_number)).await?;
 
         let block = api.block_info().await?;
@@ -64,132 +112,112 @@ impl BlockProcessingService {
         let api = SubstrtaeBlockQuery::new(self.client.clone(), Some(block_number)).await?;
         let block = api.block_info().await?;
         let timestamp = api.get_block_timestamp().await?;
+        let events = api.block_event().await?;
+        let outcomes = api.extrinsic_outcomes(&events)?;
+
+        let extrinsics = api.get_extrinsics(block).await?;
+        println!(
+            "📝 Processing {} extrinsics for block {}",
+            extrinsics.len(),
+            block_number
+        );
+
+        let mut substrate_extrinsics = Vec::new();
+
+        for extrinsic_details in extrinsics.iter() {
+            let outcome = outcomes
+                .get(&(extrinsic_details.index as u32))
+                .cloned()
+                .unwrap_or_default();
+
+            let substrate_extrinsic = SubstrateExtrinsic {
+                block_number,
+                extrinsic_index: extrinsic_details.index as u32,
+                is_signed: extrinsic_details.is_signed,
+                signer: extrinsic_details
+                    .signature_info
+                    .as_ref()
+                    .map(|s| s.signer.clone()),
+                call_module: extrinsic_details.call_info.pallet.clone(),
+                call_function: extrinsic_details.call_info.call.clone(),
+                args: extrinsic_details.call_info.args.clone(),
+                success: outcome.success,
+                dispatch_error: outcome.dispatch_error,
+                actual_fee: outcome.actual_fee,
+                tip: outcome
+                    .tip
+                    .or_else(|| extrinsic_details.signature_info.as_ref().map(|s| s.tip)),
+                weight: outcome.weight,
+                timestamp,
+                signer_display: None,
+            };
+
+            substrate_extrinsics.push(substrate_extrinsic);
+        }
 
-        match api.get_extrinsics(block).await {
-            Ok(extrinsics) => {
-                println!(
-                    "📝 Processing {} extrinsics for block {}",
-                    extrinsics.len(),
-                    block_number
-                );
-
-                let mut substrate_extrinsics = Vec::new();
-
-                for (_, extrinsic_details) in extrinsics.iter().enumerate() {
-                    let substrate_extrinsic = SubstrateExtrinsic {
-                        block_number,
-                        extrinsic_index: extrinsic_details.index as u32,
-                        is_signed: extrinsic_details.is_signed,
-                        signer: extrinsic_details
-                            .signature_info
-                            .as_ref()
-                            .map(|s| s.signer.clone()),
-                        call_module: extrinsic_details.call_info.pallet.clone(),
-                        call_function: extrinsic_details.call_info.call.clone(),
-                        args: serde_json::to_string(&extrinsic_details.call_info.args)
-                            .unwrap_or_else(|_| "{}".to_string()),
-                        timestamp,
-                    };
-
-                    substrate_extrinsics.push(substrate_extrinsic);
-                }
-
-                // Save extrinsics in batch
-                match self
-                    .db_service
-                    .substrate_extrinsics()
-                    .save_batch(&substrate_extrinsics)
-                    .await
-                {
-                    Ok(saved) => {
-                        println!(
-                            "✅ Saved {}/{} extrinsics for block {}",
-                            saved.len(),
-                            substrate_extrinsics.len(),
-                            block_number
-                        );
-                    }
-                    Err(e) => {
-                        println!(
-                            "❌ Failed to save extrinsics for block {}: {}",
-                            block_number, e
-                        );
-                    }
-                }
-            }
-
-            Err(e) => {
-                println!(
-                    "⚠️  Failed to get extrinsics for block {}: {}",
-                    block_number, e
-                );
-            }
-        };
-
-        Ok(())
+        // Replace the rows left by an earlier attempt at the block
+        let extrinsic_service = self.db_service.substrate_extrinsics();
+        extrinsic_service
+            .delete_by_block_number(block_number)
+            .await?;
+        let saved = extrinsic_service.save_batch(&substrate_extrinsics).await?;
+        println!(
+            "✅ Saved {} extrinsics for block {}",
+            saved.len(),
+            block_number
+        );
+
+        self.process_transfers(
+            block_number,
+            timestamp,
+            &events.events,
+            &substrate_extrinsics,
+        )
+        .await
     }
 
     pub async fn process_event(&self, block_number: u32) -> Result<(), ServiceError> {
         let api = SubstrtaeBlockQuery::new(self.client.clone(), Some(block_number)).await?;
         let timestamp = api.get_block_timestamp().await?;
 
-        match api.block_event().await {
-            Ok(events) => {
-                println!(
-                    "📋 Processing {} events for block {}",
-                    events.events.len(),
-                    block_number
-                );
-
-                let mut substrate_events = Vec::new();
-
-                for (index, event) in events.events.iter().enumerate() {
-                    // Parse module and event name from event string (e.g., "System.ExtrinsicSuccess")
-                    let event_parts: Vec<&str> = event.event.split('.').collect();
-                    let (module, event_name) = if event_parts.len() >= 2 {
-                        (event_parts[0].to_string(), event.event.clone())
-                    } else {
-                        ("Unknown".to_string(), event.event.clone())
-                    };
-
-                    let substrate_event = SubstrateEvent {
-                        block_number,
-                        event_index: index as u32,
-                        phase: event.phase.clone(),
-                        module,
-                        event: event_name,
-                        data: serde_json::to_string(&event.topics)
-                            .unwrap_or_else(|_| "[]".to_string()),
-                        timestamp,
-                    };
-
-                    substrate_events.push(substrate_event);
-                }
-
-                // Save events in batch
-                match self
-                    .db_service
-                    .substrate_events()
-                    .save_batch(&substrate_events)
-                    .await
-                {
-                    Ok(saved) => {
-                        println!(
-                            "✅ Saved {}/{} events for block {}",
-                            saved.len(),
-                            substrate_events.len(),
-                            block_number
-                        );
-                    }
-                    Err(e) => {
-                        println!("❌ Failed to save events for block {}: {}", block_number, e);
-                    }
-                }
-            }
-            Err(e) => {
-                println!("⚠️  Failed to get events for block {}: {}", block_number, e);
-            }
+        let events = api.block_event().await?;
+        println!(
+            "📋 Processing {} events for block {}",
+            events.events.len(),
+            block_number
+        );
+
+        let mut substrate_events = Vec::new();
+
+        for event in events.events.iter() {
+            let substrate_event = SubstrateEvent {
+                block_number,
+                event_index: event.index as u32,
+                phase: event.phase.clone(),
+                extrinsic_index: event.extrinsic_index,
+                module: event.pallet.clone(),
+                event: event.variant.clone(),
+                data: event.fields.clone(),
+                timestamp,
+                accounts: event.accounts.clone(),
+            };
+
+            substrate_events.push(substrate_event);
         }
+
+        // Replace the rows left by an earlier attempt at the block
+        let event_service = self.db_service.substrate_events();
+        event_service.delete_by_block_number(block_number).await?;
+        let saved = event_service.save_batch(&substrate_events).await?;
+        println!("✅ Saved {} events for block {}", saved.len(), block_number);
+
+        // Staking rows are keyed by block and event, a failed block is indexed again as a whole
+        self.process_staking(&api, block_number, timestamp, &events.events)
+            .await?;
+
+        self.process_session_validators(block_number, &events.events)
+            .await?;
+
         Ok(())
     }
 }
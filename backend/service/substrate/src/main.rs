@@ -1,5 +1,8 @@
 pub mod block_process;
 pub mod processing_config;
+pub mod staking_process;
+pub mod transfer_process;
+pub mod validator_process;
 
 use block_process::BlockProcessingService;
 use config::{
@@ -40,6 +43,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Initialize block processing service
     let block_processor = BlockProcessingService::new(client, database)?;
+    match block_processor.backfill_event_accounts().await {
+        Ok(0) => {}
+        Ok(updated) => println!("✅ Recorded the accounts of {} earlier events", updated),
+        Err(e) => println!("❌ Backfilling event accounts failed: {}", e),
+    }
+    match block_processor.backfill_transfers().await {
+        Ok(0) => {}
+        Ok(saved) => println!("✅ Saved {} transfers of earlier blocks", saved),
+        Err(e) => println!("❌ Backfilling transfers failed: {}", e),
+    }
+
     let processor = ContinuousProcessor::new(block_processor, config);
 
     match processor.start_processing().await {
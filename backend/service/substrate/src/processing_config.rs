@@ -1,3 +1,5 @@
+use config::SUBSTRATE_INDEXER;
+use database::sync_state::{contiguous_end, gap_scan_end};
 use std::time::Duration;
 use tokio::time::sleep;
 
@@ -39,9 +41,17 @@ impl ContinuousProcessor {
 
     pub async fn start_processing(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let latest_block = self.block_processor.lastest_block().await?;
-        let start_block = self.config.start_block.unwrap_or_default();
+        let start_block = match self.config.start_block {
+            Some(start_block) => start_block,
+            None => self.resume_block().await?.unwrap_or_default(),
+        };
         let end_block = self.config.end_block.unwrap_or(latest_block);
 
+        if start_block > end_block {
+            println!("✅ Already synced up to block {}", end_block);
+            return Ok(());
+        }
+
         println!("📊 Processing Configuration:");
         println!("   Start Block: {}", start_block);
         println!("   End Block: {}", end_block);
@@ -75,12 +85,17 @@ impl ContinuousProcessor {
                 }
                 Err(e) => {
                     println!("❌ Batch failed: {}. Retrying...", e);
+                    self.record_error(&e.to_string()).await;
                     // Don't increment current_block to retry the same batch
                     sleep(Duration::from_secs(1)).await;
                     continue;
                 }
             }
 
+            if let Err(e) = self.advance_checkpoint(start_block, batch_end).await {
+                println!("⚠️  Failed to update sync checkpoint: {}", e);
+            }
+
             current_block = batch_end + 1;
 
             // Add delay between batches to avoid overwhelming the RPC
@@ -111,7 +126,8 @@ impl ContinuousProcessor {
                 }
                 Err(e) => {
                     println!("❌ Failed to process block {}: {}", block_num, e);
-                    // Continue processing other blocks in the batch
+                    self.record_error(&e.to_string()).await;
+                    // Continue processing other blocks in the batch, the gap detector retries it
                 }
             }
         }
@@ -158,7 +174,11 @@ impl ContinuousProcessor {
 
         loop {
             let latest_block = self.block_processor.lastest_block().await?;
-            let start_block = self.config.start_block.unwrap_or(latest_block);
+            let start_block = self.resume_block().await?.unwrap_or(latest_block);
+
+            if let Err(e) = self.update_finalized_block().await {
+                println!("⚠️  Failed to update finalized block: {}", e);
+            }
 
             if start_block <= latest_block {
                 let mut temp_config = self.config.clone();
@@ -183,6 +203,87 @@ impl ContinuousProcessor {
         }
     }
 
+    /// Block after the stored sync checkpoint, if the indexer has one
+    async fn resume_block(&self) -> Result<Option<u32>, Box<dyn std::error::Error + Send + Sync>> {
+        let resume_block = self
+            .block_processor
+            .db_service
+            .sync_state()
+            .resume_block(SUBSTRATE_INDEXER)
+            .await?;
+
+        Ok(resume_block.map(|block| block as u32))
+    }
+
+    /// Fills holes below `up_to` and moves the contiguous checkpoint forward over blocks that
+    /// are fully indexed. At most `MAX_GAP_SCAN_BLOCKS` above the checkpoint are scanned.
+    ///
+    /// `default_start` is used as the lower bound when no checkpoint exists yet.
+    async fn advance_checkpoint(
+        &self,
+        default_start: u32,
+        up_to: u32,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let from = self.resume_block().await?.unwrap_or(default_start) as u64;
+
+        if from > up_to as u64 {
+            return Ok(());
+        }
+        let up_to = gap_scan_end(from, up_to as u64);
+
+        let sync_state = self.block_processor.db_service.sync_state();
+        let missing = sync_state
+            .find_unindexed(SUBSTRATE_INDEXER, from, up_to)
+            .await?;
+
+        let mut still_missing = Vec::new();
+        for block_number in missing {
+            println!("🕳️  Filling gap at block {}", block_number);
+            if let Err(e) = Self::process_single_block_with_retry(
+                self.block_processor.clone(),
+                block_number as u32,
+                self.config.max_retries,
+            )
+            .await
+            {
+                println!("❌ Failed to fill gap at block {}: {}", block_number, e);
+                self.record_error(&e.to_string()).await;
+                still_missing.push(block_number);
+            }
+        }
+
+        if let Some(highest_contiguous) = contiguous_end(from, up_to, &still_missing) {
+            sync_state
+                .set_highest_contiguous_block(SUBSTRATE_INDEXER, highest_contiguous)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn update_finalized_block(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let finalized_block = self.block_processor.finalized_block().await?;
+        self.block_processor
+            .db_service
+            .sync_state()
+            .set_finalized_block(SUBSTRATE_INDEXER, finalized_block as u64)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn record_error(&self, error: &str) {
+        if let Err(e) = self
+            .block_processor
+            .db_service
+            .sync_state()
+            .set_last_error(SUBSTRATE_INDEXER, error)
+            .await
+        {
+            println!("⚠️  Failed to record sync error: {}", e);
+        }
+    }
+
     async fn process_chain_info(
         block_processor: BlockProcessingService,
         block_number: u32,
@@ -208,6 +309,13 @@ impl ContinuousProcessor {
             .await
             .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
 
+        // Only a block whose rows all committed counts towards the checkpoint
+        block_processor
+            .db_service
+            .sync_state()
+            .mark_indexed(SUBSTRATE_INDEXER, block_number as u64)
+            .await?;
+
         Ok(())
     }
 }
@@ -0,0 +1,191 @@
+use blockscan::{SubstrtaeBlockQuery, json_to_u128};
+use blockscan_model::event::FormattedEvent;
+use custom_error::ServiceError;
+use models::substrate::{EraPayout, EraValidatorPoints, StakingReward, StakingSlash};
+use serde_json::Value as JsonValue;
+
+use crate::block_process::BlockProcessingService;
+
+/// Field of a decoded event, looked up by name or by position for unnamed fields
+pub(crate) fn event_field<'a>(
+    event: &'a FormattedEvent,
+    name: &str,
+    position: usize,
+) -> Option<&'a JsonValue> {
+    match &event.fields {
+        JsonValue::Object(fields) => fields.get(name),
+        JsonValue::Array(fields) => fields.get(position),
+        _ => None,
+    }
+}
+
+pub(crate) fn json_u32(value: &JsonValue) -> Option<u32> {
+    value.as_u64().and_then(|n| u32::try_from(n).ok())
+}
+
+pub(crate) fn json_string(value: &JsonValue) -> Option<String> {
+    value.as_str().map(str::to_string)
+}
+
+// Staking rewards, slashes and era payouts
+impl BlockProcessingService {
+    /// Indexes the staking events of a block, a reward is attributed to the era and
+    /// validator of the `PayoutStarted` that precedes it in the same extrinsic
+    pub async fn process_staking(
+        &self,
+        api: &SubstrtaeBlockQuery,
+        block_number: u32,
+        timestamp: u64,
+        events: &[FormattedEvent],
+    ) -> Result<(), ServiceError> {
+        let staking = self.db_service.staking();
+        let mut payout: Option<(Option<u32>, u32, String)> = None;
+        let mut active_era = None;
+
+        for event in events.iter().filter(|event| event.pallet == "Staking") {
+            let event_index = event.index as u32;
+
+            match event.variant.as_str() {
+                "PayoutStarted" => {
+                    let era = event_field(event, "era_index", 0).and_then(json_u32);
+                    let validator = event_field(event, "validator_stash", 1).and_then(json_string);
+                    payout = era
+                        .zip(validator)
+                        .map(|(era, validator)| (event.extrinsic_index, era, validator));
+                }
+                "Rewarded" | "Reward" => {
+                    let Some((_, era, validator)) =
+                        payout.as_ref().filter(|(extrinsic_index, _, _)| {
+                            *extrinsic_index == event.extrinsic_index
+                        })
+                    else {
+                        println!(
+                            "⚠️  Reward without payout in block {} event {}",
+                            block_number, event_index
+                        );
+                        continue;
+                    };
+                    let stash = event_field(event, "stash", 0).and_then(json_string);
+                    let amount = event_field(event, "amount", 1).and_then(json_to_u128);
+                    let (Some(stash), Some(amount)) = (stash, amount) else {
+                        continue;
+                    };
+
+                    staking
+                        .save_reward(&StakingReward {
+                            era: *era,
+                            validator: validator.clone(),
+                            is_validator: stash == *validator,
+                            stash,
+                            amount,
+                            block_number,
+                            event_index,
+                            extrinsic_index: event.extrinsic_index,
+                            timestamp,
+                        })
+                        .await?;
+                }
+                "Slashed" | "Slash" => {
+                    let staker = event_field(event, "staker", 0).and_then(json_string);
+                    let amount = event_field(event, "amount", 1).and_then(json_to_u128);
+                    let (Some(staker), Some(amount)) = (staker, amount) else {
+                        continue;
+                    };
+
+                    let era = match active_era {
+                        Some(era) => era,
+                        None => *active_era.insert(api.active_era().await?),
+                    };
+
+                    staking
+                        .save_slash(&StakingSlash {
+                            era,
+                            staker,
+                            amount,
+                            block_number,
+                            event_index,
+                            timestamp,
+                        })
+                        .await?;
+                }
+                "EraPaid" | "EraPayout" => {
+                    let era = event_field(event, "era_index", 0).and_then(json_u32);
+                    let validator_payout =
+                        event_field(event, "validator_payout", 1).and_then(json_to_u128);
+                    let remainder = event_field(event, "remainder", 2).and_then(json_to_u128);
+                    let (Some(era), Some(validator_payout)) = (era, validator_payout) else {
+                        continue;
+                    };
+
+                    self.save_era_payout(
+                        api,
+                        era,
+                        validator_payout,
+                        remainder.unwrap_or_default(),
+                        block_number,
+                        timestamp,
+                    )
+                    .await?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Saves the payout of an era together with the reward points of its validators,
+    /// each validator's share of the payout is proportional to its points
+    async fn save_era_payout(
+        &self,
+        api: &SubstrtaeBlockQuery,
+        era: u32,
+        validator_payout: u128,
+        remainder: u128,
+        block_number: u32,
+        timestamp: u64,
+    ) -> Result<(), ServiceError> {
+        let staking = self.db_service.staking();
+        let points = api.era_reward_points(era).await?;
+
+        staking
+            .save_era_payout(&EraPayout {
+                era,
+                validator_payout,
+                remainder,
+                total_points: points.total,
+                block_number,
+                timestamp,
+            })
+            .await?;
+
+        for validator in &points.validators {
+            let reward = if points.total == 0 {
+                0
+            } else {
+                validator_payout * validator.points as u128 / points.total as u128
+            };
+
+            staking
+                .save_validator_points(&EraValidatorPoints {
+                    era,
+                    validator: validator.account_id.clone(),
+                    points: validator.points,
+                    commission: validator.commission,
+                    reward,
+                    block_number,
+                    timestamp,
+                })
+                .await?;
+        }
+
+        println!(
+            "💰 Era {} paid {} to {} validators",
+            era,
+            validator_payout,
+            points.validators.len()
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,536 @@
+use std::collections::{HashMap, HashSet};
+
+use blockscan::json_to_u128;
+use blockscan_model::event::FormattedEvent;
+use config::{SUBSTRATE_INDEXER, TRANSFER_BACKFILL};
+use custom_error::ServiceError;
+use models::substrate::{SubstrateEvent, SubstrateExtrinsic, SubstrateTransfer};
+use serde_json::Value as JsonValue;
+
+use crate::{
+    block_process::BlockProcessingService,
+    staking_process::{event_field, json_string, json_u32},
+};
+
+/// Modules of the events transfers are built from
+const TRANSFER_MODULES: [&str; 3] = ["Balances", "System", "Utility"];
+
+/// Batches that go on after a failed call, so the extrinsic itself succeeds
+const BATCH_CALLS: [&str; 2] = ["batch", "force_batch"];
+
+const TRANSFER_CALLS: [&str; 5] = [
+    "transfer",
+    "transfer_allow_death",
+    "transfer_keep_alive",
+    "transfer_all",
+    "force_transfer",
+];
+
+/// Account of a `MultiAddress` argument, decoded as `{"Id": address}`
+fn json_address(value: &JsonValue) -> Option<String> {
+    match value {
+        JsonValue::String(address) => Some(address.clone()),
+        JsonValue::Object(fields) => fields.get("Id").and_then(json_string),
+        _ => None,
+    }
+}
+
+/// Balance transfer calls of a call tree, descending into batches and other
+/// calls that wrap a `call` or `calls` argument
+fn collect_transfer_calls<'a>(
+    pallet: &str,
+    call: &str,
+    args: &'a JsonValue,
+    calls: &mut Vec<&'a JsonValue>,
+) {
+    if pallet == "Balances" && TRANSFER_CALLS.contains(&call) {
+        calls.push(args);
+        return;
+    }
+
+    let nested = args
+        .get("calls")
+        .and_then(JsonValue::as_array)
+        .map(|calls| calls.iter().collect())
+        .or_else(|| args.get("call").map(|call| vec![call]))
+        .unwrap_or_default();
+
+    for inner in nested {
+        collect_nested_transfer_calls(inner, calls);
+    }
+}
+
+/// Balance transfer calls of a call argument, decoded as `{"pallet", "call", "args"}`
+fn collect_nested_transfer_calls<'a>(inner: &'a JsonValue, calls: &mut Vec<&'a JsonValue>) {
+    if let (Some(pallet), Some(call), Some(args)) = (
+        inner.get("pallet").and_then(JsonValue::as_str),
+        inner.get("call").and_then(JsonValue::as_str),
+        inner.get("args"),
+    ) {
+        collect_transfer_calls(pallet, call, args, calls);
+    }
+}
+
+/// Inner calls of an extrinsic that is a `Utility.batch` or `Utility.force_batch`
+fn batch_calls(extrinsic: &SubstrateExtrinsic) -> Option<&Vec<JsonValue>> {
+    if extrinsic.call_module == "Utility" && BATCH_CALLS.contains(&extrinsic.call_function.as_str())
+    {
+        extrinsic.args.get("calls").and_then(JsonValue::as_array)
+    } else {
+        None
+    }
+}
+
+/// Event read back from the database, in the form events are indexed from
+fn formatted_event(event: &SubstrateEvent) -> FormattedEvent {
+    FormattedEvent {
+        index: event.event_index as usize,
+        phase: event.phase.clone(),
+        extrinsic_index: event.extrinsic_index,
+        pallet: event.module.clone(),
+        variant: event.event.clone(),
+        fields: event.data.clone(),
+        accounts: event.accounts.clone(),
+        topics: Vec::new(),
+    }
+}
+
+/// Transfers of a block in event order. The fee of an extrinsic is only attached to its
+/// first transfer, so summing the fees of transfers counts it once
+struct BlockTransfers {
+    block_number: u32,
+    timestamp: u64,
+    fee_attached: HashSet<u32>,
+    transfers: Vec<SubstrateTransfer>,
+}
+
+impl BlockTransfers {
+    fn push(
+        &mut self,
+        source: Option<&SubstrateExtrinsic>,
+        event_index: Option<u32>,
+        (from, to, amount): (String, String, u128),
+        success: bool,
+    ) {
+        let fee = source
+            .filter(|extrinsic| self.fee_attached.insert(extrinsic.extrinsic_index))
+            .and_then(|extrinsic| extrinsic.actual_fee);
+
+        self.transfers.push(SubstrateTransfer {
+            block_number: self.block_number,
+            transfer_index: self.transfers.len() as u32,
+            extrinsic_index: source.map(|extrinsic| extrinsic.extrinsic_index),
+            event_index,
+            from,
+            to,
+            amount,
+            success,
+            fee,
+            timestamp: self.timestamp,
+            from_display: None,
+            to_display: None,
+        });
+    }
+
+    /// Transfer calls that did not execute, recorded from the call arguments since
+    /// they emitted no event
+    fn push_failed(&mut self, failed: &SubstrateExtrinsic, calls: Vec<&JsonValue>) {
+        let Some(signer) = &failed.signer else {
+            return;
+        };
+
+        for args in calls {
+            let from = args
+                .get("source")
+                .and_then(json_address)
+                .unwrap_or_else(|| signer.clone());
+            let Some(to) = args.get("dest").and_then(json_address) else {
+                continue;
+            };
+            // `transfer_all` moves the whole free balance, unknown once it failed
+            let amount = args.get("value").and_then(json_to_u128).unwrap_or_default();
+
+            self.push(Some(failed), None, (from, to, amount), false);
+        }
+    }
+}
+
+// Native balance transfers
+impl BlockProcessingService {
+    /// Builds the transfers of a block in event order. Every executed transfer emits
+    /// `Balances.Transfer`, whatever call or batch it came from. A failed extrinsic, the
+    /// call that interrupted a `batch` and a failed item of a `force_batch` emit none, so
+    /// their transfer calls are recorded from the call arguments where they failed
+    fn block_transfers(
+        block_number: u32,
+        timestamp: u64,
+        events: &[FormattedEvent],
+        extrinsics: &[SubstrateExtrinsic],
+    ) -> Vec<SubstrateTransfer> {
+        let extrinsic = |index: Option<u32>| {
+            index.and_then(|index| {
+                extrinsics
+                    .iter()
+                    .find(|extrinsic| extrinsic.extrinsic_index == index)
+            })
+        };
+
+        let mut block = BlockTransfers {
+            block_number,
+            timestamp,
+            fee_attached: HashSet::new(),
+            transfers: Vec::new(),
+        };
+        // Items of each `force_batch` completed or failed so far
+        let mut batch_items: HashMap<u32, usize> = HashMap::new();
+
+        for event in events {
+            let source = extrinsic(event.extrinsic_index);
+
+            match (event.pallet.as_str(), event.variant.as_str()) {
+                ("Balances", "Transfer") => {
+                    let from = event_field(event, "from", 0).and_then(json_string);
+                    let to = event_field(event, "to", 1).and_then(json_string);
+                    let amount = event_field(event, "amount", 2).and_then(json_to_u128);
+                    let (Some(from), Some(to), Some(amount)) = (from, to, amount) else {
+                        continue;
+                    };
+
+                    let success = source.is_none_or(|extrinsic| extrinsic.success);
+                    block.push(
+                        source,
+                        Some(event.index as u32),
+                        (from, to, amount),
+                        success,
+                    );
+                }
+                ("System", "ExtrinsicFailed") => {
+                    let Some(failed) = source else {
+                        continue;
+                    };
+
+                    let mut calls = Vec::new();
+                    collect_transfer_calls(
+                        &failed.call_module,
+                        &failed.call_function,
+                        &failed.args,
+                        &mut calls,
+                    );
+                    block.push_failed(failed, calls);
+                }
+                ("Utility", "BatchInterrupted") => {
+                    let index = event_field(event, "index", 0).and_then(json_u32);
+                    let (Some(batch), Some(index)) = (source, index) else {
+                        continue;
+                    };
+
+                    // The calls after the interrupted one never ran
+                    let mut calls = Vec::new();
+                    if let Some(call) =
+                        batch_calls(batch).and_then(|calls| calls.get(index as usize))
+                    {
+                        collect_nested_transfer_calls(call, &mut calls);
+                    }
+                    block.push_failed(batch, calls);
+                }
+                ("Utility", variant @ ("ItemCompleted" | "ItemFailed")) => {
+                    let Some(batch) = source else {
+                        continue;
+                    };
+
+                    let item = batch_items.entry(batch.extrinsic_index).or_default();
+                    let position = *item;
+                    *item += 1;
+
+                    if variant == "ItemFailed" {
+                        let mut calls = Vec::new();
+                        if let Some(call) = batch_calls(batch).and_then(|calls| calls.get(position))
+                        {
+                            collect_nested_transfer_calls(call, &mut calls);
+                        }
+                        block.push_failed(batch, calls);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        block.transfers
+    }
+
+    pub async fn process_transfers(
+        &self,
+        block_number: u32,
+        timestamp: u64,
+        events: &[FormattedEvent],
+        extrinsics: &[SubstrateExtrinsic],
+    ) -> Result<(), ServiceError> {
+        let transfers = Self::block_transfers(block_number, timestamp, events, extrinsics);
+        self.save_transfers(block_number, &transfers).await
+    }
+
+    /// Replaces the transfers left by an earlier attempt at the block
+    async fn save_transfers(
+        &self,
+        block_number: u32,
+        transfers: &[SubstrateTransfer],
+    ) -> Result<(), ServiceError> {
+        let transfer_service = self.db_service.substrate_transfers();
+        transfer_service
+            .delete_by_block_number(block_number)
+            .await?;
+        if transfers.is_empty() {
+            return Ok(());
+        }
+
+        let saved = transfer_service.save_batch(transfers).await?;
+        println!("💸 Saved {} transfers for block {}", saved, block_number);
+
+        Ok(())
+    }
+
+    /// Builds the transfers of the blocks indexed before transfers were, from their stored
+    /// events and extrinsics. Resumes from the last block it finished and stops at the
+    /// substrate checkpoint, the blocks above it get their transfers when indexed
+    pub async fn backfill_transfers(&self) -> Result<usize, ServiceError> {
+        const PAGE_BLOCKS: u64 = 100;
+        let sync_state = self.db_service.sync_state();
+
+        let Some(end) = sync_state
+            .get(SUBSTRATE_INDEXER)
+            .await?
+            .and_then(|state| state.highest_contiguous_block)
+        else {
+            return Ok(0);
+        };
+        let mut from = sync_state
+            .resume_block(TRANSFER_BACKFILL)
+            .await?
+            .unwrap_or(0);
+        let mut saved = 0;
+
+        while from <= end {
+            let to = end.min(from + PAGE_BLOCKS - 1);
+            let (events, extrinsics) = tokio::try_join!(
+                self.db_service.substrate_events().get_by_block_range(
+                    from as u32,
+                    to as u32,
+                    &TRANSFER_MODULES
+                ),
+                self.db_service
+                    .substrate_extrinsics()
+                    .get_by_block_range(from as u32, to as u32),
+            )?;
+
+            let mut blocks: HashMap<u32, Vec<FormattedEvent>> = HashMap::new();
+            let mut timestamps = HashMap::new();
+            for event in &events {
+                blocks
+                    .entry(event.block_number)
+                    .or_default()
+                    .push(formatted_event(event));
+                timestamps.insert(event.block_number, event.timestamp);
+            }
+
+            for (block_number, block_events) in blocks {
+                let block_extrinsics: Vec<SubstrateExtrinsic> = extrinsics
+                    .iter()
+                    .filter(|extrinsic| extrinsic.block_number == block_number)
+                    .cloned()
+                    .collect();
+                let transfers = Self::block_transfers(
+                    block_number,
+                    timestamps[&block_number],
+                    &block_events,
+                    &block_extrinsics,
+                );
+                if !transfers.is_empty() {
+                    self.save_transfers(block_number, &transfers).await?;
+                    saved += transfers.len();
+                }
+            }
+
+            sync_state
+                .set_highest_contiguous_block(TRANSFER_BACKFILL, to)
+                .await?;
+            from = to + 1;
+        }
+
+        Ok(saved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+    const BOB: &str = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+
+    fn event(
+        index: usize,
+        extrinsic_index: u32,
+        pallet: &str,
+        variant: &str,
+        fields: JsonValue,
+    ) -> FormattedEvent {
+        FormattedEvent {
+            index,
+            phase: format!("Extrinsic #{}", extrinsic_index),
+            extrinsic_index: Some(extrinsic_index),
+            pallet: pallet.to_string(),
+            variant: variant.to_string(),
+            fields,
+            accounts: Vec::new(),
+            topics: Vec::new(),
+        }
+    }
+
+    fn transfer_event(index: usize, extrinsic_index: u32, amount: u128) -> FormattedEvent {
+        event(
+            index,
+            extrinsic_index,
+            "Balances",
+            "Transfer",
+            json!({ "from": ALICE, "to": BOB, "amount": amount.to_string() }),
+        )
+    }
+
+    fn extrinsic(
+        index: u32,
+        module: &str,
+        function: &str,
+        args: JsonValue,
+        success: bool,
+    ) -> SubstrateExtrinsic {
+        SubstrateExtrinsic {
+            block_number: 1,
+            extrinsic_index: index,
+            is_signed: true,
+            signer: Some(ALICE.to_string()),
+            call_module: module.to_string(),
+            call_function: function.to_string(),
+            args,
+            success,
+            dispatch_error: None,
+            actual_fee: Some(1_000),
+            tip: None,
+            weight: None,
+            timestamp: 0,
+            signer_display: None,
+        }
+    }
+
+    fn transfer_call(value: u128) -> JsonValue {
+        json!({
+            "pallet": "Balances",
+            "call": "transfer_keep_alive",
+            "args": { "dest": { "Id": BOB }, "value": value.to_string() },
+        })
+    }
+
+    fn transfers(
+        events: &[FormattedEvent],
+        extrinsics: &[SubstrateExtrinsic],
+    ) -> Vec<SubstrateTransfer> {
+        BlockProcessingService::block_transfers(1, 0, events, extrinsics)
+    }
+
+    #[test]
+    fn fee_is_attached_to_the_first_transfer_of_an_extrinsic() {
+        let batch = extrinsic(
+            1,
+            "Utility",
+            "batch_all",
+            json!({ "calls": [transfer_call(5), transfer_call(7)] }),
+            true,
+        );
+        let found = transfers(
+            &[transfer_event(2, 1, 5), transfer_event(3, 1, 7)],
+            &[batch],
+        );
+
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].fee, Some(1_000));
+        assert_eq!(found[1].fee, None);
+        assert_eq!(found[1].amount, 7);
+        assert!(found.iter().all(|transfer| transfer.success));
+    }
+
+    #[test]
+    fn failed_extrinsic_transfers_follow_event_order() {
+        let failed = extrinsic(
+            1,
+            "Balances",
+            "transfer_keep_alive",
+            transfer_call(9)["args"].clone(),
+            false,
+        );
+        let sent = extrinsic(2, "Balances", "transfer_keep_alive", json!({}), true);
+        let events = [
+            event(1, 1, "System", "ExtrinsicFailed", json!({})),
+            transfer_event(2, 2, 3),
+        ];
+        let found = transfers(&events, &[failed, sent]);
+
+        assert_eq!(found.len(), 2);
+        assert_eq!((found[0].transfer_index, found[0].success), (0, false));
+        assert_eq!((found[0].amount, found[0].event_index), (9, None));
+        assert_eq!(found[0].from, ALICE);
+        assert_eq!(found[0].to, BOB);
+        assert_eq!((found[1].transfer_index, found[1].success), (1, true));
+        assert_eq!(found[1].extrinsic_index, Some(2));
+    }
+
+    #[test]
+    fn interrupted_batch_records_the_failed_call() {
+        let batch = extrinsic(
+            1,
+            "Utility",
+            "batch",
+            json!({ "calls": [transfer_call(5), transfer_call(7), transfer_call(11)] }),
+            true,
+        );
+        let events = [
+            transfer_event(2, 1, 5),
+            event(
+                3,
+                1,
+                "Utility",
+                "BatchInterrupted",
+                json!({ "index": 1, "error": {} }),
+            ),
+        ];
+        let found = transfers(&events, &[batch]);
+
+        assert_eq!(found.len(), 2);
+        assert!(found[0].success);
+        assert_eq!((found[1].amount, found[1].success), (7, false));
+        assert_eq!(found[1].fee, None);
+    }
+
+    #[test]
+    fn force_batch_records_failed_items() {
+        let batch = extrinsic(
+            1,
+            "Utility",
+            "force_batch",
+            json!({ "calls": [transfer_call(5), transfer_call(7), transfer_call(11)] }),
+            true,
+        );
+        let events = [
+            event(2, 1, "Utility", "ItemFailed", json!({ "error": {} })),
+            transfer_event(3, 1, 7),
+            event(4, 1, "Utility", "ItemCompleted", json!({})),
+            transfer_event(5, 1, 11),
+            event(6, 1, "Utility", "ItemCompleted", json!({})),
+        ];
+        let found = transfers(&events, &[batch]);
+
+        let amounts: Vec<(u128, bool)> = found.iter().map(|t| (t.amount, t.success)).collect();
+        assert_eq!(amounts, vec![(5, false), (7, true), (11, true)]);
+        assert_eq!(found[0].fee, Some(1_000));
+        assert_eq!(found[1].fee, None);
+    }
+}
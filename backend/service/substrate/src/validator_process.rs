@@ -0,0 +1,63 @@
+use blockscan::SubstrtaeBlockQuery;
+use blockscan_model::event::FormattedEvent;
+use custom_error::ServiceError;
+use models::substrate::{SessionBlocks, ValidatorSnapshot};
+
+use crate::block_process::BlockProcessingService;
+
+// Per-era validator snapshots
+impl BlockProcessingService {
+    /// Snapshots the validators when a block starts a new session. The state is read at the
+    /// parent block, the last one of the finished session, whose block counts are still stored
+    pub async fn process_session_validators(
+        &self,
+        block_number: u32,
+        events: &[FormattedEvent],
+    ) -> Result<(), ServiceError> {
+        let new_session = events
+            .iter()
+            .any(|event| event.pallet == "Session" && event.variant == "NewSession");
+        if !new_session || block_number == 0 {
+            return Ok(());
+        }
+
+        let snapshot_block = block_number - 1;
+        let api = SubstrtaeBlockQuery::new(self.client.clone(), Some(snapshot_block)).await?;
+        let timestamp = api.get_block_timestamp().await?;
+        let session = api.session_validators().await?;
+
+        let snapshot_service = self.db_service.validator_snapshots();
+        for validator in &session.validators {
+            let active = &validator.validator;
+            snapshot_service
+                .save(&ValidatorSnapshot {
+                    era: session.era,
+                    account_id: active.account_id.clone(),
+                    validator_type: active.validator_type.clone(),
+                    commission: active.prefs.commission,
+                    blocked: active.prefs.blocked,
+                    total_stake: active.staking_info.total,
+                    own_stake: active.staking_info.own,
+                    nominator_count: active.staking_info.nominator_count,
+                    blocks_produced: validator.blocks,
+                    session_blocks: vec![SessionBlocks {
+                        session: session.session,
+                        blocks: validator.blocks,
+                    }],
+                    block_number: snapshot_block,
+                    timestamp,
+                    display_name: None,
+                })
+                .await?;
+        }
+
+        println!(
+            "🛡️  Saved {} validators of era {} session {}",
+            session.validators.len(),
+            session.era,
+            session.session
+        );
+
+        Ok(())
+    }
+}
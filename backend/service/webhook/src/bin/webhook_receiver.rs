@@ -0,0 +1,114 @@
+//! Local stand-in for a webhook endpoint. Verifies the signature of every
+//! delivery and prints it, and can fail on purpose to exercise retries.
+//!
+//! - `WEBHOOK_RECEIVER_PORT`: port to listen on (default 4000)
+//! - `WEBHOOK_SECRET`: secret returned when the webhook was registered
+//! - `WEBHOOK_RECEIVER_FAIL_FIRST`: attempts of each delivery answered with 500 (default 0)
+
+use std::{
+    collections::HashMap,
+    env,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use axum::{
+    Router,
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    routing::post,
+};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use tokio::net::TcpListener;
+
+struct Receiver {
+    secret: String,
+    fail_first: u32,
+    attempts: Mutex<HashMap<String, u32>>,
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|value| value.to_str().ok())
+}
+
+/// Checks `X-Webhook-Signature` against the HMAC-SHA256 of `timestamp.body`
+fn verify(secret: &str, timestamp: &str, body: &[u8], signature: &str) -> bool {
+    let Some(signature) = signature
+        .strip_prefix("sha256=")
+        .and_then(|signature| hex::decode(signature).ok())
+    else {
+        return false;
+    };
+
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(body);
+
+    mac.verify_slice(&signature).is_ok()
+}
+
+async fn receive(
+    State(receiver): State<Arc<Receiver>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> StatusCode {
+    let delivery = header(&headers, "X-Webhook-Delivery").unwrap_or_default();
+    let timestamp = header(&headers, "X-Webhook-Timestamp").unwrap_or_default();
+    let signature = header(&headers, "X-Webhook-Signature").unwrap_or_default();
+
+    if !verify(&receiver.secret, timestamp, &body, signature) {
+        println!("❌ Invalid signature on delivery {}", delivery);
+        return StatusCode::UNAUTHORIZED;
+    }
+
+    let attempt = {
+        let mut attempts = receiver.attempts.lock().unwrap();
+        let attempt = attempts.entry(delivery.to_string()).or_default();
+        *attempt += 1;
+        *attempt
+    };
+    if attempt <= receiver.fail_first {
+        println!("💥 Failing delivery {} on attempt {}", delivery, attempt);
+        return StatusCode::INTERNAL_SERVER_ERROR;
+    }
+
+    println!(
+        "📬 Delivery {} (attempt {}): {}",
+        delivery,
+        attempt,
+        String::from_utf8_lossy(&body)
+    );
+    StatusCode::OK
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    dotenv::dotenv().ok();
+
+    let port: u16 = env::var("WEBHOOK_RECEIVER_PORT")
+        .ok()
+        .and_then(|port| port.parse().ok())
+        .unwrap_or(4000);
+    let receiver = Arc::new(Receiver {
+        secret: env::var("WEBHOOK_SECRET").expect("WEBHOOK_SECRET must be set"),
+        fail_first: env::var("WEBHOOK_RECEIVER_FAIL_FIRST")
+            .ok()
+            .and_then(|count| count.parse().ok())
+            .unwrap_or(0),
+        attempts: Mutex::new(HashMap::new()),
+    });
+
+    let app = Router::new().route("/", post(receive)).with_state(receiver);
+    let addr = SocketAddr::from(([127, 0, 0, 1], port));
+    let listener = TcpListener::bind(addr).await?;
+
+    println!("🎯 Webhook receiver listening on http://{}", addr);
+    axum::serve(listener, app).await?;
+
+    Ok(())
+}
@@ -0,0 +1,282 @@
+use std::{
+    net::{IpAddr, SocketAddr},
+    time::{Duration, Instant},
+};
+
+use config::WEBHOOK_ALLOW_PRIVATE_URLS;
+use custom_error::ServiceError;
+use futures::{StreamExt, stream};
+use hmac::{Hmac, Mac};
+use models::webhook::{DeliveryAttempt, DeliveryStatus, WebhookDelivery, is_public_ip};
+use reqwest::{
+    Url,
+    dns::{Addrs, Name, Resolve, Resolving},
+};
+use sha2::Sha256;
+use tokio::{net::lookup_host, time::timeout};
+
+use crate::webhook_process::{WebhookProcessingService, now_millis};
+
+pub const SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+pub const TIMESTAMP_HEADER: &str = "X-Webhook-Timestamp";
+pub const DELIVERY_HEADER: &str = "X-Webhook-Delivery";
+
+#[derive(Debug, Clone)]
+pub struct DeliveryConfig {
+    /// Attempts made before a delivery is moved to the dead-letter table
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled after every failed attempt
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub timeout: Duration,
+    /// Deliveries sent at the same time
+    pub concurrency: usize,
+    /// Longest wait for a queued delivery before due retries are checked again
+    pub poll_interval: Duration,
+}
+
+impl Default for DeliveryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 6,
+            base_delay: Duration::from_secs(10),
+            max_delay: Duration::from_secs(3600),
+            timeout: Duration::from_secs(10),
+            concurrency: 8,
+            poll_interval: Duration::from_secs(5),
+        }
+    }
+}
+
+impl DeliveryConfig {
+    /// Delay after the failed attempt number `attempt`, starting at 1
+    pub fn retry_delay(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
+
+/// Hex HMAC-SHA256 of `timestamp.body` under the webhook secret
+pub fn sign(secret: &str, timestamp: u128, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Resolves webhook hosts for the HTTP client and refuses a host with any address that is
+/// not public, so a name re-pointed after registration cannot reach internal services
+pub struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addresses: Vec<SocketAddr> = lookup_host((name.as_str(), 0)).await?.collect();
+            if addresses.iter().any(|address| !is_public_ip(address.ip())) {
+                return Err(format!("{} resolves to a non-public address", name.as_str()).into());
+            }
+
+            let addresses: Addrs = Box::new(addresses.into_iter());
+            Ok::<_, Box<dyn std::error::Error + Send + Sync>>(addresses)
+        })
+    }
+}
+
+/// Refuses a URL whose host is a non-public IP address, hosts given by name are checked
+/// by `PublicResolver` when connecting
+fn check_url(url: &str) -> Result<(), String> {
+    if *WEBHOOK_ALLOW_PRIVATE_URLS {
+        return Ok(());
+    }
+
+    let url = Url::parse(url).map_err(|e| format!("Invalid URL: {}", e))?;
+    let host = url.host_str().ok_or("URL has no host")?;
+    match host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse::<IpAddr>()
+    {
+        Ok(ip) if !is_public_ip(ip) => Err(format!("{} is not a public address", ip)),
+        _ => Ok(()),
+    }
+}
+
+// Signed delivery with retries and dead letters
+impl WebhookProcessingService {
+    /// Sends the due deliveries whenever one is queued or a retry comes due
+    pub async fn deliver_pending(&self) {
+        loop {
+            if let Err(e) = self.deliver_due().await {
+                println!("❌ Failed to deliver webhooks: {}", e);
+            }
+
+            // A notification sent while delivering is kept, so nothing queued meanwhile waits
+            let _ = timeout(self.config.poll_interval, self.queued.notified()).await;
+        }
+    }
+
+    async fn deliver_due(&self) -> Result<(), ServiceError> {
+        loop {
+            let batch_size = (self.config.concurrency * 4) as u32;
+            let due = self
+                .db_service
+                .webhook_deliveries()
+                .get_due(now_millis(), batch_size)
+                .await?;
+            if due.is_empty() {
+                return Ok(());
+            }
+
+            let full_batch = due.len() as u32 == batch_size;
+            stream::iter(due)
+                .for_each_concurrent(self.config.concurrency, |delivery| async move {
+                    let id = delivery.id.clone();
+                    if let Err(e) = self.attempt(delivery).await {
+                        println!("❌ Failed to record delivery {}: {}", id, e);
+                    }
+                })
+                .await;
+
+            if !full_batch {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Makes one attempt and records its outcome on the delivery
+    async fn attempt(&self, mut delivery: WebhookDelivery) -> Result<(), ServiceError> {
+        let webhook = self.db_service.webhooks().get(&delivery.webhook_id).await?;
+        let attempted_at = now_millis();
+        let started = Instant::now();
+
+        let (response_status, error) = match &webhook {
+            Some(webhook) => self.post(&webhook.secret, &delivery, attempted_at).await,
+            None => (None, Some("Webhook was deleted".to_string())),
+        };
+
+        let attempt = delivery.attempts.len() as u32 + 1;
+        let delivered = error.is_none();
+        delivery.attempts.push(DeliveryAttempt {
+            attempt,
+            attempted_at,
+            response_status,
+            error,
+            duration_ms: started.elapsed().as_millis() as u64,
+        });
+
+        if delivered {
+            delivery.status = DeliveryStatus::Delivered;
+            delivery.delivered_at = Some(now_millis());
+            println!(
+                "📬 Delivered {} to {} (attempt {})",
+                delivery.id, delivery.url, attempt
+            );
+        } else if webhook.is_none() || attempt >= self.config.max_attempts {
+            delivery.status = DeliveryStatus::DeadLetter;
+            self.db_service
+                .webhook_deliveries()
+                .save_dead_letter(&delivery)
+                .await?;
+            println!(
+                "💀 Delivery {} to {} moved to dead letters after {} attempts",
+                delivery.id, delivery.url, attempt
+            );
+        } else {
+            let delay = self.config.retry_delay(attempt);
+            delivery.next_attempt_at = now_millis() + delay.as_millis();
+            println!(
+                "🔁 Delivery {} to {} failed (attempt {}), retrying in {}s",
+                delivery.id,
+                delivery.url,
+                attempt,
+                delay.as_secs()
+            );
+        }
+
+        self.db_service.webhook_deliveries().save(&delivery).await
+    }
+
+    /// Response status and the error of a failed attempt, any 2xx status is a success
+    async fn post(
+        &self,
+        secret: &str,
+        delivery: &WebhookDelivery,
+        timestamp: u128,
+    ) -> (Option<u16>, Option<String>) {
+        if let Err(e) = check_url(&delivery.url) {
+            return (None, Some(e));
+        }
+
+        let body = match serde_json::to_vec(&delivery.payload) {
+            Ok(body) => body,
+            Err(e) => return (None, Some(format!("Payload encoding failed: {}", e))),
+        };
+
+        let response = self
+            .client
+            .post(&delivery.url)
+            .header("Content-Type", "application/json")
+            .header(DELIVERY_HEADER, &delivery.id)
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(SIGNATURE_HEADER, sign(secret, timestamp, &body))
+            .body(body)
+            .send()
+            .await;
+
+        match response {
+            Ok(response) if response.status().is_success() => {
+                (Some(response.status().as_u16()), None)
+            }
+            Ok(response) => (
+                Some(response.status().as_u16()),
+                Some(format!("Unexpected status {}", response.status())),
+            ),
+            Err(e) => (None, Some(e.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signature_covers_timestamp_and_body() {
+        let signature = sign("secret", 1706610600000, b"{}");
+
+        assert!(signature.starts_with("sha256="));
+        assert_eq!(signature.len(), "sha256=".len() + 64);
+        assert_eq!(signature, sign("secret", 1706610600000, b"{}"));
+        assert_ne!(signature, sign("secret", 1706610600001, b"{}"));
+        assert_ne!(signature, sign("other", 1706610600000, b"{}"));
+    }
+
+    #[test]
+    fn signature_is_hmac_sha256_of_timestamp_and_body() {
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"secret").unwrap();
+        mac.update(b"42.{\"a\":1}");
+        let expected = format!("sha256={}", hex::encode(mac.finalize().into_bytes()));
+
+        assert_eq!(sign("secret", 42, b"{\"a\":1}"), expected);
+    }
+
+    #[test]
+    fn retry_delay_doubles_up_to_the_maximum() {
+        let config = DeliveryConfig::default();
+
+        assert_eq!(config.retry_delay(1), Duration::from_secs(10));
+        assert_eq!(config.retry_delay(3), Duration::from_secs(40));
+        assert_eq!(config.retry_delay(20), Duration::from_secs(3600));
+    }
+
+    #[test]
+    fn private_ip_urls_are_refused() {
+        assert!(check_url("http://127.0.0.1:4000/").is_err());
+        assert!(check_url("http://[::1]/").is_err());
+        assert!(check_url("https://169.254.169.254/latest").is_err());
+        assert!(check_url("https://example.com/hooks").is_ok());
+    }
+}
@@ -0,0 +1,202 @@
+use models::{
+    live::{ChainEvent, same_address},
+    webhook::WebhookFilter,
+};
+
+/// Payload `event` name of the records webhooks are notified about, blocks are not
+pub fn event_name(event: &ChainEvent) -> Option<&'static str> {
+    match event {
+        ChainEvent::EvmTransaction(_) => Some("evm_transaction"),
+        ChainEvent::TokenTransfer(_) => Some("token_transfer"),
+        ChainEvent::SubstrateExtrinsic(_) => Some("substrate_extrinsic"),
+        ChainEvent::SubstrateEvent(_) => Some("substrate_event"),
+        ChainEvent::EvmBlock(_)
+        | ChainEvent::SubstrateBlock(_)
+        | ChainEvent::FinalizedEvmBlock(_)
+        | ChainEvent::FinalizedSubstrateBlock(_) => None,
+    }
+}
+
+fn any_address<'a>(filter: &str, mut addresses: impl Iterator<Item = &'a String>) -> bool {
+    addresses.any(|address| same_address(filter, address))
+}
+
+/// Whether the decimal string `value` is at least `min`, for amounts of any size
+pub fn decimal_at_least(value: &str, min: &str) -> bool {
+    let value = value.trim_start_matches('0');
+    let min = min.trim_start_matches('0');
+
+    (value.len(), value) >= (min.len(), min)
+}
+
+fn validator_event(
+    event: &ChainEvent,
+    pallet: &str,
+    names: &[&str],
+    validator: &Option<String>,
+) -> bool {
+    let ChainEvent::SubstrateEvent(event) = event else {
+        return false;
+    };
+
+    event.module == pallet
+        && names.contains(&event.event.as_str())
+        && validator
+            .as_ref()
+            .is_none_or(|validator| any_address(validator, event.accounts.iter()))
+}
+
+pub fn matches(filter: &WebhookFilter, event: &ChainEvent) -> bool {
+    match filter {
+        WebhookFilter::AddressActivity { address } => match event {
+            ChainEvent::EvmTransaction(transaction) => any_address(
+                address,
+                std::iter::once(&transaction.from).chain(&transaction.to),
+            ),
+            ChainEvent::TokenTransfer(transfer) => {
+                any_address(address, [&transfer.from, &transfer.to].into_iter())
+            }
+            ChainEvent::SubstrateExtrinsic(extrinsic) => {
+                any_address(address, extrinsic.signer.iter())
+            }
+            ChainEvent::SubstrateEvent(event) => any_address(address, event.accounts.iter()),
+            _ => false,
+        },
+        WebhookFilter::TokenTransfer { token, min_value } => {
+            let ChainEvent::TokenTransfer(transfer) = event else {
+                return false;
+            };
+
+            token
+                .as_ref()
+                .is_none_or(|token| same_address(token, &transfer.token_address))
+                && min_value.as_ref().is_none_or(|min_value| {
+                    transfer
+                        .value
+                        .as_ref()
+                        .is_some_and(|value| decimal_at_least(value, min_value))
+                })
+        }
+        WebhookFilter::PalletEvent {
+            pallet,
+            event: name,
+        } => {
+            let ChainEvent::SubstrateEvent(event) = event else {
+                return false;
+            };
+
+            event.module.eq_ignore_ascii_case(pallet)
+                && name
+                    .as_ref()
+                    .is_none_or(|name| event.event.eq_ignore_ascii_case(name))
+        }
+        WebhookFilter::ValidatorSlashed { validator } => {
+            validator_event(event, "Staking", &["Slashed", "Slash"], validator)
+        }
+        WebhookFilter::ValidatorOffline { validator } => {
+            validator_event(event, "CommitteeManagement", &["BanValidators"], validator)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use models::{
+        evm::{TokenStandard, TokenTransfer},
+        substrate::SubstrateEvent,
+    };
+
+    const TOKEN: &str = "0x5fbdb2315678afecb367f032d93f642f64180aa3";
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+    fn transfer(value: &str) -> ChainEvent {
+        ChainEvent::TokenTransfer(TokenTransfer {
+            transaction_hash: "0x01".to_string(),
+            block_number: 1,
+            log_index: 0,
+            batch_index: 0,
+            token_address: TOKEN.to_string(),
+            standard: TokenStandard::ERC20,
+            operator: None,
+            from: "0x742d35cc6634c0532925a3b844bc454e4438f44e".to_string(),
+            to: "0x0000000000000000000000000000000000000001".to_string(),
+            value: Some(value.to_string()),
+            token_id: None,
+            timestamp: 0,
+        })
+    }
+
+    fn substrate_event(module: &str, event: &str, accounts: &[&str]) -> ChainEvent {
+        ChainEvent::SubstrateEvent(SubstrateEvent {
+            block_number: 1,
+            event_index: 0,
+            phase: "Extrinsic #1".to_string(),
+            extrinsic_index: Some(1),
+            module: module.to_string(),
+            event: event.to_string(),
+            data: serde_json::Value::Null,
+            timestamp: 0,
+            accounts: accounts.iter().map(|account| account.to_string()).collect(),
+        })
+    }
+
+    #[test]
+    fn decimal_comparison_handles_any_size() {
+        assert!(decimal_at_least(
+            "1000000000000000000000",
+            "999999999999999999999"
+        ));
+        assert!(decimal_at_least("0042", "42"));
+        assert!(!decimal_at_least("99", "100"));
+    }
+
+    #[test]
+    fn token_transfer_filter_checks_token_and_minimum() {
+        let filter = WebhookFilter::TokenTransfer {
+            token: Some(TOKEN.to_uppercase().replace("0X", "0x")),
+            min_value: Some("1000".to_string()),
+        };
+
+        assert!(matches(&filter, &transfer("1000")));
+        assert!(!matches(&filter, &transfer("999")));
+        assert!(!matches(
+            &filter,
+            &substrate_event("Balances", "Transfer", &[])
+        ));
+    }
+
+    #[test]
+    fn address_activity_matches_evm_addresses_ignoring_case() {
+        let filter = WebhookFilter::AddressActivity {
+            address: "0x742D35CC6634C0532925A3B844BC454E4438F44E".to_string(),
+        };
+
+        assert!(matches(&filter, &transfer("1")));
+    }
+
+    #[test]
+    fn validator_filters_match_their_events() {
+        let slashed = WebhookFilter::ValidatorSlashed {
+            validator: Some(ALICE.to_string()),
+        };
+        assert!(matches(
+            &slashed,
+            &substrate_event("Staking", "Slashed", &[ALICE])
+        ));
+        assert!(!matches(
+            &slashed,
+            &substrate_event("Staking", "Slashed", &[])
+        ));
+
+        let pallet = WebhookFilter::PalletEvent {
+            pallet: "staking".to_string(),
+            event: None,
+        };
+        assert!(matches(
+            &pallet,
+            &substrate_event("Staking", "Rewarded", &[])
+        ));
+        assert_eq!(event_name(&transfer("1")), Some("token_transfer"));
+    }
+}
@@ -0,0 +1,37 @@
+pub mod delivery;
+pub mod filter;
+pub mod webhook_process;
+
+use config::{
+    DATABASE_NAMESPACE, DATABASE_PASSWORD, DATABASE_TABLE, DATABASE_URL, DATABASE_USERNAME,
+};
+use database::DatabaseService;
+use dotenv::dotenv;
+
+use crate::{delivery::DeliveryConfig, webhook_process::WebhookProcessingService};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    dotenv().ok();
+
+    println!("🚀 Starting Webhook Delivery Service");
+
+    let database = DatabaseService::new(
+        DATABASE_URL.as_str(),
+        DATABASE_USERNAME.as_str(),
+        DATABASE_PASSWORD.as_str(),
+        DATABASE_NAMESPACE.as_str(),
+        DATABASE_TABLE.as_str(),
+    )
+    .await?;
+
+    let processor = WebhookProcessingService::new(database, DeliveryConfig::default())?;
+
+    // Deliveries left pending by a previous run are sent first
+    let deliverer = processor.clone();
+    tokio::spawn(async move { deliverer.deliver_pending().await });
+
+    processor.follow_chain_events().await;
+
+    Ok(())
+}
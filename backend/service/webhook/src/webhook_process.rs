@@ -0,0 +1,211 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use config::{WEBHOOK_ALLOW_PRIVATE_URLS, WEBHOOK_EVM_MATCHER, WEBHOOK_SUBSTRATE_MATCHER};
+use custom_error::ServiceError;
+use database::DatabaseService;
+use models::{
+    live::{ChainEvent, Feed},
+    webhook::{DeliveryStatus, Webhook, WebhookDelivery},
+};
+use serde_json::json;
+use tokio::{sync::Notify, time::sleep};
+
+use crate::{
+    delivery::{DeliveryConfig, PublicResolver},
+    filter::{event_name, matches},
+};
+
+/// How long registered webhooks are cached before being read again
+const WEBHOOK_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+/// How often the indexer checkpoints are checked for new blocks
+const MATCH_INTERVAL: Duration = Duration::from_secs(2);
+/// Most blocks matched before the cursor is saved
+const MAX_MATCH_BLOCKS: u64 = 50;
+
+/// Feeds matched against webhooks, with the `sync_state` record of their cursor
+const MATCHED_FEEDS: [(Feed, &str); 2] = [
+    (Feed::Evm, WEBHOOK_EVM_MATCHER),
+    (Feed::Substrate, WEBHOOK_SUBSTRATE_MATCHER),
+];
+
+pub(crate) fn now_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0)
+}
+
+#[derive(Clone)]
+pub struct WebhookProcessingService {
+    pub db_service: DatabaseService,
+    pub client: reqwest::Client,
+    pub config: DeliveryConfig,
+    /// Wakes the delivery loop as soon as a payload is queued
+    pub queued: Arc<Notify>,
+}
+
+impl WebhookProcessingService {
+    pub fn new(db_service: DatabaseService, config: DeliveryConfig) -> Result<Self, ServiceError> {
+        let mut client = reqwest::Client::builder()
+            .timeout(config.timeout)
+            // A redirect could lead to an internal address the checks never saw
+            .redirect(reqwest::redirect::Policy::none());
+        if !*WEBHOOK_ALLOW_PRIVATE_URLS {
+            client = client.dns_resolver(Arc::new(PublicResolver));
+        }
+        let client = client
+            .build()
+            .map_err(|e| ServiceError::WebhookError(format!("HTTP client error: {}", e)))?;
+
+        Ok(Self {
+            db_service,
+            client,
+            config,
+            queued: Arc::new(Notify::new()),
+        })
+    }
+
+    /// Evaluates every registered filter against the indexed records, block by block from
+    /// a cursor kept in `sync_state`, and queues a delivery for each match. A restart
+    /// continues after the last matched block
+    pub async fn follow_chain_events(&self) {
+        let mut webhooks = None;
+        let mut refreshed_at = Instant::now();
+
+        loop {
+            if webhooks.is_none() || refreshed_at.elapsed() >= WEBHOOK_REFRESH_INTERVAL {
+                match self.db_service.webhooks().get_all().await {
+                    Ok(all) => {
+                        webhooks = Some(all);
+                        refreshed_at = Instant::now();
+                    }
+                    Err(e) => println!("⚠️  Failed to read webhooks: {}", e),
+                }
+            }
+
+            // The cursors only move once the webhooks could be read
+            if let Some(webhooks) = &webhooks {
+                for (feed, matcher) in MATCHED_FEEDS {
+                    if let Err(e) = self.match_new_blocks(feed, matcher, webhooks).await {
+                        println!("❌ Webhook matching on {} failed: {}", feed.name(), e);
+                    }
+                }
+            }
+
+            sleep(MATCH_INTERVAL).await;
+        }
+    }
+
+    /// Matches the blocks between the cursor of `matcher` and the checkpoint of the
+    /// indexer. The cursor is saved after each page, a failed page is matched again
+    async fn match_new_blocks(
+        &self,
+        feed: Feed,
+        matcher: &str,
+        webhooks: &[Webhook],
+    ) -> Result<(), ServiceError> {
+        let sync_state = self.db_service.sync_state();
+        let Some(height) = sync_state
+            .get(feed.indexer())
+            .await?
+            .and_then(|state| feed.height(&state))
+        else {
+            return Ok(());
+        };
+        let matched = sync_state
+            .get(matcher)
+            .await?
+            .and_then(|state| state.highest_contiguous_block);
+
+        let Some(matched) = matched else {
+            // Blocks indexed before webhooks were first matched are history
+            println!(
+                "👂 Matching {} records from block {}",
+                feed.name(),
+                height + 1
+            );
+            return sync_state
+                .set_highest_contiguous_block(matcher, height)
+                .await;
+        };
+        if height < matched {
+            // Blocks replaced by a reorganization are matched again, the records they
+            // kept are not delivered twice
+            println!(
+                "↩️  Matching {} records again from block {}",
+                feed.name(),
+                height + 1
+            );
+            return sync_state
+                .set_highest_contiguous_block(matcher, height)
+                .await;
+        }
+
+        let mut from = matched + 1;
+        while from <= height {
+            let to = height.min(from + MAX_MATCH_BLOCKS - 1);
+
+            for record in self.db_service.live().records(feed, from, to).await? {
+                for webhook in webhooks
+                    .iter()
+                    .filter(|webhook| matches(&webhook.filter, &record.event))
+                {
+                    self.enqueue(webhook, &record.event).await?;
+                }
+            }
+
+            sync_state.set_highest_contiguous_block(matcher, to).await?;
+            from = to + 1;
+        }
+
+        Ok(())
+    }
+
+    async fn enqueue(&self, webhook: &Webhook, event: &ChainEvent) -> Result<(), ServiceError> {
+        let Some(name) = event_name(event) else {
+            return Ok(());
+        };
+
+        let data = match event {
+            ChainEvent::EvmTransaction(transaction) => serde_json::to_value(transaction),
+            ChainEvent::TokenTransfer(transfer) => serde_json::to_value(transfer),
+            ChainEvent::SubstrateExtrinsic(extrinsic) => serde_json::to_value(extrinsic),
+            ChainEvent::SubstrateEvent(substrate_event) => serde_json::to_value(substrate_event),
+            _ => return Ok(()),
+        }
+        .map_err(|e| ServiceError::WebhookError(format!("Payload encoding failed: {}", e)))?;
+
+        // The same record always yields the same delivery for a webhook
+        let id = format!("{}:{}", webhook.id, event.record_id());
+        let created_at = now_millis();
+        let delivery = WebhookDelivery {
+            payload: json!({
+                "id": id,
+                "webhook_id": webhook.id,
+                "event": name,
+                "created_at": created_at as u64,
+                "data": data,
+            }),
+            id,
+            webhook_id: webhook.id.clone(),
+            url: webhook.url.clone(),
+            event: name.to_string(),
+            status: DeliveryStatus::Pending,
+            attempts: Vec::new(),
+            next_attempt_at: created_at,
+            created_at,
+            delivered_at: None,
+        };
+
+        self.db_service
+            .webhook_deliveries()
+            .create(&delivery)
+            .await?;
+        self.queued.notify_one();
+
+        Ok(())
+    }
+}